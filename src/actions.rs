@@ -0,0 +1,306 @@
+use reqwest::Method;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use crate::{error::Result, Element, Session};
+
+/// Special keys for [`Actions`], sent as the code points WebDriver reserves for them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    Backspace,
+    Tab,
+    Enter,
+    Shift,
+    Control,
+    Alt,
+    Escape,
+    Space,
+    PageUp,
+    PageDown,
+    End,
+    Home,
+    ArrowLeft,
+    ArrowUp,
+    ArrowRight,
+    ArrowDown,
+    Delete,
+    /// ⌘ on macOS, ⊞ on Windows
+    Meta,
+}
+
+impl From<Key> for char {
+    fn from(key: Key) -> char {
+        match key {
+            Key::Backspace => '\u{E003}',
+            Key::Tab => '\u{E004}',
+            Key::Enter => '\u{E007}',
+            Key::Shift => '\u{E008}',
+            Key::Control => '\u{E009}',
+            Key::Alt => '\u{E00A}',
+            Key::Escape => '\u{E00C}',
+            Key::Space => '\u{E00D}',
+            Key::PageUp => '\u{E00E}',
+            Key::PageDown => '\u{E00F}',
+            Key::End => '\u{E010}',
+            Key::Home => '\u{E011}',
+            Key::ArrowLeft => '\u{E012}',
+            Key::ArrowUp => '\u{E013}',
+            Key::ArrowRight => '\u{E014}',
+            Key::ArrowDown => '\u{E015}',
+            Key::Delete => '\u{E017}',
+            Key::Meta => '\u{E03D}',
+        }
+    }
+}
+
+/// Mouse button pressed by [`Actions`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+    Back,
+    Forward,
+}
+
+impl MouseButton {
+    fn code(self) -> u8 {
+        match self {
+            MouseButton::Left => 0,
+            MouseButton::Middle => 1,
+            MouseButton::Right => 2,
+            MouseButton::Back => 3,
+            MouseButton::Forward => 4,
+        }
+    }
+}
+
+/// One tick of an action chain
+#[derive(Debug, Clone)]
+enum Step {
+    Key(Value),
+    Pointer(Value),
+    Wheel(Value),
+    /// Every device waits
+    Pause(Duration),
+}
+
+/// A chain of keyboard, mouse and scroll-wheel input, run by [`Session::perform_actions`].
+///
+/// * Steps run one after another, in the order they were added.
+/// * Keys and buttons still held at the end stay pressed until
+///   [`Session::release_actions`].
+#[derive(Debug, Clone, Default)]
+pub struct Actions {
+    steps: Vec<Step>,
+}
+
+impl Actions {
+    /// An empty chain
+    pub fn new() -> Actions {
+        Actions::default()
+    }
+
+    /// Move the mouse to `x`/`y` in the viewport
+    pub fn move_to(self, x: i64, y: i64) -> Self {
+        self.pointer_move(json!("viewport"), x, y)
+    }
+
+    /// Move the mouse to the center of `element`, e.g. to open a hover menu
+    pub fn move_to_element(self, element: &Element) -> Self {
+        self.move_to_element_offset(element, 0, 0)
+    }
+
+    /// Move the mouse `x`/`y` away from the center of `element`
+    pub fn move_to_element_offset(self, element: &Element, x: i64, y: i64) -> Self {
+        self.pointer_move(json!(element), x, y)
+    }
+
+    /// Move the mouse by `x`/`y` from where it is
+    pub fn move_by(self, x: i64, y: i64) -> Self {
+        self.pointer_move(json!("pointer"), x, y)
+    }
+
+    fn pointer_move(self, origin: Value, x: i64, y: i64) -> Self {
+        self.pointer(
+            json!({ "type": "pointerMove", "duration": 0, "origin": origin, "x": x, "y": y }),
+        )
+    }
+
+    /// Press `button` where the mouse is
+    pub fn button_down(self, button: MouseButton) -> Self {
+        self.pointer(json!({ "type": "pointerDown", "button": button.code() }))
+    }
+
+    /// Release `button`
+    pub fn button_up(self, button: MouseButton) -> Self {
+        self.pointer(json!({ "type": "pointerUp", "button": button.code() }))
+    }
+
+    /// Left-click where the mouse is
+    pub fn click(self) -> Self {
+        self.button_down(MouseButton::Left)
+            .button_up(MouseButton::Left)
+    }
+
+    /// Move to the center of `element` and left-click it
+    pub fn click_element(self, element: &Element) -> Self {
+        self.move_to_element(element).click()
+    }
+
+    /// Left-click twice where the mouse is
+    pub fn double_click(self) -> Self {
+        self.click().click()
+    }
+
+    /// Right-click where the mouse is, e.g. to open a context menu
+    pub fn context_click(self) -> Self {
+        self.button_down(MouseButton::Right)
+            .button_up(MouseButton::Right)
+    }
+
+    /// Drag `source` onto the center of `target` with the left button
+    pub fn drag_and_drop(self, source: &Element, target: &Element) -> Self {
+        self.move_to_element(source)
+            .button_down(MouseButton::Left)
+            .move_to_element(target)
+            .button_up(MouseButton::Left)
+    }
+
+    /// Press `key`, a character or a [`Key`], and keep it held
+    pub fn key_down(self, key: impl Into<char>) -> Self {
+        self.key(json!({ "type": "keyDown", "value": key.into().to_string() }))
+    }
+
+    /// Release `key`
+    pub fn key_up(self, key: impl Into<char>) -> Self {
+        self.key(json!({ "type": "keyUp", "value": key.into().to_string() }))
+    }
+
+    /// Type `text` into the focused element, one key press per character
+    pub fn send_keys(mut self, text: &str) -> Self {
+        for key in text.chars() {
+            self = self.key_down(key).key_up(key);
+        }
+        self
+    }
+
+    /// Press `key` while holding `modifiers`, e.g. `key_chord(&[Key::Control], 'a')`
+    pub fn key_chord(mut self, modifiers: &[Key], key: impl Into<char>) -> Self {
+        let key = key.into();
+        for &modifier in modifiers {
+            self = self.key_down(modifier);
+        }
+        self = self.key_down(key).key_up(key);
+        for &modifier in modifiers.iter().rev() {
+            self = self.key_up(modifier);
+        }
+        self
+    }
+
+    /// Scroll by `delta_x`/`delta_y` with the wheel over `x`/`y` in the viewport
+    pub fn scroll(self, x: i64, y: i64, delta_x: i64, delta_y: i64) -> Self {
+        self.wheel(json!("viewport"), x, y, delta_x, delta_y)
+    }
+
+    /// Scroll by `delta_x`/`delta_y` with the wheel over the center of `element`
+    pub fn scroll_from_element(self, element: &Element, delta_x: i64, delta_y: i64) -> Self {
+        self.wheel(json!(element), 0, 0, delta_x, delta_y)
+    }
+
+    fn wheel(self, origin: Value, x: i64, y: i64, delta_x: i64, delta_y: i64) -> Self {
+        self.step(Step::Wheel(json!({
+            "type": "scroll",
+            "duration": 0,
+            "origin": origin,
+            "x": x,
+            "y": y,
+            "deltaX": delta_x,
+            "deltaY": delta_y,
+        })))
+    }
+
+    /// Wait `duration` before the next step
+    pub fn pause(self, duration: Duration) -> Self {
+        self.step(Step::Pause(duration))
+    }
+
+    fn key(self, action: Value) -> Self {
+        self.step(Step::Key(action))
+    }
+
+    fn pointer(self, action: Value) -> Self {
+        self.step(Step::Pointer(action))
+    }
+
+    fn step(mut self, step: Step) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// The chain as WebDriver input sources; each step is one tick, so every device gets
+    /// a pause while another one acts
+    fn to_json(&self) -> Value {
+        let used = |matches: fn(&Step) -> bool| self.steps.iter().any(matches);
+        let mut sources = Vec::new();
+        let keyboard = used(|step| matches!(step, Step::Key(_)));
+        let mouse = used(|step| matches!(step, Step::Pointer(_)));
+        let wheel = used(|step| matches!(step, Step::Wheel(_)));
+        let pick = |own: fn(&Step) -> Option<&Value>| -> Vec<Value> {
+            self.steps
+                .iter()
+                .map(|step| match (own(step), step) {
+                    (Some(action), _) => action.clone(),
+                    (None, Step::Pause(duration)) => {
+                        json!({ "type": "pause", "duration": duration.as_millis() as u64 })
+                    }
+                    (None, _) => json!({ "type": "pause", "duration": 0 }),
+                })
+                .collect()
+        };
+        // A chain of pauses still needs a device to wait on
+        if keyboard || !(mouse || wheel) {
+            let actions = pick(|step| match step {
+                Step::Key(action) => Some(action),
+                _ => None,
+            });
+            sources.push(json!({ "type": "key", "id": "keyboard", "actions": actions }));
+        }
+        if mouse {
+            let actions = pick(|step| match step {
+                Step::Pointer(action) => Some(action),
+                _ => None,
+            });
+            sources.push(json!({
+                "type": "pointer",
+                "id": "mouse",
+                "parameters": { "pointerType": "mouse" },
+                "actions": actions,
+            }));
+        }
+        if wheel {
+            let actions = pick(|step| match step {
+                Step::Wheel(action) => Some(action),
+                _ => None,
+            });
+            sources.push(json!({ "type": "wheel", "id": "wheel", "actions": actions }));
+        }
+        json!({ "actions": sources })
+    }
+}
+
+impl Session {
+    /// Run an [`Actions`] chain, e.g. a hover, a drag or a keyboard shortcut
+    pub async fn perform_actions(&self, actions: &Actions) -> Result<()> {
+        self.command(Method::POST, "/actions", Some(actions.to_json()))
+            .await
+            .map(drop)
+    }
+
+    /// Release every key and mouse button still held by earlier chains
+    pub async fn release_actions(&self) -> Result<()> {
+        self.command(Method::DELETE, "/actions", None)
+            .await
+            .map(drop)
+    }
+}
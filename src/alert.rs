@@ -0,0 +1,36 @@
+use reqwest::Method;
+use serde_json::json;
+
+use crate::{error::Result, Session};
+
+impl Session {
+    /// Press OK on the open `alert`, `confirm` or `prompt` dialog.
+    ///
+    /// * While a dialog is open most other commands fail with `unexpected alert open`;
+    ///   without one, these fail with `no such alert`.
+    pub async fn accept_alert(&self) -> Result<()> {
+        self.command(Method::POST, "/alert/accept", None)
+            .await
+            .map(drop)
+    }
+
+    /// Press Cancel on the open dialog, or close an `alert`
+    pub async fn dismiss_alert(&self) -> Result<()> {
+        self.command(Method::POST, "/alert/dismiss", None)
+            .await
+            .map(drop)
+    }
+
+    /// Message shown by the open dialog
+    pub async fn alert_text(&self) -> Result<String> {
+        let text = self.command(Method::GET, "/alert/text", None).await?;
+        Ok(text.as_str().unwrap_or_default().to_string())
+    }
+
+    /// Type `text` into the open `prompt`; [`accept_alert`](Self::accept_alert) submits it
+    pub async fn send_alert_text(&self, text: &str) -> Result<()> {
+        self.command(Method::POST, "/alert/text", Some(json!({ "text": text })))
+            .await
+            .map(drop)
+    }
+}
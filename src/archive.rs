@@ -0,0 +1,190 @@
+use std::{
+    fs,
+    io::{self, Read, Seek},
+    path::{Component, Path, PathBuf},
+};
+
+use zip::{result::ZipError, ZipArchive};
+
+use crate::error::{DriverError, Result};
+
+/// Extract every entry of `archive` into `dest`.
+///
+/// * Archives come from the network, so every entry is checked first.
+/// * Absolute paths, `..` components and symlinks pointing outside `dest` are
+///   rejected with [`DriverError::UnsafeArchiveEntry`] before anything is written.
+pub(crate) fn extract<R: Read + Seek>(mut archive: ZipArchive<R>, dest: &Path) -> Result<()> {
+    let extraction = |source| DriverError::Extraction {
+        path: dest.to_owned(),
+        source,
+    };
+    let unsafe_entry = |entry: &str| DriverError::UnsafeArchiveEntry {
+        path: dest.to_owned(),
+        entry: entry.to_string(),
+    };
+
+    // 1️⃣ Validate all entries
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index).map_err(extraction)?;
+        let name = file.name().to_string();
+        let relative = contained_path(Path::new(""), &name).ok_or_else(|| unsafe_entry(&name))?;
+        if file.is_symlink() {
+            let mut target = String::new();
+            file.read_to_string(&mut target)
+                .map_err(|e| DriverError::io(format!("reading symlink {name}"), e))?;
+            let parent = relative.parent().unwrap_or(Path::new(""));
+            contained_path(parent, &target).ok_or_else(|| unsafe_entry(&name))?;
+        }
+    }
+
+    // 2️⃣ Extract
+    fs::create_dir_all(dest)
+        .map_err(|e| DriverError::io(format!("creating {}", dest.display()), e))?;
+    archive.extract(dest).map_err(extraction)
+}
+
+/// `base` joined with the relative `path`, or `None` if the result leaves the root
+fn contained_path(base: &Path, path: &str) -> Option<PathBuf> {
+    let mut resolved = base.to_path_buf();
+    for component in Path::new(&path.replace('\\', "/")).components() {
+        match component {
+            Component::Normal(part) => resolved.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !resolved.pop() {
+                    return None;
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(resolved)
+}
+
+/// Copy the entry named `file_name` of the zip at `zip_path` to `dest`,
+/// wherever it sits inside the archive
+pub(crate) fn extract_file(zip_path: &Path, file_name: &str, dest: &Path) -> Result<()> {
+    let file = fs::File::open(zip_path)
+        .map_err(|e| DriverError::io(format!("opening {}", zip_path.display()), e))?;
+    let extraction = |source| DriverError::Extraction {
+        path: dest.to_owned(),
+        source,
+    };
+    let mut archive = ZipArchive::new(file).map_err(extraction)?;
+    let entry = archive
+        .file_names()
+        .find(|name| name.rsplit(['/', '\\']).next() == Some(file_name))
+        .map(str::to_string)
+        .ok_or_else(|| extraction(ZipError::FileNotFound))?;
+
+    let mut source = archive.by_name(&entry).map_err(extraction)?;
+    let mut target = fs::File::create(dest)
+        .map_err(|e| DriverError::io(format!("creating {}", dest.display()), e))?;
+    io::copy(&mut source, &mut target)
+        .map_err(|e| DriverError::io(format!("writing {}", dest.display()), e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use zip::{write::SimpleFileOptions, ZipWriter};
+
+    use super::*;
+
+    /// An in-memory zip with one regular file per `(name, contents)`
+    /// and one symlink per `(name, target)`
+    fn zip(files: &[(&str, &str)], symlinks: &[(&str, &str)]) -> ZipArchive<Cursor<Vec<u8>>> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default();
+        for (name, contents) in files {
+            writer.start_file(*name, options).unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
+        }
+        for (name, target) in symlinks {
+            writer.add_symlink(*name, *target, options).unwrap();
+        }
+        ZipArchive::new(writer.finish().unwrap()).unwrap()
+    }
+
+    /// A fresh directory nothing else writes to
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "chrome-driver-rs-archive-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn assert_rejected(archive: ZipArchive<Cursor<Vec<u8>>>, expected: &str) {
+        let dest = scratch(expected.replace(['/', '\\', '.'], "_").as_str());
+        match extract(archive, &dest) {
+            Err(DriverError::UnsafeArchiveEntry { entry, .. }) => assert_eq!(entry, expected),
+            other => panic!("expected UnsafeArchiveEntry for {expected:?}, got {other:?}"),
+        }
+        assert!(
+            !dest.exists(),
+            "nothing may be written for an unsafe archive"
+        );
+    }
+
+    #[test]
+    fn contained_path_resolves_inside_the_root() {
+        let base = Path::new("chromedriver-linux64");
+        assert_eq!(
+            contained_path(base, "./LICENSE"),
+            Some(base.join("LICENSE"))
+        );
+        assert_eq!(
+            contained_path(base, "../other/chromedriver"),
+            Some(PathBuf::from("other/chromedriver"))
+        );
+        assert_eq!(
+            contained_path(Path::new(""), r"dir\chromedriver.exe"),
+            Some(PathBuf::from("dir/chromedriver.exe"))
+        );
+    }
+
+    #[test]
+    fn contained_path_rejects_escapes() {
+        assert_eq!(contained_path(Path::new(""), "../evil"), None);
+        assert_eq!(contained_path(Path::new("a"), "../../evil"), None);
+        assert_eq!(contained_path(Path::new(""), "a/../../evil"), None);
+        assert_eq!(contained_path(Path::new(""), "/etc/evil"), None);
+        assert_eq!(contained_path(Path::new(""), r"..\evil"), None);
+    }
+
+    #[test]
+    fn rejects_parent_dir_entries() {
+        assert_rejected(zip(&[("ok.txt", ""), ("../evil", "x")], &[]), "../evil");
+        assert_rejected(zip(&[("dir/../../evil", "x")], &[]), "dir/../../evil");
+    }
+
+    #[test]
+    fn rejects_absolute_entries() {
+        assert_rejected(zip(&[("/tmp/evil", "x")], &[]), "/tmp/evil");
+    }
+
+    #[test]
+    fn rejects_symlinks_escaping_the_destination() {
+        assert_rejected(zip(&[], &[("link", "../../etc/passwd")]), "link");
+        assert_rejected(zip(&[], &[("dir/link", "/etc/passwd")]), "dir/link");
+    }
+
+    #[test]
+    fn extracts_safe_archives() {
+        let dest = scratch("safe");
+        let archive = zip(
+            &[("chromedriver-linux64/chromedriver", "binary")],
+            &[("chromedriver-linux64/link", "chromedriver")],
+        );
+        extract(archive, &dest).unwrap();
+        assert_eq!(
+            fs::read_to_string(dest.join("chromedriver-linux64/chromedriver")).unwrap(),
+            "binary"
+        );
+        fs::remove_dir_all(&dest).unwrap();
+    }
+}
@@ -0,0 +1,183 @@
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_json::{json, Value};
+use std::{fmt, sync::Arc};
+use tracing::info;
+
+use crate::{
+    error::{DriverError, Result},
+    rpc::{Connection, Protocol},
+    CdpEvents, ChromeOptions, ConsoleEvents, ConsoleLog, Session,
+};
+
+/// A WebDriver BiDi connection, the event-driven successor of classic WebDriver and CDP.
+///
+/// * [`Session::bidi`] connects to a session started with [`ChromeOptions::bidi`].
+/// * Any module's commands can be sent with [`send`](Self::send); `browsingContext` and
+///   `log` also have typed helpers.
+/// * Clones share the connection, which closes once the last clone and [`BidiEvents`]
+///   are dropped.
+#[derive(Clone)]
+pub struct Bidi {
+    connection: Arc<Connection>,
+}
+
+/// An event pushed by the browser, e.g. `network.responseCompleted`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BidiEvent {
+    /// Module and event, e.g. `log.entryAdded`
+    pub method: String,
+    /// Event payload
+    pub params: Value,
+}
+
+impl BidiEvent {
+    /// Deserialize the payload into a `#[derive(Deserialize)]` struct
+    pub fn convert<T: DeserializeOwned>(&self) -> Result<T> {
+        serde_json::from_value(self.params.clone()).map_err(|e| DriverError::WebDriver {
+            command: self.method.clone(),
+            error: "invalid response".into(),
+            message: format!("unexpected event parameters: {e}"),
+        })
+    }
+}
+
+/// Events received through [`Bidi::subscribe`]; ends when the connection closes
+#[derive(Debug)]
+pub struct BidiEvents {
+    events: CdpEvents,
+}
+
+impl BidiEvents {
+    /// Wait for the next event, or `None` once the browser is gone
+    pub async fn next(&mut self) -> Option<BidiEvent> {
+        let event = self.events.next().await?;
+        Some(BidiEvent {
+            method: event.method,
+            params: event.params,
+        })
+    }
+}
+
+/// A tab, window or frame, from [`Bidi::browsing_contexts`]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct BrowsingContext {
+    /// Context id; for top-level contexts the same as the window handle
+    pub context: String,
+    /// URL of its document
+    pub url: String,
+    /// Frames inside it
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub children: Vec<BrowsingContext>,
+    /// Context it is a frame of; `None` for tabs and windows
+    #[serde(default)]
+    pub parent: Option<String>,
+}
+
+impl Bidi {
+    /// Connect to a BiDi websocket, e.g. the `webSocketUrl` capability of a session
+    pub async fn connect(url: &str) -> Result<Bidi> {
+        let connection = Connection::open(url, Protocol::Bidi).await?;
+        info!("🔌 connected to BiDi at {url}");
+        Ok(Bidi { connection })
+    }
+
+    /// Run `method` with raw JSON `params`, returning its raw `result`, e.g.
+    /// `send("browsingContext.reload", json!({"context": id}))`.
+    ///
+    /// * Rejected commands fail with [`DriverError::WebDriver`], as in classic WebDriver.
+    pub async fn send(&self, method: &str, params: impl Serialize) -> Result<Value> {
+        let params = match serde_json::to_value(params) {
+            Ok(Value::Null) => json!({}),
+            Ok(params) => params,
+            Err(e) => {
+                return Err(DriverError::Config(format!(
+                    "serializing {method} parameters: {e}"
+                )))
+            }
+        };
+        self.connection.call(method, params, None).await
+    }
+
+    /// Subscribe to `events`, each a module such as `log` or an event such as
+    /// `network.beforeRequestSent`
+    pub async fn subscribe(&self, events: &[&str]) -> Result<BidiEvents> {
+        Ok(BidiEvents {
+            events: self.subscription(events).await?,
+        })
+    }
+
+    async fn subscription(&self, events: &[&str]) -> Result<CdpEvents> {
+        let methods = events.iter().map(|event| event.to_string()).collect();
+        // Listen before subscribing, so the first events aren't missed
+        let receiver = self.connection.subscribe(methods, None);
+        self.send("session.subscribe", json!({ "events": events }))
+            .await?;
+        Ok(CdpEvents::new(&self.connection, receiver))
+    }
+
+    /// Tabs and windows of the browser, with their frames
+    pub async fn browsing_contexts(&self) -> Result<Vec<BrowsingContext>> {
+        let tree = self.send("browsingContext.getTree", ()).await?;
+        serde_json::from_value(tree["contexts"].clone()).map_err(|e| DriverError::WebDriver {
+            command: "browsingContext.getTree".into(),
+            error: "invalid response".into(),
+            message: e.to_string(),
+        })
+    }
+
+    /// Load `url` in `context`, waiting for the page to load
+    pub async fn navigate(&self, context: &str, url: &str) -> Result<()> {
+        let params = json!({ "context": context, "url": url, "wait": "complete" });
+        self.send("browsingContext.navigate", params)
+            .await
+            .map(drop)
+    }
+
+    /// Report console calls and uncaught exceptions through the `log` module
+    pub async fn console(&self) -> Result<ConsoleEvents> {
+        let events = self.subscription(&["log.entryAdded"]).await?;
+        Ok(ConsoleEvents::new(events))
+    }
+
+    /// Collect console messages into a [`ConsoleLog`], like [`crate::Cdp::capture_console`]
+    pub async fn capture_console(&self) -> Result<ConsoleLog> {
+        Ok(ConsoleLog::collect(self.console().await?))
+    }
+}
+
+impl fmt::Debug for Bidi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Bidi")
+            .field("url", &self.connection.url())
+            .finish()
+    }
+}
+
+impl ChromeOptions {
+    /// Ask the driver for a BiDi endpoint (`webSocketUrl`), see [`Session::bidi`]
+    pub fn bidi(self) -> Self {
+        self.capability("webSocketUrl", true)
+    }
+}
+
+impl Session {
+    /// BiDi connection to this session's browser.
+    ///
+    /// * The session must be started with [`ChromeOptions::bidi`].
+    pub async fn bidi(&self) -> Result<Bidi> {
+        match self.capabilities()["webSocketUrl"].as_str() {
+            Some(url) => Bidi::connect(url).await,
+            None => Err(DriverError::Config(
+                "the session has no webSocketUrl; start it with ChromeOptions::bidi()".into(),
+            )),
+        }
+    }
+}
+
+/// `getTree` sends `null` children beyond its depth limit
+fn null_as_empty<'de, D>(deserializer: D) -> Result<Vec<BrowsingContext>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::deserialize(deserializer)?.unwrap_or_default())
+}
@@ -0,0 +1,241 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use chrome_driver_rs::{default_cache_dir, Verbosity};
+
+/// Errors of a command; [`UsageError`]s exit with status 2, everything else with 1
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// A mistake on the command line, e.g. an unknown option
+#[derive(Debug)]
+pub struct UsageError(String);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for UsageError {}
+
+/// A [`UsageError`] with `message`
+pub fn usage(message: impl Into<String>) -> Box<dyn Error> {
+    Box::new(UsageError(message.into()))
+}
+
+/// Command-line arguments a command hasn't consumed yet.
+///
+/// * Options may come in any order, as `--name value` or `--name=value`.
+/// * Everything after `--` is kept apart, see [`trailing`](Self::trailing).
+#[derive(Debug)]
+pub struct Args {
+    args: Vec<String>,
+    trailing: Option<Vec<String>>,
+}
+
+impl Args {
+    pub fn new(args: impl IntoIterator<Item = String>) -> Args {
+        let mut args: Vec<String> = args.into_iter().collect();
+        let trailing = args.iter().position(|arg| arg == "--").map(|separator| {
+            let trailing = args.split_off(separator + 1);
+            args.pop();
+            trailing
+        });
+        Args { args, trailing }
+    }
+
+    /// The first argument, naming the command
+    pub fn command(&mut self) -> Option<String> {
+        (!self.args.is_empty()).then(|| self.args.remove(0))
+    }
+
+    /// Whether `-h`/`--help` was given
+    pub fn help(&mut self) -> bool {
+        self.flag(&["-h", "--help"])
+    }
+
+    /// Whether a switch was given under any of `names`
+    pub fn flag(&mut self, names: &[&str]) -> bool {
+        let before = self.args.len();
+        self.args.retain(|arg| !names.contains(&arg.as_str()));
+        self.args.len() != before
+    }
+
+    /// Value of the option `name`; the last one wins when repeated
+    pub fn value(&mut self, name: &str) -> Result<Option<String>> {
+        let mut value = None;
+        let mut i = 0;
+        while i < self.args.len() {
+            if self.args[i] == name {
+                if i + 1 == self.args.len() {
+                    return Err(usage(format!("{name} needs a value")));
+                }
+                self.args.remove(i);
+                value = Some(self.args.remove(i));
+            } else if let Some(inline) = self.args[i]
+                .strip_prefix(name)
+                .and_then(|rest| rest.strip_prefix('='))
+            {
+                value = Some(inline.to_string());
+                self.args.remove(i);
+            } else {
+                i += 1;
+            }
+        }
+        Ok(value)
+    }
+
+    /// [`value`](Self::value) parsed as a `T`, e.g. a number
+    pub fn parse<T>(&mut self, name: &str) -> Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        match self.value(name)? {
+            Some(value) => match value.parse() {
+                Ok(parsed) => Ok(Some(parsed)),
+                Err(e) => Err(usage(format!("invalid {name} `{value}`: {e}"))),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Arguments after `--`, for commands passing them on
+    pub fn trailing(&mut self) -> Vec<String> {
+        self.trailing.take().unwrap_or_default()
+    }
+
+    /// Fail on anything no option consumed
+    pub fn finish(self) -> Result<()> {
+        if let Some(arg) = self.args.first() {
+            return Err(usage(format!("unexpected argument `{arg}`")));
+        }
+        if self.trailing.is_some_and(|trailing| !trailing.is_empty()) {
+            return Err(usage("this command takes no arguments after `--`"));
+        }
+        Ok(())
+    }
+}
+
+/// Options every command takes
+#[derive(Debug)]
+pub struct Common {
+    /// `--out-dir`, defaulting to the per-user cache
+    pub out_dir: String,
+    /// `-q`/`-v`
+    pub verbosity: Verbosity,
+}
+
+/// Help lines of [`Common`]
+pub const COMMON_OPTIONS: &str =
+    "  --out-dir <DIR>   Driver directory [default: the per-user cache]
+  -q, --quiet       Print only the result
+  -v, --verbose     Also print checksum and version checks
+  -h, --help        Print this help";
+
+impl Common {
+    pub fn parse(args: &mut Args) -> Result<Common> {
+        let out_dir = match args.value("--out-dir")? {
+            Some(out_dir) => out_dir,
+            None => default_cache_dir()?.to_string_lossy().into_owned(),
+        };
+        let verbosity = match (
+            args.flag(&["-q", "--quiet"]),
+            args.flag(&["-v", "--verbose"]),
+        ) {
+            (true, true) => return Err(usage("--quiet and --verbose exclude each other")),
+            (true, false) => Verbosity::Silent,
+            (false, true) => Verbosity::Verbose,
+            (false, false) => Verbosity::Normal,
+        };
+        Ok(Common { out_dir, verbosity })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Args {
+        Args::new(line.split_whitespace().map(str::to_string))
+    }
+
+    fn is_usage(result: Result<impl fmt::Debug>) -> bool {
+        result.is_err_and(|e| e.is::<UsageError>())
+    }
+
+    #[test]
+    fn takes_the_command_first() {
+        let mut args = args("install --force");
+        assert_eq!(args.command().as_deref(), Some("install"));
+        assert!(args.flag(&["--force"]));
+        assert!(args.finish().is_ok());
+        assert_eq!(Args::new(Vec::new()).command(), None);
+    }
+
+    #[test]
+    fn reads_values_in_both_forms() {
+        let mut args = args("--channel beta --out-dir=/tmp/drivers");
+        assert_eq!(
+            args.value("--out-dir").unwrap().as_deref(),
+            Some("/tmp/drivers")
+        );
+        assert_eq!(args.value("--channel").unwrap().as_deref(), Some("beta"));
+        assert_eq!(args.value("--version").unwrap(), None);
+        assert!(args.finish().is_ok());
+    }
+
+    #[test]
+    fn keeps_the_last_repeated_value() {
+        let mut args = args("--keep 1 --keep=3");
+        assert_eq!(args.parse::<usize>("--keep").unwrap(), Some(3));
+        assert!(args.finish().is_ok());
+    }
+
+    #[test]
+    fn does_not_mistake_longer_options_for_values() {
+        let mut args = args("--versions=1");
+        assert_eq!(args.value("--version").unwrap(), None);
+        assert!(is_usage(args.finish()));
+    }
+
+    #[test]
+    fn rejects_missing_and_invalid_values() {
+        assert!(is_usage(args("--channel").value("--channel")));
+        assert!(is_usage(args("--port nine").parse::<u16>("--port")));
+        assert!(is_usage(args("--port=70000").parse::<u16>("--port")));
+    }
+
+    #[test]
+    fn rejects_leftover_arguments() {
+        let mut args = args("--force stray");
+        assert!(args.flag(&["--force"]));
+        let e = args.finish().unwrap_err();
+        assert_eq!(e.to_string(), "unexpected argument `stray`");
+    }
+
+    #[test]
+    fn keeps_arguments_after_the_separator_apart() {
+        let mut args = args("--port 4444 -- --verbose --port=1");
+        assert_eq!(args.parse::<u16>("--port").unwrap(), Some(4444));
+        assert_eq!(args.trailing(), ["--verbose", "--port=1"]);
+        assert!(args.finish().is_ok());
+    }
+
+    #[test]
+    fn rejects_trailing_arguments_nobody_took() {
+        assert!(is_usage(args("-- --verbose").finish()));
+        assert!(args("--").finish().is_ok());
+    }
+
+    #[test]
+    fn parses_common_options() {
+        let common = Common::parse(&mut args("--out-dir /tmp/drivers -q")).unwrap();
+        assert_eq!(common.out_dir, "/tmp/drivers");
+        assert_eq!(common.verbosity, Verbosity::Silent);
+
+        let common = Common::parse(&mut args("--out-dir=/tmp/drivers --verbose")).unwrap();
+        assert_eq!(common.verbosity, Verbosity::Verbose);
+
+        assert!(is_usage(Common::parse(&mut args("--out-dir /tmp -q -v"))));
+    }
+}
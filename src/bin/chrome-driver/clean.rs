@@ -0,0 +1,34 @@
+use chrome_driver_rs::{cleanup_old_drivers, uninstall_all};
+
+use crate::args::{usage, Args, Common, Result, COMMON_OPTIONS};
+
+const USAGE: &str = "\
+Remove old driver versions from --out-dir and print the disk space reclaimed
+
+Usage: chrome-driver clean [options]
+
+Options:
+  --keep <N>        Keep the N newest versions, and always the current one [default: 1]
+  --all             Remove every version and the current pointer";
+
+pub async fn run(mut args: Args) -> Result<()> {
+    if args.help() {
+        println!("{USAGE}\n{COMMON_OPTIONS}");
+        return Ok(());
+    }
+    let keep = args.parse::<usize>("--keep")?;
+    let all = args.flag(&["--all"]);
+    let common = Common::parse(&mut args)?;
+    args.finish()?;
+    if all && keep.is_some() {
+        return Err(usage("--keep and --all exclude each other"));
+    }
+
+    let reclaimed = if all {
+        uninstall_all(&common.out_dir).await?
+    } else {
+        cleanup_old_drivers(&common.out_dir, keep.unwrap_or(1)).await?
+    };
+    println!("Reclaimed {:.1} MB", reclaimed as f64 / 1_000_000.0);
+    Ok(())
+}
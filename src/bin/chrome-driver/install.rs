@@ -0,0 +1,105 @@
+use std::io::{self, IsTerminal};
+
+use chrome_driver_rs::{Artifact, Channel, DriverManager, Verbosity, VersionSelector};
+
+use crate::args::{usage, Args, Common, Result, COMMON_OPTIONS};
+
+const USAGE: &str = "\
+Install ChromeDriver or a browser and print the path of its executable
+
+Usage: chrome-driver install [options]
+
+Options:
+  --artifact <NAME> chromedriver, chrome or chrome-headless-shell [default: chromedriver]";
+
+/// Help lines of [`Selection`]
+pub const SELECTION_OPTIONS: &str =
+    "  --channel <NAME>  Latest version of stable, beta, dev or canary [default: stable]
+  --version <VER>   Exactly this version, e.g. 130.0.6723.58
+  --milestone <N>   Latest version of a major version, e.g. 130
+  --match-chrome    The version matching the installed Chrome
+  --force           Reinstall even if already installed
+  --offline         Only use what is already installed";
+
+/// Which version to install and how, shared by the commands installing a driver
+#[derive(Debug)]
+pub struct Selection {
+    selector: VersionSelector,
+    force: bool,
+    offline: bool,
+}
+
+impl Selection {
+    pub fn parse(args: &mut Args) -> Result<Selection> {
+        let channel = match args.value("--channel")? {
+            Some(name) => Some(Channel::from_name(&name).ok_or_else(|| {
+                usage(format!(
+                    "unknown channel `{name}`; expected stable, beta, dev or canary"
+                ))
+            })?),
+            None => None,
+        };
+        let version = args.value("--version")?;
+        let milestone = args.parse::<u32>("--milestone")?;
+        let match_chrome = args.flag(&["--match-chrome"]);
+        let selector = match (channel, version, milestone, match_chrome) {
+            (channel, None, None, false) => {
+                VersionSelector::Latest(channel.unwrap_or(Channel::Stable))
+            }
+            (None, Some(version), None, false) => VersionSelector::Exact(version),
+            (None, None, Some(milestone), false) => VersionSelector::Milestone(milestone),
+            (None, None, None, true) => VersionSelector::InstalledChrome,
+            _ => {
+                return Err(usage(
+                    "--channel, --version, --milestone and --match-chrome exclude each other",
+                ))
+            }
+        };
+        Ok(Selection {
+            selector,
+            force: args.flag(&["--force"]),
+            offline: args.flag(&["--offline"]),
+        })
+    }
+
+    /// An installer for the selected version into `common.out_dir`
+    pub fn manager(self, common: &Common) -> Result<DriverManager> {
+        let mut builder = DriverManager::builder()
+            .out_dir(&common.out_dir)
+            .selector(self.selector)
+            .force(self.force)
+            .offline(self.offline)
+            .verbosity(common.verbosity);
+        // A bar redrawn in place is noise once stderr goes to a CI log
+        if common.verbosity != Verbosity::Silent && io::stderr().is_terminal() {
+            builder = builder.progress_bar();
+        }
+        Ok(builder.build()?)
+    }
+}
+
+pub async fn run(mut args: Args) -> Result<()> {
+    if args.help() {
+        println!("{USAGE}\n{SELECTION_OPTIONS}\n{COMMON_OPTIONS}");
+        return Ok(());
+    }
+    let selection = Selection::parse(&mut args)?;
+    let artifact = match args.value("--artifact")? {
+        Some(name) => Artifact::from_name(&name).ok_or_else(|| {
+            usage(format!(
+                "unknown artifact `{name}`; expected chromedriver, chrome or chrome-headless-shell"
+            ))
+        })?,
+        None => Artifact::ChromeDriver,
+    };
+    let common = Common::parse(&mut args)?;
+    args.finish()?;
+
+    let manager = selection.manager(&common)?;
+    let path = match artifact {
+        Artifact::ChromeDriver => manager.install().await?.driver_path,
+        artifact => manager.install_artifact(artifact).await?.path,
+    };
+    println!("{path}");
+    Ok(())
+}
@@ -0,0 +1,71 @@
+//! `chrome-driver`: install and manage ChromeDriver without writing a Rust program.
+//!
+//! * Results go to stdout, one per line, so they can be used in `$(...)`.
+//! * Progress and errors go to stderr.
+
+mod args;
+mod clean;
+mod doctor;
+mod install;
+mod run;
+mod versions;
+mod which;
+
+use std::{env, process::ExitCode};
+
+use args::{usage, Args, UsageError};
+
+const USAGE: &str = "\
+Usage: chrome-driver <command> [options]
+
+Commands:
+  install   Install ChromeDriver or a browser and print its path
+  versions  List published and installed driver versions
+  clean     Remove old driver versions
+  which     Print the path of the current driver
+  run       Install the driver if needed and run it
+  doctor    Diagnose installs, versions and network access
+
+Run `chrome-driver <command> --help` for the options of a command.";
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    // Die quietly like other tools when stdout is closed early, e.g. by `| head`
+    #[cfg(unix)]
+    unsafe {
+        libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+    }
+    let mut args = Args::new(env::args().skip(1));
+    let result = match args.command().as_deref() {
+        Some("install") => install::run(args).await,
+        Some("versions") => versions::run(args).await,
+        Some("clean") => clean::run(args).await,
+        Some("which") => which::run(args).await,
+        Some("run") => run::run(args).await,
+        Some("doctor") => doctor::run(args).await,
+        Some("-h" | "--help") => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Some("-V" | "--version") => {
+            println!("chrome-driver {}", env!("CARGO_PKG_VERSION"));
+            return ExitCode::SUCCESS;
+        }
+        Some(command) => Err(usage(format!("unknown command `{command}`"))),
+        None => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) if e.is::<UsageError>() => {
+            eprintln!("error: {e}\n\nRun `chrome-driver --help` for usage.");
+            ExitCode::from(2)
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
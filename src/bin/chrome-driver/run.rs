@@ -0,0 +1,56 @@
+use std::process::Command;
+
+use chrome_driver_rs::{Verbosity, DEFAULT_DRIVER_PORT};
+
+use crate::{
+    args::{Args, Common, Result, COMMON_OPTIONS},
+    install::{Selection, SELECTION_OPTIONS},
+};
+
+const USAGE: &str = "\
+Install the driver if needed, then run it in place of this process
+
+Usage: chrome-driver run [options] [-- <driver flags>...]
+
+Flags after `--` go to chromedriver verbatim, e.g. `-- --verbose --allowed-ips=`.
+
+Options:
+  --port <PORT>     Port the driver listens on [default: 9515]";
+
+pub async fn run(mut args: Args) -> Result<()> {
+    if args.help() {
+        println!("{USAGE}\n{SELECTION_OPTIONS}\n{COMMON_OPTIONS}");
+        return Ok(());
+    }
+    let port = args.parse::<u16>("--port")?.unwrap_or(DEFAULT_DRIVER_PORT);
+    let selection = Selection::parse(&mut args)?;
+    let common = Common::parse(&mut args)?;
+    let driver_args = args.trailing();
+    args.finish()?;
+
+    let driver = selection.manager(&common)?.install().await?;
+    if common.verbosity != Verbosity::Silent {
+        eprintln!("🚀 Starting ChromeDriver {} on port {port}", driver.version);
+    }
+    let mut command = Command::new(&driver.driver_path);
+    command.arg(format!("--port={port}")).args(driver_args);
+    exec(command)
+}
+
+/// Replace this process with the driver, so signals and the exit status are its own
+#[cfg(unix)]
+fn exec(mut command: Command) -> Result<()> {
+    use std::os::unix::process::CommandExt;
+
+    let e = command.exec();
+    Err(format!("starting {}: {e}", command.get_program().to_string_lossy()).into())
+}
+
+/// Run the driver until it exits, then exit with its status; Ctrl+C reaches both processes
+#[cfg(not(unix))]
+fn exec(mut command: Command) -> Result<()> {
+    let status = command
+        .status()
+        .map_err(|e| format!("starting {}: {e}", command.get_program().to_string_lossy()))?;
+    std::process::exit(status.code().unwrap_or(1))
+}
@@ -0,0 +1,81 @@
+use chrome_driver_rs::{current_driver, list_installed, Artifact, DriverManager, Platform};
+
+use crate::args::{Args, Common, Result, COMMON_OPTIONS};
+
+const USAGE: &str = "\
+List the ChromeDriver versions published upstream, newest first, marking installed ones
+
+Usage: chrome-driver versions [options]
+
+Options:
+  --milestone <N>   Only versions of this major version, e.g. 130
+  --installed       Only the drivers installed in --out-dir, with their paths";
+
+pub async fn run(mut args: Args) -> Result<()> {
+    if args.help() {
+        println!("{USAGE}\n{COMMON_OPTIONS}");
+        return Ok(());
+    }
+    let milestone = args.parse::<u32>("--milestone")?;
+    let installed_only = args.flag(&["--installed"]);
+    let common = Common::parse(&mut args)?;
+    args.finish()?;
+
+    let in_milestone = |version: &str| {
+        milestone.is_none_or(|milestone| version.split('.').next() == Some(&milestone.to_string()))
+    };
+    let installed = list_installed(&common.out_dir).await?;
+    let current = current_driver(&common.out_dir)
+        .await?
+        .map(|current| current.version);
+    let is_current = |version: &str| current.as_deref() == Some(version);
+
+    if installed_only {
+        for driver in installed
+            .iter()
+            .filter(|driver| in_milestone(&driver.version))
+        {
+            let marker = if is_current(&driver.version) {
+                "  (current)"
+            } else {
+                ""
+            };
+            println!("{}  {}{marker}", driver.version, driver.driver_path);
+        }
+        return Ok(());
+    }
+
+    let manager = DriverManager::builder()
+        .out_dir(&common.out_dir)
+        .verbosity(common.verbosity)
+        .build()?;
+    // Without a Chrome for Testing build for this machine, list every driver release
+    let platform = Platform::current().ok();
+    let published = manager.available_versions().await?;
+    for release in published.iter().rev() {
+        let has_driver = match platform {
+            Some(platform) => release
+                .url_for(Artifact::ChromeDriver, platform.as_str())
+                .is_some(),
+            None => release
+                .downloads
+                .iter()
+                .any(|download| download.artifact == Artifact::ChromeDriver),
+        };
+        if !has_driver || !in_milestone(&release.version) {
+            continue;
+        }
+        let marker = match (
+            is_current(&release.version),
+            installed
+                .iter()
+                .any(|driver| driver.version == release.version),
+        ) {
+            (true, _) => "  (installed, current)",
+            (false, true) => "  (installed)",
+            (false, false) => "",
+        };
+        println!("{}{marker}", release.version);
+    }
+    Ok(())
+}
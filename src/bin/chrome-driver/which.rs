@@ -0,0 +1,50 @@
+use chrome_driver_rs::{current_driver, Artifact, DriverManager, Verbosity};
+
+use crate::args::{Args, Common, Result, COMMON_OPTIONS};
+
+const USAGE: &str = "\
+Print the path of the current driver, e.g. for DRIVER=$(chrome-driver which)
+
+Usage: chrome-driver which [options]
+
+Options:
+  --browser         Print the Chrome installed alongside it instead";
+
+pub async fn run(mut args: Args) -> Result<()> {
+    if args.help() {
+        println!("{USAGE}\n{COMMON_OPTIONS}");
+        return Ok(());
+    }
+    let browser = args.flag(&["--browser"]);
+    let common = Common::parse(&mut args)?;
+    args.finish()?;
+
+    let Some(driver) = current_driver(&common.out_dir).await? else {
+        return Err(format!(
+            "no current driver in {}; run `chrome-driver install` first",
+            common.out_dir
+        )
+        .into());
+    };
+    if !browser {
+        println!("{}", driver.driver_path);
+        return Ok(());
+    }
+    // An offline install only looks the browser of that version up
+    let chrome = DriverManager::builder()
+        .out_dir(&common.out_dir)
+        .version(&driver.version)
+        .offline(true)
+        .verbosity(Verbosity::Silent)
+        .build()?
+        .install_artifact(Artifact::Chrome)
+        .await
+        .map_err(|_| {
+            format!(
+                "no Chrome {} installed in {}; run `chrome-driver install --artifact chrome --version {}`",
+                driver.version, common.out_dir, driver.version
+            )
+        })?;
+    println!("{}", chrome.path);
+    Ok(())
+}
@@ -0,0 +1,729 @@
+//! Synchronous variants of the installer API.
+//!
+//! Like `reqwest::blocking`, each call drives the async implementation on a
+//! private runtime, so callers don't need to set up tokio. The runtime is shared by
+//! all calls, so connections a [`Session`] or [`DriverProcess`] keeps stay usable
+//! from one call to the next. They must not be called from within an async runtime.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::{future::Future, path::Path, process::ExitStatus};
+
+use crate::{
+    error::Result, rt, Actions, Artifact, ArtifactInfo, AvailableVersion, Bidi, BidiEvent,
+    BidiEvents, BrowserInfo, BrowsingContext, By, Cdp, CdpCommand, CdpEvent, CdpEvents, Channel,
+    ChromeInstall, ChromeOptions, ConsoleEvents, ConsoleLog, ConsoleMessage, Cookie, DriverInfo,
+    DriverManager, DriverPool, DriverProcess, DriverProcessBuilder, DriverStatus, DriverVersion,
+    Element, ElementWait, Frame, InstallPlan, Interception, Launched, MatchedPair, NetworkEvent,
+    NetworkEvents, PausedRequest, PerformanceMetrics, PooledDriver, PrintOptions, ScriptValue,
+    Session, ShadowRoot, Trace, WindowRect, WindowType,
+};
+
+/// Run `future` to completion on the shared runtime
+fn block_on<F: Future>(future: F) -> Result<F::Output> {
+    Ok(rt::block_on(future))
+}
+
+/// Blocking [`crate::ensure_latest_driver`]
+pub fn ensure_latest_driver_blocking(out_dir: &str) -> Result<DriverInfo> {
+    block_on(crate::ensure_latest_driver(out_dir))?
+}
+
+/// Blocking [`crate::launch`]
+pub fn launch_blocking(out_dir: &str, options: &ChromeOptions) -> Result<Launched> {
+    block_on(crate::launch(out_dir, options))?
+}
+
+/// Blocking [`crate::launch_with_capabilities`]
+pub fn launch_with_capabilities_blocking(out_dir: &str, capabilities: Value) -> Result<Launched> {
+    block_on(crate::launch_with_capabilities(out_dir, capabilities))?
+}
+
+impl Launched {
+    /// Blocking [`Launched::stop`]
+    pub fn stop_blocking(self) -> Result<()> {
+        block_on(self.stop())?
+    }
+}
+
+/// Blocking [`crate::ensure_latest_driver_default`]
+pub fn ensure_latest_driver_default_blocking() -> Result<DriverInfo> {
+    block_on(crate::ensure_latest_driver_default())?
+}
+
+/// Blocking [`crate::ensure_driver`]
+pub fn ensure_driver_blocking(channel: Channel, out_dir: &str) -> Result<DriverInfo> {
+    block_on(crate::ensure_driver(channel, out_dir))?
+}
+
+/// Blocking [`crate::ensure_driver_version`]
+pub fn ensure_driver_version_blocking(version: &str, out_dir: &str) -> Result<DriverInfo> {
+    block_on(crate::ensure_driver_version(version, out_dir))?
+}
+
+/// Blocking [`crate::ensure_driver_for_milestone`]
+pub fn ensure_driver_for_milestone_blocking(milestone: u32, out_dir: &str) -> Result<DriverInfo> {
+    block_on(crate::ensure_driver_for_milestone(milestone, out_dir))?
+}
+
+/// Blocking [`crate::ensure_driver_for_installed_chrome`]
+pub fn ensure_driver_for_installed_chrome_blocking(out_dir: &str) -> Result<DriverInfo> {
+    block_on(crate::ensure_driver_for_installed_chrome(out_dir))?
+}
+
+/// Blocking [`crate::driver_status`]
+pub fn driver_status_blocking(base_url: &str) -> Result<DriverStatus> {
+    block_on(crate::driver_status(base_url))?
+}
+
+/// Blocking [`crate::ensure_chrome_browser`]
+pub fn ensure_chrome_browser_blocking(out_dir: &str) -> Result<BrowserInfo> {
+    block_on(crate::ensure_chrome_browser(out_dir))?
+}
+
+/// Blocking [`crate::ensure_matched_pair`]
+pub fn ensure_matched_pair_blocking(out_dir: &str) -> Result<MatchedPair> {
+    block_on(crate::ensure_matched_pair(out_dir))?
+}
+
+/// Blocking [`crate::ensure_artifact`]
+pub fn ensure_artifact_blocking(
+    artifact: Artifact,
+    channel: Channel,
+    out_dir: &str,
+) -> Result<ArtifactInfo> {
+    block_on(crate::ensure_artifact(artifact, channel, out_dir))?
+}
+
+/// Blocking [`crate::install_from_zip`]
+pub fn install_from_zip_blocking(
+    zip_path: impl AsRef<Path>,
+    out_dir: impl AsRef<Path>,
+) -> Result<DriverInfo> {
+    block_on(crate::install_from_zip(zip_path, out_dir))?
+}
+
+/// Blocking [`crate::cleanup_old_drivers`]
+pub fn cleanup_old_drivers_blocking(out_dir: &str, keep: usize) -> Result<u64> {
+    block_on(crate::cleanup_old_drivers(out_dir, keep))?
+}
+
+/// Blocking [`crate::current_driver`]
+pub fn current_driver_blocking(out_dir: &str) -> Result<Option<DriverInfo>> {
+    block_on(crate::current_driver(out_dir))?
+}
+
+/// Blocking [`crate::list_installed`]
+pub fn list_installed_blocking(out_dir: &str) -> Result<Vec<DriverInfo>> {
+    block_on(crate::list_installed(out_dir))?
+}
+
+/// Blocking [`crate::rollback`]
+pub fn rollback_blocking(out_dir: &str) -> Result<DriverInfo> {
+    block_on(crate::rollback(out_dir))?
+}
+
+/// Blocking [`crate::uninstall`]
+pub fn uninstall_blocking(out_dir: &str, version: &str) -> Result<u64> {
+    block_on(crate::uninstall(out_dir, version))?
+}
+
+/// Blocking [`crate::uninstall_all`]
+pub fn uninstall_all_blocking(out_dir: &str) -> Result<u64> {
+    block_on(crate::uninstall_all(out_dir))?
+}
+
+/// Blocking [`crate::detect_chrome_version`]
+pub fn detect_chrome_version_blocking() -> Result<ChromeInstall> {
+    block_on(crate::detect_chrome_version())?
+}
+
+/// Blocking [`crate::find_chrome_installs`]
+pub fn find_chrome_installs_blocking() -> Result<Vec<ChromeInstall>> {
+    block_on(crate::find_chrome_installs())
+}
+
+/// Blocking [`crate::find_system_driver`]
+pub fn find_system_driver_blocking() -> Result<Option<DriverInfo>> {
+    block_on(crate::find_system_driver())
+}
+
+/// Blocking [`crate::latest_version`]
+pub fn latest_version_blocking(channel: Channel) -> Result<String> {
+    block_on(crate::latest_version(channel))?
+}
+
+/// Blocking [`crate::list_available_versions`]
+pub fn list_available_versions_blocking() -> Result<Vec<AvailableVersion>> {
+    block_on(crate::list_available_versions())?
+}
+
+/// Blocking [`crate::driver_version`]
+pub fn driver_version_blocking(driver_path: impl AsRef<Path>) -> Result<DriverVersion> {
+    block_on(crate::driver_version(driver_path))?
+}
+
+/// Blocking [`crate::verify_compatibility`]
+pub fn verify_compatibility_blocking(
+    driver_path: impl AsRef<Path>,
+    chrome_path: impl AsRef<Path>,
+) -> Result<()> {
+    block_on(crate::verify_compatibility(driver_path, chrome_path))?
+}
+
+/// Blocking [`crate::check_version`]
+pub fn check_version_blocking(driver_path: &str) -> Result<()> {
+    block_on(crate::check_version(driver_path))?
+}
+
+impl DriverManager {
+    /// Blocking [`DriverManager::install`]
+    pub fn install_blocking(&self) -> Result<DriverInfo> {
+        block_on(self.install())?
+    }
+
+    /// Blocking [`DriverManager::install_artifact`]
+    pub fn install_artifact_blocking(&self, artifact: Artifact) -> Result<ArtifactInfo> {
+        block_on(self.install_artifact(artifact))?
+    }
+
+    /// Blocking [`DriverManager::available_versions`]
+    pub fn available_versions_blocking(&self) -> Result<Vec<AvailableVersion>> {
+        block_on(self.available_versions())?
+    }
+
+    /// Blocking [`DriverManager::install_matched_pair`]
+    pub fn install_matched_pair_blocking(&self) -> Result<MatchedPair> {
+        block_on(self.install_matched_pair())?
+    }
+
+    /// Blocking [`DriverManager::plan`]
+    pub fn plan_blocking(&self) -> Result<InstallPlan> {
+        block_on(self.plan())?
+    }
+}
+
+impl DriverProcess {
+    /// Blocking [`DriverProcess::start`]
+    pub fn start_blocking(driver: &DriverInfo) -> Result<DriverProcess> {
+        block_on(DriverProcess::start(driver))?
+    }
+
+    /// Blocking [`DriverProcess::stop`]
+    pub fn stop_blocking(self) -> Result<()> {
+        block_on(self.stop())?
+    }
+
+    /// Blocking [`DriverProcess::shutdown`]
+    pub fn shutdown_blocking(self) -> Result<ExitStatus> {
+        block_on(self.shutdown())?
+    }
+
+    /// Blocking [`DriverProcess::new_session`]
+    pub fn new_session_blocking(&self, capabilities: Value) -> Result<Session> {
+        block_on(self.new_session(capabilities))?
+    }
+
+    /// Blocking [`DriverProcess::new_session_with_options`]
+    pub fn new_session_with_options_blocking(&self, options: &ChromeOptions) -> Result<Session> {
+        block_on(self.new_session_with_options(options))?
+    }
+
+    /// Blocking [`DriverProcess::status`]
+    pub fn status_blocking(&self) -> Result<DriverStatus> {
+        block_on(self.status())?
+    }
+}
+
+impl DriverProcessBuilder {
+    /// Blocking [`DriverProcessBuilder::start`]
+    pub fn start_blocking(self) -> Result<DriverProcess> {
+        block_on(self.start())?
+    }
+}
+
+impl DriverPool {
+    /// Blocking [`DriverPool::start`]
+    pub fn start_blocking(builder: DriverProcessBuilder, size: usize) -> Result<DriverPool> {
+        block_on(DriverPool::start(builder, size))?
+    }
+
+    /// Blocking [`DriverPool::checkout`]
+    pub fn checkout_blocking(&self) -> Result<PooledDriver> {
+        block_on(self.checkout())?
+    }
+
+    /// Blocking [`DriverPool::shutdown`]
+    pub fn shutdown_blocking(self) -> Result<()> {
+        block_on(self.shutdown())?
+    }
+}
+
+impl Session {
+    /// Blocking [`Session::new`]
+    pub fn new_blocking(base_url: &str, capabilities: Value) -> Result<Session> {
+        block_on(Session::new(base_url, capabilities))?
+    }
+
+    /// Blocking [`Session::with_options`]
+    pub fn with_options_blocking(base_url: &str, options: &ChromeOptions) -> Result<Session> {
+        block_on(Session::with_options(base_url, options))?
+    }
+
+    /// Blocking [`Session::status`]
+    pub fn status_blocking(&self) -> Result<DriverStatus> {
+        block_on(self.status())?
+    }
+
+    /// Blocking [`Session::goto`]
+    pub fn goto_blocking(&self, url: &str) -> Result<()> {
+        block_on(self.goto(url))?
+    }
+
+    /// Blocking [`Session::back`]
+    pub fn back_blocking(&self) -> Result<()> {
+        block_on(self.back())?
+    }
+
+    /// Blocking [`Session::forward`]
+    pub fn forward_blocking(&self) -> Result<()> {
+        block_on(self.forward())?
+    }
+
+    /// Blocking [`Session::refresh`]
+    pub fn refresh_blocking(&self) -> Result<()> {
+        block_on(self.refresh())?
+    }
+
+    /// Blocking [`Session::current_url`]
+    pub fn current_url_blocking(&self) -> Result<String> {
+        block_on(self.current_url())?
+    }
+
+    /// Blocking [`Session::title`]
+    pub fn title_blocking(&self) -> Result<String> {
+        block_on(self.title())?
+    }
+
+    /// Blocking [`Session::find_element`]
+    pub fn find_element_blocking(&self, by: By) -> Result<Element> {
+        block_on(self.find_element(by))?
+    }
+
+    /// Blocking [`Session::find_elements`]
+    pub fn find_elements_blocking(&self, by: By) -> Result<Vec<Element>> {
+        block_on(self.find_elements(by))?
+    }
+
+    /// Blocking [`Session::execute_script`]
+    pub fn execute_script_blocking(
+        &self,
+        script: &str,
+        args: impl Serialize,
+    ) -> Result<ScriptValue> {
+        block_on(self.execute_script(script, args))?
+    }
+
+    /// Blocking [`Session::execute_async_script`]
+    pub fn execute_async_script_blocking(
+        &self,
+        script: &str,
+        args: impl Serialize,
+    ) -> Result<ScriptValue> {
+        block_on(self.execute_async_script(script, args))?
+    }
+
+    /// Blocking [`Session::screenshot`]
+    pub fn screenshot_blocking(&self) -> Result<Vec<u8>> {
+        block_on(self.screenshot())?
+    }
+
+    /// Blocking [`Session::screenshot_to_file`]
+    pub fn screenshot_to_file_blocking(&self, path: impl AsRef<Path>) -> Result<()> {
+        block_on(self.screenshot_to_file(path))?
+    }
+
+    /// Blocking [`Session::get_cookies`]
+    pub fn get_cookies_blocking(&self) -> Result<Vec<Cookie>> {
+        block_on(self.get_cookies())?
+    }
+
+    /// Blocking [`Session::add_cookie`]
+    pub fn add_cookie_blocking(&self, cookie: &Cookie) -> Result<()> {
+        block_on(self.add_cookie(cookie))?
+    }
+
+    /// Blocking [`Session::delete_cookie`]
+    pub fn delete_cookie_blocking(&self, name: &str) -> Result<()> {
+        block_on(self.delete_cookie(name))?
+    }
+
+    /// Blocking [`Session::delete_all_cookies`]
+    pub fn delete_all_cookies_blocking(&self) -> Result<()> {
+        block_on(self.delete_all_cookies())?
+    }
+
+    /// Blocking [`Session::new_window`]
+    pub fn new_window_blocking(&self, kind: WindowType) -> Result<String> {
+        block_on(self.new_window(kind))?
+    }
+
+    /// Blocking [`Session::window_handle`]
+    pub fn window_handle_blocking(&self) -> Result<String> {
+        block_on(self.window_handle())?
+    }
+
+    /// Blocking [`Session::window_handles`]
+    pub fn window_handles_blocking(&self) -> Result<Vec<String>> {
+        block_on(self.window_handles())?
+    }
+
+    /// Blocking [`Session::switch_to_window`]
+    pub fn switch_to_window_blocking(&self, handle: &str) -> Result<()> {
+        block_on(self.switch_to_window(handle))?
+    }
+
+    /// Blocking [`Session::close_window`]
+    pub fn close_window_blocking(&self) -> Result<Vec<String>> {
+        block_on(self.close_window())?
+    }
+
+    /// Blocking [`Session::switch_to_frame`]
+    pub fn switch_to_frame_blocking(&self, frame: Frame) -> Result<()> {
+        block_on(self.switch_to_frame(frame))?
+    }
+
+    /// Blocking [`Session::switch_to_default_content`]
+    pub fn switch_to_default_content_blocking(&self) -> Result<()> {
+        block_on(self.switch_to_default_content())?
+    }
+
+    /// Blocking [`Session::window_rect`]
+    pub fn window_rect_blocking(&self) -> Result<WindowRect> {
+        block_on(self.window_rect())?
+    }
+
+    /// Blocking [`Session::set_window_rect`]
+    pub fn set_window_rect_blocking(&self, rect: WindowRect) -> Result<WindowRect> {
+        block_on(self.set_window_rect(rect))?
+    }
+
+    /// Blocking [`Session::maximize`]
+    pub fn maximize_blocking(&self) -> Result<WindowRect> {
+        block_on(self.maximize())?
+    }
+
+    /// Blocking [`Session::accept_alert`]
+    pub fn accept_alert_blocking(&self) -> Result<()> {
+        block_on(self.accept_alert())?
+    }
+
+    /// Blocking [`Session::dismiss_alert`]
+    pub fn dismiss_alert_blocking(&self) -> Result<()> {
+        block_on(self.dismiss_alert())?
+    }
+
+    /// Blocking [`Session::alert_text`]
+    pub fn alert_text_blocking(&self) -> Result<String> {
+        block_on(self.alert_text())?
+    }
+
+    /// Blocking [`Session::send_alert_text`]
+    pub fn send_alert_text_blocking(&self, text: &str) -> Result<()> {
+        block_on(self.send_alert_text(text))?
+    }
+
+    /// Blocking [`Session::perform_actions`]
+    pub fn perform_actions_blocking(&self, actions: &Actions) -> Result<()> {
+        block_on(self.perform_actions(actions))?
+    }
+
+    /// Blocking [`Session::release_actions`]
+    pub fn release_actions_blocking(&self) -> Result<()> {
+        block_on(self.release_actions())?
+    }
+
+    /// Blocking [`Session::print`]
+    pub fn print_blocking(&self, options: &PrintOptions) -> Result<Vec<u8>> {
+        block_on(self.print(options))?
+    }
+
+    /// Blocking [`Session::find_shadow_element`]
+    pub fn find_shadow_element_blocking(&self, path: &[By]) -> Result<Element> {
+        block_on(self.find_shadow_element(path))?
+    }
+
+    /// Blocking [`Session::cdp`]
+    pub fn cdp_blocking(&self) -> Result<Cdp> {
+        block_on(self.cdp())?
+    }
+
+    /// Blocking [`Session::capture_console`]
+    pub fn capture_console_blocking(&self) -> Result<ConsoleLog> {
+        block_on(self.capture_console())?
+    }
+
+    /// Blocking [`Session::bidi`]
+    pub fn bidi_blocking(&self) -> Result<Bidi> {
+        block_on(self.bidi())?
+    }
+
+    /// Blocking [`Session::quit`]
+    pub fn quit_blocking(self) -> Result<()> {
+        block_on(self.quit())?
+    }
+}
+
+impl Element {
+    /// Blocking [`Element::click`]
+    pub fn click_blocking(&self) -> Result<()> {
+        block_on(self.click())?
+    }
+
+    /// Blocking [`Element::send_keys`]
+    pub fn send_keys_blocking(&self, text: &str) -> Result<()> {
+        block_on(self.send_keys(text))?
+    }
+
+    /// Blocking [`Element::clear`]
+    pub fn clear_blocking(&self) -> Result<()> {
+        block_on(self.clear())?
+    }
+
+    /// Blocking [`Element::text`]
+    pub fn text_blocking(&self) -> Result<String> {
+        block_on(self.text())?
+    }
+
+    /// Blocking [`Element::attribute`]
+    pub fn attribute_blocking(&self, name: &str) -> Result<Option<String>> {
+        block_on(self.attribute(name))?
+    }
+
+    /// Blocking [`Element::is_displayed`]
+    pub fn is_displayed_blocking(&self) -> Result<bool> {
+        block_on(self.is_displayed())?
+    }
+
+    /// Blocking [`Element::is_enabled`]
+    pub fn is_enabled_blocking(&self) -> Result<bool> {
+        block_on(self.is_enabled())?
+    }
+
+    /// Blocking [`Element::upload_file`]
+    pub fn upload_file_blocking(&self, path: impl AsRef<Path>) -> Result<()> {
+        block_on(self.upload_file(path))?
+    }
+
+    /// Blocking [`Element::shadow_root`]
+    pub fn shadow_root_blocking(&self) -> Result<ShadowRoot> {
+        block_on(self.shadow_root())?
+    }
+
+    /// Blocking [`Element::find_element`]
+    pub fn find_element_blocking(&self, by: By) -> Result<Element> {
+        block_on(self.find_element(by))?
+    }
+
+    /// Blocking [`Element::find_elements`]
+    pub fn find_elements_blocking(&self, by: By) -> Result<Vec<Element>> {
+        block_on(self.find_elements(by))?
+    }
+}
+
+impl ElementWait {
+    /// Blocking [`ElementWait::wait`]
+    pub fn wait_blocking(self) -> Result<Element> {
+        block_on(self.wait())?
+    }
+}
+
+impl ShadowRoot {
+    /// Blocking [`ShadowRoot::find_element`]
+    pub fn find_element_blocking(&self, by: By) -> Result<Element> {
+        block_on(self.find_element(by))?
+    }
+
+    /// Blocking [`ShadowRoot::find_elements`]
+    pub fn find_elements_blocking(&self, by: By) -> Result<Vec<Element>> {
+        block_on(self.find_elements(by))?
+    }
+}
+
+impl Cdp {
+    /// Blocking [`Cdp::connect`]
+    pub fn connect_blocking(url: &str) -> Result<Cdp> {
+        block_on(Cdp::connect(url))?
+    }
+
+    /// Blocking [`Cdp::from_debugger_address`]
+    pub fn from_debugger_address_blocking(address: &str) -> Result<Cdp> {
+        block_on(Cdp::from_debugger_address(address))?
+    }
+
+    /// Blocking [`Cdp::attach`]
+    pub fn attach_blocking(&self, target_id: &str) -> Result<Cdp> {
+        block_on(self.attach(target_id))?
+    }
+
+    /// Blocking [`Cdp::send`]
+    pub fn send_blocking(&self, method: &str, params: impl Serialize) -> Result<Value> {
+        block_on(self.send(method, params))?
+    }
+
+    /// Blocking [`Cdp::execute`]
+    pub fn execute_blocking<C: CdpCommand>(&self, command: C) -> Result<C::Response> {
+        block_on(self.execute(command))?
+    }
+
+    /// Blocking [`Cdp::monitor_network`]
+    pub fn monitor_network_blocking(&self) -> Result<NetworkEvents> {
+        block_on(self.monitor_network())?
+    }
+
+    /// Blocking [`Cdp::response_body`]
+    pub fn response_body_blocking(&self, request_id: &str) -> Result<Vec<u8>> {
+        block_on(self.response_body(request_id))?
+    }
+
+    /// Blocking [`Cdp::block_urls`]
+    pub fn block_urls_blocking(&self, patterns: &[&str]) -> Result<()> {
+        block_on(self.block_urls(patterns))?
+    }
+
+    /// Blocking [`Cdp::console`]
+    pub fn console_blocking(&self) -> Result<ConsoleEvents> {
+        block_on(self.console())?
+    }
+
+    /// Blocking [`Cdp::capture_console`]
+    pub fn capture_console_blocking(&self) -> Result<ConsoleLog> {
+        block_on(self.capture_console())?
+    }
+
+    /// Blocking [`Cdp::performance_metrics`]
+    pub fn performance_metrics_blocking(&self) -> Result<PerformanceMetrics> {
+        block_on(self.performance_metrics())?
+    }
+
+    /// Blocking [`Cdp::start_trace`]
+    pub fn start_trace_blocking(&self, categories: &[&str]) -> Result<Trace> {
+        block_on(self.start_trace(categories))?
+    }
+
+    /// Blocking [`Cdp::intercept`]
+    pub fn intercept_blocking(&self, patterns: &[&str]) -> Result<Interception> {
+        block_on(self.intercept(patterns))?
+    }
+}
+
+impl CdpEvents {
+    /// Blocking [`CdpEvents::next`]
+    pub fn next_blocking(&mut self) -> Result<Option<CdpEvent>> {
+        block_on(self.next())
+    }
+}
+
+impl NetworkEvents {
+    /// Blocking [`NetworkEvents::next`]
+    pub fn next_blocking(&mut self) -> Result<Option<NetworkEvent>> {
+        block_on(self.next())
+    }
+}
+
+impl Interception {
+    /// Blocking [`Interception::next`]
+    pub fn next_blocking(&mut self) -> Result<Option<PausedRequest>> {
+        block_on(self.next())
+    }
+
+    /// Blocking [`Interception::stop`]
+    pub fn stop_blocking(self) -> Result<()> {
+        block_on(self.stop())?
+    }
+}
+
+impl PausedRequest {
+    /// Blocking [`PausedRequest::continue_request`]
+    pub fn continue_request_blocking(self) -> Result<()> {
+        block_on(self.continue_request())?
+    }
+
+    /// Blocking [`PausedRequest::abort`]
+    pub fn abort_blocking(self) -> Result<()> {
+        block_on(self.abort())?
+    }
+
+    /// Blocking [`PausedRequest::fulfill`]
+    pub fn fulfill_blocking(
+        self,
+        status: u16,
+        headers: &[(&str, &str)],
+        body: &[u8],
+    ) -> Result<()> {
+        block_on(self.fulfill(status, headers, body))?
+    }
+}
+
+impl ConsoleEvents {
+    /// Blocking [`ConsoleEvents::next`]
+    pub fn next_blocking(&mut self) -> Result<Option<ConsoleMessage>> {
+        block_on(self.next())
+    }
+}
+
+impl Trace {
+    /// Blocking [`Trace::stop`]
+    pub fn stop_blocking(self) -> Result<Vec<u8>> {
+        block_on(self.stop())?
+    }
+
+    /// Blocking [`Trace::stop_to_file`]
+    pub fn stop_to_file_blocking(self, path: impl AsRef<Path>) -> Result<()> {
+        block_on(self.stop_to_file(path))?
+    }
+}
+
+impl Bidi {
+    /// Blocking [`Bidi::connect`]
+    pub fn connect_blocking(url: &str) -> Result<Bidi> {
+        block_on(Bidi::connect(url))?
+    }
+
+    /// Blocking [`Bidi::send`]
+    pub fn send_blocking(&self, method: &str, params: impl Serialize) -> Result<Value> {
+        block_on(self.send(method, params))?
+    }
+
+    /// Blocking [`Bidi::subscribe`]
+    pub fn subscribe_blocking(&self, events: &[&str]) -> Result<BidiEvents> {
+        block_on(self.subscribe(events))?
+    }
+
+    /// Blocking [`Bidi::browsing_contexts`]
+    pub fn browsing_contexts_blocking(&self) -> Result<Vec<BrowsingContext>> {
+        block_on(self.browsing_contexts())?
+    }
+
+    /// Blocking [`Bidi::navigate`]
+    pub fn navigate_blocking(&self, context: &str, url: &str) -> Result<()> {
+        block_on(self.navigate(context, url))?
+    }
+
+    /// Blocking [`Bidi::console`]
+    pub fn console_blocking(&self) -> Result<ConsoleEvents> {
+        block_on(self.console())?
+    }
+
+    /// Blocking [`Bidi::capture_console`]
+    pub fn capture_console_blocking(&self) -> Result<ConsoleLog> {
+        block_on(self.capture_console())?
+    }
+}
+
+impl BidiEvents {
+    /// Blocking [`BidiEvents::next`]
+    pub fn next_blocking(&mut self) -> Result<Option<BidiEvent>> {
+        block_on(self.next())
+    }
+}
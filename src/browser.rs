@@ -0,0 +1,156 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{DriverError, Result},
+    manager::read_binary_version,
+    rt, DriverInfo,
+};
+
+/// A Chrome/Chromium installation found on this machine
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChromeInstall {
+    /// Path to the browser executable
+    pub path: String,
+    /// Browser version, e.g. `126.0.6478.126`
+    pub version: String,
+}
+
+/// Find the installed Chrome/Chromium binary and read its version.
+///
+/// * macOS: app bundles in `/Applications` and `~/Applications`.
+/// * Windows: the standard `Program Files` / `%LOCALAPPDATA%` install folders.
+/// * Linux: `google-chrome`, `chromium` and friends on `PATH`.
+pub async fn detect_chrome_version() -> Result<ChromeInstall> {
+    for path in chrome_candidates() {
+        if !path.exists() {
+            continue;
+        }
+        if let Some(version) = read_chrome_version(&path).await {
+            return Ok(ChromeInstall {
+                path: path.to_string_lossy().into_owned(),
+                version,
+            });
+        }
+    }
+    Err(DriverError::ChromeNotFound)
+}
+
+/// Every Chrome/Chromium installation [`detect_chrome_version`] considers, most preferred
+/// first.
+///
+/// * Aliases of the same binary, e.g. `google-chrome` and `google-chrome-stable`, are
+///   listed once.
+/// * Candidates whose version can't be read are skipped.
+pub async fn find_chrome_installs() -> Vec<ChromeInstall> {
+    let mut installs = Vec::new();
+    let mut seen = Vec::new();
+    for path in chrome_candidates() {
+        let Ok(resolved) = fs::canonicalize(&path) else {
+            continue;
+        };
+        if seen.contains(&resolved) {
+            continue;
+        }
+        seen.push(resolved);
+        if let Some(version) = read_chrome_version(&path).await {
+            installs.push(ChromeInstall {
+                path: path.to_string_lossy().into_owned(),
+                version,
+            });
+        }
+    }
+    installs
+}
+
+/// Find `chromedriver` on `PATH` and read its version.
+///
+/// * Returns `None` if there is none, or it doesn't run.
+pub async fn find_system_driver() -> Option<DriverInfo> {
+    let path = find_in_path("chromedriver")?;
+    let version = read_binary_version(&path).await?;
+    Some(DriverInfo::new(
+        path.to_string_lossy().into_owned(),
+        version,
+    ))
+}
+
+/// Candidate browser executables for the current OS, most preferred first
+fn chrome_candidates() -> Vec<PathBuf> {
+    match env::consts::OS {
+        "macos" => {
+            let bundles = [
+                "Google Chrome.app/Contents/MacOS/Google Chrome",
+                "Chromium.app/Contents/MacOS/Chromium",
+                "Google Chrome Canary.app/Contents/MacOS/Google Chrome Canary",
+            ];
+            let mut roots = vec![PathBuf::from("/Applications")];
+            if let Some(home) = env::var_os("HOME") {
+                roots.push(Path::new(&home).join("Applications"));
+            }
+            roots
+                .iter()
+                .flat_map(|root| bundles.iter().map(move |bundle| root.join(bundle)))
+                .collect()
+        }
+        "windows" => ["PROGRAMFILES", "PROGRAMFILES(X86)", "LOCALAPPDATA"]
+            .iter()
+            .filter_map(env::var_os)
+            .map(|root| Path::new(&root).join(r"Google\Chrome\Application\chrome.exe"))
+            .collect(),
+        _ => [
+            "google-chrome",
+            "google-chrome-stable",
+            "chromium",
+            "chromium-browser",
+        ]
+        .iter()
+        .filter_map(|name| find_in_path(name))
+        .collect(),
+    }
+}
+
+/// Read the browser version without launching a window
+pub(crate) async fn read_chrome_version(path: &Path) -> Option<String> {
+    if cfg!(windows) {
+        // chrome.exe --version doesn't print on Windows; the install folder
+        // holds one sub-directory per version instead
+        let app_dir = path.parent()?;
+        return fs::read_dir(app_dir)
+            .ok()?
+            .filter_map(|entry| parse_version(&entry.ok()?.file_name().to_string_lossy()))
+            .max_by_key(|version| version_key(version));
+    }
+
+    let output = rt::command_output(path, &["--version"]).await.ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .find_map(parse_version)
+}
+
+/// Locate an executable on `PATH`
+pub(crate) fn find_in_path(name: &str) -> Option<PathBuf> {
+    let exe = if cfg!(windows) {
+        format!("{name}.exe")
+    } else {
+        name.to_string()
+    };
+    env::split_paths(&env::var_os("PATH")?)
+        .map(|dir| dir.join(&exe))
+        .find(|path| path.is_file())
+}
+
+/// Accept `A.B.C.D` version strings
+pub(crate) fn parse_version(token: &str) -> Option<String> {
+    let is_version =
+        token.split('.').count() == 4 && token.split('.').all(|p| p.parse::<u32>().is_ok());
+    is_version.then(|| token.to_string())
+}
+
+/// Numeric sort key of an `A.B.C.D` version string
+pub(crate) fn version_key(version: &str) -> Vec<u32> {
+    version.split('.').filter_map(|p| p.parse().ok()).collect()
+}
@@ -0,0 +1,650 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+use crate::{
+    browser::{parse_version, version_key},
+    error::{DriverError, Result},
+    manager::read_binary_version,
+    rt, DriverInfo,
+};
+
+/// Name of the pointer to the active driver inside `out_dir`
+const CURRENT: &str = "current";
+
+/// Subdirectory of the platform cache directory used by this crate
+const CACHE_SUBDIR: &str = "chrome-driver-rs";
+
+/// Per-user cache directory for drivers shared across projects.
+///
+/// * macOS: `~/Library/Caches/chrome-driver-rs`
+/// * Windows: `%LOCALAPPDATA%\chrome-driver-rs`
+/// * Elsewhere: `$XDG_CACHE_HOME/chrome-driver-rs`, defaulting to `~/.cache/chrome-driver-rs`
+pub fn default_cache_dir() -> Result<PathBuf> {
+    let var = |name| {
+        env::var_os(name)
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+    };
+    let base = match env::consts::OS {
+        "macos" => var("HOME").map(|home| home.join("Library").join("Caches")),
+        "windows" => var("LOCALAPPDATA"),
+        _ => var("XDG_CACHE_HOME").or_else(|| var("HOME").map(|home| home.join(".cache"))),
+    };
+    base.map(|base| base.join(CACHE_SUBDIR)).ok_or_else(|| {
+        DriverError::Config("cannot locate the user cache directory; pass an out_dir".to_string())
+    })
+}
+
+/// Remove all but the `keep` newest version directories in `out_dir`.
+///
+/// * Only `{out_dir}/{version}/` directories are considered.
+/// * The [`current_driver`]'s version is never removed, even with `keep == 0`;
+///   without a `current` pointer the newest version is kept.
+/// * Returns the number of bytes reclaimed, like [`uninstall`].
+pub async fn cleanup_old_drivers(out_dir: &str, keep: usize) -> Result<u64> {
+    let versions = installed_versions(Path::new(out_dir))?;
+
+    // 1️⃣ Find the active version
+    let active = match current_driver(out_dir).await? {
+        Some(current) => Some(current.version),
+        None => versions.first().cloned(),
+    };
+
+    // 2️⃣ Pick everything past the newest `keep`
+    let stale: Vec<String> = versions
+        .into_iter()
+        .skip(keep)
+        .filter(|version| Some(version) != active.as_ref())
+        .collect();
+
+    // 3️⃣ Remove them
+    let out_dir = out_dir.to_string();
+    rt::spawn_blocking(move || {
+        let mut reclaimed = 0;
+        for version in &stale {
+            reclaimed += remove_version(Path::new(&out_dir), version)?;
+        }
+        Ok(reclaimed)
+    })
+    .await
+}
+
+/// Point `{out_dir}/current` back at the newest installed driver older than the current one.
+///
+/// * Nothing is removed, so the newer version can be switched back to by installing it again.
+/// * Without a `current` pointer the newest installed driver counts as current.
+/// * Fails with [`DriverError::NoPreviousVersion`] when there is no older driver.
+pub async fn rollback(out_dir: &str) -> Result<DriverInfo> {
+    let installed = list_installed(out_dir).await?;
+    let current = match current_driver(out_dir).await? {
+        Some(current) => Some(current.version),
+        None => installed.first().map(|driver| driver.version.clone()),
+    };
+    let previous = current.and_then(|current| {
+        installed
+            .into_iter()
+            .find(|driver| version_key(&driver.version) < version_key(&current))
+    });
+    let previous = previous.ok_or_else(|| DriverError::NoPreviousVersion {
+        out_dir: PathBuf::from(out_dir),
+    })?;
+
+    set_current(Path::new(out_dir), Path::new(&previous.driver_path))?;
+    info!("⏪ Rolled back to ChromeDriver {}", previous.version);
+    Ok(previous)
+}
+
+/// Remove `{out_dir}/{version}/`, returning the number of bytes reclaimed.
+///
+/// * Removes the `current` pointer too if it pointed into that version.
+/// * A version that is not installed reclaims nothing.
+pub async fn uninstall(out_dir: &str, version: &str) -> Result<u64> {
+    let pointed_here = current_driver(out_dir)
+        .await?
+        .is_some_and(|current| current.version == version);
+    let (out_dir, version) = (out_dir.to_string(), version.to_string());
+    rt::spawn_blocking(move || {
+        let out_dir = Path::new(&out_dir);
+        let reclaimed = remove_version(out_dir, &version)?;
+        if pointed_here {
+            remove_current(out_dir)?;
+        }
+        Ok(reclaimed)
+    })
+    .await
+}
+
+/// Remove every installed version and the `current` pointer, returning the bytes reclaimed
+pub async fn uninstall_all(out_dir: &str) -> Result<u64> {
+    let out_dir = out_dir.to_string();
+    rt::spawn_blocking(move || {
+        let out_dir = Path::new(&out_dir);
+        let mut reclaimed = 0;
+        for version in installed_versions(out_dir)? {
+            reclaimed += remove_version(out_dir, &version)?;
+        }
+        remove_current(out_dir)?;
+        Ok(reclaimed)
+    })
+    .await
+}
+
+/// Remove `{out_dir}/{version}/` if present, returning its size
+fn remove_version(out_dir: &Path, version: &str) -> Result<u64> {
+    let path = out_dir.join(version);
+    if !path.is_dir() {
+        return Ok(0);
+    }
+    let size = dir_size(&path);
+    fs::remove_dir_all(&path)
+        .map_err(|e| DriverError::io(format!("removing {}", path.display()), e))?;
+    info!("🗑️ Removed {} ({size} bytes)", path.display());
+    Ok(size)
+}
+
+/// Total size of the files below `dir`, not following symlinks
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let metadata = fs::symlink_metadata(entry.path()).ok()?;
+            Some(if metadata.is_dir() {
+                dir_size(&entry.path())
+            } else {
+                metadata.len()
+            })
+        })
+        .sum()
+}
+
+/// Every driver installed in `out_dir`, newest first.
+///
+/// * Covers every layout this crate installs: Chrome for Testing, legacy, linux-arm64 and flat.
+/// * Versions without a driver executable (e.g. only the browser) are skipped.
+pub async fn list_installed(out_dir: &str) -> Result<Vec<DriverInfo>> {
+    let out_dir = out_dir.to_string();
+    rt::spawn_blocking(move || {
+        let drivers = installed_versions(Path::new(&out_dir))?
+            .into_iter()
+            .filter_map(|version| {
+                let path = find_driver(&Path::new(&out_dir).join(&version))?;
+                Some(DriverInfo::new(path.to_string_lossy().into_owned(), version).with_manifest())
+            })
+            .collect();
+        Ok(drivers)
+    })
+    .await
+}
+
+/// Shallowest `chromedriver` executable below `version_dir`
+fn find_driver(version_dir: &Path) -> Option<PathBuf> {
+    let mut level = vec![version_dir.to_path_buf()];
+    // `{version}/{platform}/{zip name}/chromedriver` is the deepest layout
+    for _ in 0..3 {
+        let mut next = Vec::new();
+        for dir in level {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if name.starts_with('.') {
+                    continue;
+                }
+                let path = entry.path();
+                if path.is_dir() {
+                    next.push(path);
+                } else if name == "chromedriver" || name == "chromedriver.exe" {
+                    return Some(path);
+                }
+            }
+        }
+        level = next;
+    }
+    None
+}
+
+/// Version-named subdirectories of `out_dir`, newest first
+pub(crate) fn installed_versions(out_dir: &Path) -> Result<Vec<String>> {
+    let entries = match fs::read_dir(out_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(DriverError::io(format!("reading {}", out_dir.display()), e)),
+    };
+    let mut versions: Vec<String> = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let is_version = parse_version(&name).as_deref() == Some(name.as_str());
+            (is_version && entry.path().is_dir()).then_some(name)
+        })
+        .collect();
+    versions.sort_by_key(|version| std::cmp::Reverse(version_key(version)));
+    Ok(versions)
+}
+
+/// The driver `{out_dir}/current` points at, if any.
+///
+/// * On Unix `current` is a relative symlink to the driver executable, usable from shell
+///   scripts as `./driver/current --port=9515`.
+/// * On Windows it is a text file holding the path of the executable relative to `out_dir`.
+pub async fn current_driver(out_dir: &str) -> Result<Option<DriverInfo>> {
+    let pointer = Path::new(out_dir).join(CURRENT);
+    let target = match read_pointer(&pointer) {
+        Ok(target) => target,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(DriverError::io(format!("reading {}", pointer.display()), e)),
+    };
+    let path = Path::new(out_dir).join(target);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    // The version is the first version-named directory, else ask the binary
+    let from_path = path
+        .strip_prefix(out_dir)
+        .ok()
+        .and_then(|relative| relative.components().next())
+        .and_then(|first| {
+            let name = first.as_os_str().to_string_lossy();
+            parse_version(&name).filter(|version| *version == name)
+        });
+    let version = match from_path {
+        Some(version) => version,
+        None => read_binary_version(&path)
+            .await
+            .unwrap_or_else(|| "unknown".to_string()),
+    };
+    Ok(Some(
+        DriverInfo::new(path.to_string_lossy().into_owned(), version).with_manifest(),
+    ))
+}
+
+/// Point `{out_dir}/current` at `driver_path`, replacing the old pointer atomically
+pub(crate) fn set_current(out_dir: &Path, driver_path: &Path) -> Result<()> {
+    let pointer = out_dir.join(CURRENT);
+    let staging = out_dir.join(format!(".{CURRENT}.staging"));
+    let target = driver_path
+        .strip_prefix(out_dir)
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|_| driver_path.to_path_buf());
+    let context = || format!("updating {}", pointer.display());
+
+    let _ = fs::remove_file(&staging);
+    write_pointer(&staging, &target).map_err(|e| DriverError::io(context(), e))?;
+    fs::rename(&staging, &pointer).map_err(|e| DriverError::io(context(), e))
+}
+
+/// Remove `{out_dir}/current` if present
+fn remove_current(out_dir: &Path) -> Result<()> {
+    let pointer = out_dir.join(CURRENT);
+    match fs::remove_file(&pointer) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(DriverError::io(
+            format!("removing {}", pointer.display()),
+            e,
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(unix)]
+fn write_pointer(path: &Path, target: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, path)
+}
+
+#[cfg(not(unix))]
+fn write_pointer(path: &Path, target: &Path) -> std::io::Result<()> {
+    fs::write(path, target.to_string_lossy().as_bytes())
+}
+
+#[cfg(unix)]
+fn read_pointer(path: &Path) -> std::io::Result<PathBuf> {
+    fs::read_link(path)
+}
+
+#[cfg(not(unix))]
+fn read_pointer(path: &Path) -> std::io::Result<PathBuf> {
+    Ok(PathBuf::from(fs::read_to_string(path)?.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = "118.0.5993.70";
+    const MIDDLE: &str = "119.0.6045.105";
+    const NEW: &str = "120.0.6099.109";
+
+    /// A fresh `out_dir` nothing else writes to
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "chrome-driver-rs-cache-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Install a fake Chrome for Testing driver of `version`, returning its path
+    fn install(out_dir: &Path, version: &str) -> PathBuf {
+        let dir = out_dir.join(version).join("linux64/chromedriver-linux64");
+        fs::create_dir_all(&dir).unwrap();
+        let driver = dir.join("chromedriver");
+        fs::write(&driver, "binary").unwrap();
+        driver
+    }
+
+    fn versions(out_dir: &Path) -> Vec<String> {
+        installed_versions(out_dir).unwrap()
+    }
+
+    #[tokio::test]
+    async fn cleanup_keeps_the_newest_versions() {
+        let out_dir = scratch("cleanup-keep");
+        for version in [OLD, MIDDLE, NEW] {
+            install(&out_dir, version);
+        }
+        let reclaimed = cleanup_old_drivers(out_dir.to_str().unwrap(), 2)
+            .await
+            .unwrap();
+        assert_eq!(versions(&out_dir), [NEW, MIDDLE]);
+        assert_eq!(reclaimed, "binary".len() as u64);
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn cleanup_never_removes_the_current_version() {
+        let out_dir = scratch("cleanup-current");
+        install(&out_dir, OLD);
+        let middle = install(&out_dir, MIDDLE);
+        install(&out_dir, NEW);
+        set_current(&out_dir, &middle).unwrap();
+
+        let reclaimed = cleanup_old_drivers(out_dir.to_str().unwrap(), 0)
+            .await
+            .unwrap();
+        assert_eq!(versions(&out_dir), [MIDDLE]);
+        assert_eq!(reclaimed, 2 * "binary".len() as u64);
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn cleanup_without_current_keeps_the_newest_version() {
+        let out_dir = scratch("cleanup-no-current");
+        install(&out_dir, OLD);
+        install(&out_dir, NEW);
+        cleanup_old_drivers(out_dir.to_str().unwrap(), 0)
+            .await
+            .unwrap();
+        assert_eq!(versions(&out_dir), [NEW]);
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn cleanup_ignores_other_directories() {
+        let out_dir = scratch("cleanup-other");
+        install(&out_dir, OLD);
+        install(&out_dir, NEW);
+        fs::create_dir_all(out_dir.join("downloads")).unwrap();
+        fs::create_dir_all(out_dir.join("120.0")).unwrap();
+
+        cleanup_old_drivers(out_dir.to_str().unwrap(), 1)
+            .await
+            .unwrap();
+        assert_eq!(versions(&out_dir), [NEW]);
+        assert!(out_dir.join("downloads").is_dir());
+        assert!(out_dir.join("120.0").is_dir());
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn cleanup_of_a_missing_out_dir_reclaims_nothing() {
+        let parent = scratch("cleanup-missing");
+        let out_dir = parent.join("absent");
+        let reclaimed = cleanup_old_drivers(out_dir.to_str().unwrap(), 0)
+            .await
+            .unwrap();
+        assert_eq!(reclaimed, 0);
+        fs::remove_dir_all(&parent).unwrap();
+    }
+
+    #[tokio::test]
+    async fn lists_installed_drivers_newest_first() {
+        let out_dir = scratch("list");
+        let old = install(&out_dir, OLD);
+        let new = install(&out_dir, NEW);
+        let installed = list_installed(out_dir.to_str().unwrap()).await.unwrap();
+        let listed: Vec<_> = installed
+            .iter()
+            .map(|driver| (driver.version.as_str(), PathBuf::from(&driver.driver_path)))
+            .collect();
+        assert_eq!(listed, [(NEW, new), (OLD, old)]);
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn lists_every_layout_and_skips_versions_without_a_driver() {
+        let out_dir = scratch("list-layouts");
+        let layouts = [
+            ("113.0.5672.63", "linux64/chromedriver_linux64/chromedriver"),
+            ("120.0.6099.109", "chromedriver"),
+            (
+                "121.0.6167.85",
+                "linux-arm64/chromedriver-linux-arm64/chromedriver",
+            ),
+            ("122.0.6261.94", "win64/chromedriver-win64/chromedriver.exe"),
+        ];
+        for (version, relative) in layouts {
+            let path = out_dir.join(version).join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "binary").unwrap();
+        }
+        // Only the browser, and a leftover staging directory
+        fs::create_dir_all(out_dir.join("123.0.6312.58/linux64/chrome-linux64")).unwrap();
+        fs::create_dir_all(out_dir.join("124.0.6367.60/linux64/.chromedriver-linux64.staging"))
+            .unwrap();
+        fs::write(
+            out_dir.join("124.0.6367.60/linux64/.chromedriver-linux64.staging/chromedriver"),
+            "partial",
+        )
+        .unwrap();
+
+        let installed = list_installed(out_dir.to_str().unwrap()).await.unwrap();
+        let listed: Vec<_> = installed
+            .iter()
+            .map(|driver| driver.version.as_str())
+            .collect();
+        assert_eq!(
+            listed,
+            [
+                "122.0.6261.94",
+                "121.0.6167.85",
+                "120.0.6099.109",
+                "113.0.5672.63"
+            ]
+        );
+        for driver in &installed {
+            let (_, relative) = layouts
+                .iter()
+                .find(|(version, _)| *version == driver.version)
+                .unwrap();
+            assert_eq!(
+                PathBuf::from(&driver.driver_path),
+                out_dir.join(&driver.version).join(relative)
+            );
+        }
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn lists_nothing_for_a_missing_out_dir() {
+        let parent = scratch("list-missing");
+        let installed = list_installed(parent.join("absent").to_str().unwrap())
+            .await
+            .unwrap();
+        assert!(installed.is_empty());
+        fs::remove_dir_all(&parent).unwrap();
+    }
+
+    #[tokio::test]
+    async fn uninstall_removes_the_version_and_a_pointer_into_it() {
+        let out_dir = scratch("uninstall-current");
+        install(&out_dir, OLD);
+        let new = install(&out_dir, NEW);
+        set_current(&out_dir, &new).unwrap();
+        let dir = out_dir.to_str().unwrap();
+
+        assert_eq!(uninstall(dir, NEW).await.unwrap(), "binary".len() as u64);
+        assert_eq!(versions(&out_dir), [OLD]);
+        assert!(current_driver(dir).await.unwrap().is_none());
+        assert!(fs::symlink_metadata(out_dir.join(CURRENT)).is_err());
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn uninstall_keeps_a_pointer_elsewhere() {
+        let out_dir = scratch("uninstall-other");
+        let old = install(&out_dir, OLD);
+        install(&out_dir, NEW);
+        set_current(&out_dir, &old).unwrap();
+        let dir = out_dir.to_str().unwrap();
+
+        uninstall(dir, NEW).await.unwrap();
+        assert_eq!(current_driver(dir).await.unwrap().unwrap().version, OLD);
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn uninstalling_a_missing_version_reclaims_nothing() {
+        let out_dir = scratch("uninstall-missing");
+        install(&out_dir, OLD);
+        let dir = out_dir.to_str().unwrap();
+        assert_eq!(uninstall(dir, NEW).await.unwrap(), 0);
+        assert_eq!(versions(&out_dir), [OLD]);
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn uninstall_all_removes_every_version_and_the_pointer() {
+        let out_dir = scratch("uninstall-all");
+        install(&out_dir, OLD);
+        let new = install(&out_dir, NEW);
+        set_current(&out_dir, &new).unwrap();
+        fs::create_dir_all(out_dir.join("downloads")).unwrap();
+        let dir = out_dir.to_str().unwrap();
+
+        assert_eq!(uninstall_all(dir).await.unwrap(), 2 * "binary".len() as u64);
+        assert!(versions(&out_dir).is_empty());
+        assert!(fs::symlink_metadata(out_dir.join(CURRENT)).is_err());
+        assert!(out_dir.join("downloads").is_dir());
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn rollback_steps_back_one_version_at_a_time() {
+        let out_dir = scratch("rollback");
+        let old = install(&out_dir, OLD);
+        install(&out_dir, MIDDLE);
+        let new = install(&out_dir, NEW);
+        set_current(&out_dir, &new).unwrap();
+        let dir = out_dir.to_str().unwrap();
+
+        assert_eq!(rollback(dir).await.unwrap().version, MIDDLE);
+        assert_eq!(current_driver(dir).await.unwrap().unwrap().version, MIDDLE);
+        let previous = rollback(dir).await.unwrap();
+        assert_eq!(PathBuf::from(previous.driver_path), old);
+        assert!(matches!(
+            rollback(dir).await,
+            Err(DriverError::NoPreviousVersion { .. })
+        ));
+        // Nothing was removed
+        assert_eq!(versions(&out_dir), [NEW, MIDDLE, OLD]);
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn rollback_without_current_starts_from_the_newest() {
+        let out_dir = scratch("rollback-no-current");
+        install(&out_dir, OLD);
+        install(&out_dir, NEW);
+        let dir = out_dir.to_str().unwrap();
+        assert_eq!(rollback(dir).await.unwrap().version, OLD);
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn current_points_relative_to_out_dir() {
+        let out_dir = scratch("current-relative");
+        let old = install(&out_dir, OLD);
+        let new = install(&out_dir, NEW);
+        let dir = out_dir.to_str().unwrap();
+        assert!(current_driver(dir).await.unwrap().is_none());
+
+        set_current(&out_dir, &old).unwrap();
+        set_current(&out_dir, &new).unwrap();
+        assert_eq!(
+            read_pointer(&out_dir.join(CURRENT)).unwrap(),
+            Path::new(NEW).join("linux64/chromedriver-linux64/chromedriver")
+        );
+        let current = current_driver(dir).await.unwrap().unwrap();
+        assert_eq!(
+            (current.version.as_str(), PathBuf::from(current.driver_path)),
+            (NEW, new)
+        );
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn current_pointing_at_a_removed_driver_is_none() {
+        let out_dir = scratch("current-dangling");
+        let new = install(&out_dir, NEW);
+        set_current(&out_dir, &new).unwrap();
+        fs::remove_dir_all(out_dir.join(NEW)).unwrap();
+        assert!(current_driver(out_dir.to_str().unwrap())
+            .await
+            .unwrap()
+            .is_none());
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn current_outside_out_dir_asks_the_binary_for_its_version() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let out_dir = scratch("current-outside");
+        let elsewhere = scratch("current-outside-driver");
+        let driver = elsewhere.join("chromedriver");
+        fs::write(
+            &driver,
+            format!("#!/bin/sh\necho 'ChromeDriver {NEW} (abc)'\n"),
+        )
+        .unwrap();
+        fs::set_permissions(&driver, fs::Permissions::from_mode(0o755)).unwrap();
+        let dir = out_dir.to_str().unwrap();
+
+        set_current(&out_dir, &driver).unwrap();
+        assert_eq!(read_pointer(&out_dir.join(CURRENT)).unwrap(), driver);
+        let current = current_driver(dir).await.unwrap().unwrap();
+        assert_eq!(
+            (current.version.as_str(), PathBuf::from(current.driver_path)),
+            (NEW, driver.clone())
+        );
+
+        // A binary that can't report its version
+        fs::set_permissions(&driver, fs::Permissions::from_mode(0o644)).unwrap();
+        assert_eq!(
+            current_driver(dir).await.unwrap().unwrap().version,
+            "unknown"
+        );
+        fs::remove_dir_all(&out_dir).unwrap();
+        fs::remove_dir_all(&elsewhere).unwrap();
+    }
+}
@@ -0,0 +1,227 @@
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+use std::{fmt, sync::Arc};
+use tokio::sync::mpsc;
+use tracing::info;
+
+use crate::{
+    error::{DriverError, Result},
+    http,
+    rpc::{Connection, Protocol},
+    rt, session, Session,
+};
+
+/// A DevTools protocol command with typed parameters and result, run by [`Cdp::execute`]
+pub trait CdpCommand: Serialize {
+    /// Protocol method, e.g. `Page.navigate`
+    const METHOD: &'static str;
+    /// The command's `result`
+    type Response: DeserializeOwned;
+}
+
+/// An event pushed by the browser, e.g. `Network.requestWillBeSent`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CdpEvent {
+    /// Protocol method, e.g. `Page.loadEventFired`
+    pub method: String,
+    /// Event payload
+    pub params: Value,
+    /// Attached target the event belongs to; `None` for browser-wide events
+    pub session_id: Option<String>,
+}
+
+impl CdpEvent {
+    /// Deserialize the payload into a `#[derive(Deserialize)]` struct
+    pub fn convert<T: DeserializeOwned>(&self) -> Result<T> {
+        serde_json::from_value(self.params.clone()).map_err(|e| DriverError::DevTools {
+            method: self.method.clone(),
+            message: format!("unexpected event parameters: {e}"),
+        })
+    }
+}
+
+/// Events received through [`Cdp::subscribe`]; ends when the connection closes
+pub struct CdpEvents {
+    receiver: mpsc::UnboundedReceiver<CdpEvent>,
+    /// Subscriptions keep the connection open
+    _connection: Arc<Connection>,
+}
+
+impl CdpEvents {
+    pub(crate) fn new(
+        connection: &Arc<Connection>,
+        receiver: mpsc::UnboundedReceiver<CdpEvent>,
+    ) -> CdpEvents {
+        CdpEvents {
+            receiver,
+            _connection: Arc::clone(connection),
+        }
+    }
+
+    /// Wait for the next event, or `None` once the browser is gone
+    pub async fn next(&mut self) -> Option<CdpEvent> {
+        self.receiver.recv().await
+    }
+}
+
+impl fmt::Debug for CdpEvents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CdpEvents")
+            .field("url", &self._connection.url())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A Chrome DevTools Protocol connection, for what WebDriver doesn't cover (network
+/// capture, emulation, ...).
+///
+/// * [`Session::cdp`] connects to a session's browser, [`Cdp::from_debugger_address`] to
+///   a Chrome started with `--remote-debugging-port`.
+/// * Clones share the connection, which closes once the last clone and
+///   [`CdpEvents`] are dropped.
+#[derive(Clone)]
+pub struct Cdp {
+    connection: Arc<Connection>,
+    session_id: Option<String>,
+}
+
+impl Cdp {
+    /// Connect to a DevTools websocket, e.g. the `webSocketDebuggerUrl` of `/json/version`
+    pub async fn connect(url: &str) -> Result<Cdp> {
+        let connection = Connection::open(url, Protocol::Cdp).await?;
+        info!("🔌 connected to DevTools at {url}");
+        Ok(Cdp {
+            connection,
+            session_id: None,
+        })
+    }
+
+    /// Connect to the browser serving DevTools on `address`, e.g. `127.0.0.1:9222` for
+    /// `--remote-debugging-port=9222`
+    pub async fn from_debugger_address(address: &str) -> Result<Cdp> {
+        let url = format!("http://{address}/json/version");
+        let client = session::client()?;
+        let lookup = url.clone();
+        let text = rt::io(async move {
+            let response = client
+                .get(&lookup)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(|source| http::network(&lookup, source))?;
+            response
+                .text()
+                .await
+                .map_err(|source| http::network(&lookup, source))
+        })
+        .await?;
+        let version: Value = serde_json::from_str(&text).unwrap_or_default();
+        let Some(websocket) = version["webSocketDebuggerUrl"].as_str() else {
+            return Err(DriverError::Config(format!(
+                "{url} lists no webSocketDebuggerUrl"
+            )));
+        };
+        Cdp::connect(websocket).await
+    }
+
+    /// Talk to the target (tab, worker, ...) with id `target_id` over this connection
+    pub async fn attach(&self, target_id: &str) -> Result<Cdp> {
+        let params = json!({ "targetId": target_id, "flatten": true });
+        let attached = self.send("Target.attachToTarget", params).await?;
+        let session_id = attached["sessionId"]
+            .as_str()
+            .ok_or_else(|| DriverError::DevTools {
+                method: "Target.attachToTarget".into(),
+                message: format!("no sessionId in {attached}"),
+            })?;
+        Ok(Cdp {
+            connection: Arc::clone(&self.connection),
+            session_id: Some(session_id.to_string()),
+        })
+    }
+
+    /// DevTools session of the attached target; `None` for the browser itself
+    pub fn session_id(&self) -> Option<&str> {
+        self.session_id.as_deref()
+    }
+
+    /// Run `method` with raw JSON `params`, returning its raw `result`, e.g.
+    /// `send("Emulation.setTimezoneOverride", json!({"timezoneId": "Asia/Seoul"}))`
+    pub async fn send(&self, method: &str, params: impl Serialize) -> Result<Value> {
+        let params = match serde_json::to_value(params) {
+            Ok(Value::Null) => json!({}),
+            Ok(params) => params,
+            Err(e) => {
+                return Err(DriverError::Config(format!(
+                    "serializing {method} parameters: {e}"
+                )))
+            }
+        };
+        self.connection
+            .call(method, params, self.session_id.as_deref())
+            .await
+    }
+
+    /// Run a typed [`CdpCommand`]
+    pub async fn execute<C: CdpCommand>(&self, command: C) -> Result<C::Response> {
+        let result = self.send(C::METHOD, &command).await?;
+        serde_json::from_value(result).map_err(|e| DriverError::DevTools {
+            method: C::METHOD.to_string(),
+            message: format!("unexpected result: {e}"),
+        })
+    }
+
+    /// Receive `method` events of this target, e.g. `Page.loadEventFired`.
+    ///
+    /// * Most domains only send events once enabled, e.g. with `Page.enable`; subscribe
+    ///   first so none are missed.
+    pub fn subscribe(&self, method: &str) -> CdpEvents {
+        self.subscription(vec![method.to_string()])
+    }
+
+    /// Receive every event of this target
+    pub fn subscribe_all(&self) -> CdpEvents {
+        self.subscription(Vec::new())
+    }
+
+    fn subscription(&self, methods: Vec<String>) -> CdpEvents {
+        let receiver = self.connection.subscribe(methods, self.session_id.clone());
+        CdpEvents {
+            receiver,
+            _connection: Arc::clone(&self.connection),
+        }
+    }
+}
+
+impl fmt::Debug for Cdp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cdp")
+            .field("url", &self.connection.url())
+            .field("session_id", &self.session_id)
+            .finish()
+    }
+}
+
+impl Session {
+    /// DevTools connection attached to the current window of this session.
+    ///
+    /// * Finds the browser through the session's `se:cdp` (Selenium Grid) or
+    ///   `goog:chromeOptions.debuggerAddress` (chromedriver) capability.
+    pub async fn cdp(&self) -> Result<Cdp> {
+        let capabilities = self.capabilities();
+        let browser = if let Some(url) = capabilities["se:cdp"].as_str() {
+            Cdp::connect(url).await?
+        } else if let Some(address) = capabilities["goog:chromeOptions"]["debuggerAddress"].as_str()
+        {
+            Cdp::from_debugger_address(address).await?
+        } else {
+            return Err(DriverError::Config(
+                "the session advertises no DevTools endpoint (se:cdp or debuggerAddress)".into(),
+            ));
+        };
+        // Chromedriver's window handles are DevTools target ids, formerly prefixed
+        let window = self.window_handle().await?;
+        let target = window.strip_prefix("CDwindow-").unwrap_or(&window);
+        browser.attach(target).await
+    }
+}
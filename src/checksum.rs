@@ -0,0 +1,121 @@
+use tracing::debug;
+
+use crate::{
+    error::{DriverError, Result},
+    http, DriverManager,
+};
+
+/// Where the expected SHA-256 of a downloaded archive comes from
+#[derive(Debug, Clone)]
+pub(crate) enum Checksum {
+    /// A fixed hex digest
+    Sha256(String),
+    /// A `sha256sum`-style manifest (`<digest>  <file name>` per line)
+    ManifestUrl(String),
+}
+
+/// Digest listed for `file_name` in a `sha256sum`-style manifest
+fn find_in_manifest(manifest: &str, file_name: &str) -> Option<String> {
+    manifest.lines().find_map(|line| {
+        let (digest, name) = line.trim().split_once(char::is_whitespace)?;
+        let name = name.trim_start().trim_start_matches('*');
+        (name == file_name || name.rsplit('/').next() == Some(file_name))
+            .then(|| digest.to_ascii_lowercase())
+    })
+}
+
+/// Resolve the expected digest of the archive at `url`, if verification is enabled
+pub(crate) async fn expected(manager: &DriverManager, url: &str) -> Result<Option<String>> {
+    match manager.checksum() {
+        None => Ok(None),
+        Some(Checksum::Sha256(digest)) => Ok(Some(digest.trim().to_ascii_lowercase())),
+        Some(Checksum::ManifestUrl(manifest_url)) => {
+            let file_name = url.rsplit('/').next().unwrap_or(url);
+            let manifest = manager
+                .retrying(|| {
+                    http::get_text(manager.client(), manifest_url, manager.metadata_timeout())
+                })
+                .await?;
+            find_in_manifest(&manifest, file_name)
+                .map(Some)
+                .ok_or_else(|| {
+                    DriverError::Config(format!("no checksum for {file_name} in {manifest_url}"))
+                })
+        }
+    }
+}
+
+/// Fail with [`DriverError::ChecksumMismatch`] unless `actual` equals `expected`
+pub(crate) fn verify(url: &str, actual: &str, expected: &str) -> Result<()> {
+    if actual != expected {
+        return Err(DriverError::ChecksumMismatch {
+            url: url.to_string(),
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        });
+    }
+    debug!("🔒 SHA-256 verified: {actual}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "\
+3B1F00D5AF3DBAB1E8B3A7C2D9E0F4A5B6C7D8E9F0A1B2C3D4E5F6A7B8C9D0E1  chromedriver-linux64.zip
+a1b2c3d4e5f6a7b8c9d0e1f2a3b4c5d6e7f8a9b0c1d2e3f4a5b6c7d8e9f0a1b2 *chromedriver-win64.zip
+ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff  mac-arm64/chromedriver-mac-arm64.zip
+";
+
+    #[test]
+    fn finds_text_mode_entries() {
+        assert_eq!(
+            find_in_manifest(MANIFEST, "chromedriver-linux64.zip").as_deref(),
+            Some("3b1f00d5af3dbab1e8b3a7c2d9e0f4a5b6c7d8e9f0a1b2c3d4e5f6a7b8c9d0e1")
+        );
+    }
+
+    #[test]
+    fn finds_binary_mode_entries() {
+        assert_eq!(
+            find_in_manifest(MANIFEST, "chromedriver-win64.zip").as_deref(),
+            Some("a1b2c3d4e5f6a7b8c9d0e1f2a3b4c5d6e7f8a9b0c1d2e3f4a5b6c7d8e9f0a1b2")
+        );
+    }
+
+    #[test]
+    fn matches_entries_by_file_name_when_listed_with_a_directory() {
+        assert_eq!(
+            find_in_manifest(MANIFEST, "chromedriver-mac-arm64.zip").as_deref(),
+            Some("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff")
+        );
+    }
+
+    #[test]
+    fn misses_absent_entries() {
+        assert_eq!(find_in_manifest(MANIFEST, "chromedriver-mac-x64.zip"), None);
+        assert_eq!(find_in_manifest(MANIFEST, "linux64.zip"), None);
+        assert_eq!(find_in_manifest("", "chromedriver-linux64.zip"), None);
+        assert_eq!(
+            find_in_manifest("not a manifest\n", "chromedriver-linux64.zip"),
+            None
+        );
+    }
+
+    #[test]
+    fn verify_reports_both_digests() {
+        assert!(verify("https://example.com/a.zip", "abc", "abc").is_ok());
+        match verify("https://example.com/a.zip", "abc", "def") {
+            Err(DriverError::ChecksumMismatch {
+                url,
+                expected,
+                actual,
+            }) => {
+                assert_eq!(url, "https://example.com/a.zip");
+                assert_eq!((expected.as_str(), actual.as_str()), ("def", "abc"));
+            }
+            other => panic!("expected ChecksumMismatch, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use serde_json::Value;
+use std::{
+    fmt,
+    sync::{Arc, Mutex, MutexGuard},
+};
+use tokio::task::JoinHandle;
+
+use crate::{error::Result, rt, Cdp, CdpEvent, CdpEvents, Session};
+
+/// Severity of a [`ConsoleMessage`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warning,
+    Error,
+}
+
+impl LogLevel {
+    fn as_str(self) -> &'static str {
+        match self {
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            LogLevel::Warning => "warning",
+            LogLevel::Error => "error",
+        }
+    }
+}
+
+/// Where a [`ConsoleMessage`] came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConsoleSource {
+    /// A `console.log/warn/error/...` call of the page
+    Console,
+    /// A JavaScript exception nothing caught
+    Exception,
+    /// The browser itself, e.g. a failed resource load or a CSP violation
+    Browser,
+}
+
+/// A line of the page's console, see [`Cdp::console`] and [`ConsoleLog`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsoleMessage {
+    /// `console.warn` is a warning, uncaught exceptions are errors
+    pub level: LogLevel,
+    /// Page script, exception or browser
+    pub source: ConsoleSource,
+    /// The logged values joined by spaces, or the exception with its stack
+    pub text: String,
+    /// Script or resource that logged it
+    pub url: Option<String>,
+    /// 1-based line in `url`
+    pub line: Option<u32>,
+    /// Milliseconds since the Unix epoch
+    pub timestamp: f64,
+}
+
+impl fmt::Display for ConsoleMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.level.as_str(), self.text)?;
+        match (&self.url, self.line) {
+            (Some(url), Some(line)) => write!(f, " ({url}:{line})"),
+            (Some(url), None) => write!(f, " ({url})"),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Console messages reported by [`Cdp::console`] or [`crate::Bidi::console`]; ends when
+/// the connection closes
+#[derive(Debug)]
+pub struct ConsoleEvents {
+    events: CdpEvents,
+}
+
+/// Console messages collected in the background since [`Cdp::capture_console`], e.g. to
+/// print the page's own errors when a test fails.
+///
+/// * Collection stops when the log is dropped.
+#[derive(Debug)]
+pub struct ConsoleLog {
+    messages: Arc<Mutex<Vec<ConsoleMessage>>>,
+    collector: JoinHandle<()>,
+}
+
+impl Cdp {
+    /// Report console calls, uncaught exceptions and browser warnings of the page
+    pub async fn console(&self) -> Result<ConsoleEvents> {
+        let events = self.subscribe_all();
+        self.send("Runtime.enable", ()).await?;
+        self.send("Log.enable", ()).await?;
+        Ok(ConsoleEvents::new(events))
+    }
+
+    /// Collect the page's console messages into a [`ConsoleLog`]
+    pub async fn capture_console(&self) -> Result<ConsoleLog> {
+        Ok(ConsoleLog::collect(self.console().await?))
+    }
+}
+
+impl Session {
+    /// Collect the console messages of the current window, see [`Cdp::capture_console`]
+    pub async fn capture_console(&self) -> Result<ConsoleLog> {
+        self.cdp().await?.capture_console().await
+    }
+}
+
+impl ConsoleEvents {
+    pub(crate) fn new(events: CdpEvents) -> ConsoleEvents {
+        ConsoleEvents { events }
+    }
+
+    /// Wait for the next console message, or `None` once the browser is gone
+    pub async fn next(&mut self) -> Option<ConsoleMessage> {
+        loop {
+            let event = self.events.next().await?;
+            if let Some(message) = parse(&event) {
+                return Some(message);
+            }
+        }
+    }
+}
+
+impl ConsoleLog {
+    /// Gather `events` in the background
+    pub(crate) fn collect(mut events: ConsoleEvents) -> ConsoleLog {
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        let collected = Arc::clone(&messages);
+        let collector = rt::spawn(async move {
+            while let Some(message) = events.next().await {
+                lock(&collected).push(message);
+            }
+        });
+        ConsoleLog {
+            messages,
+            collector,
+        }
+    }
+
+    /// Everything logged so far, oldest first
+    pub fn messages(&self) -> Vec<ConsoleMessage> {
+        lock(&self.messages).clone()
+    }
+
+    /// Errors and uncaught exceptions logged so far
+    pub fn errors(&self) -> Vec<ConsoleMessage> {
+        let messages = lock(&self.messages);
+        messages
+            .iter()
+            .filter(|message| message.level == LogLevel::Error)
+            .cloned()
+            .collect()
+    }
+
+    /// Everything logged so far, emptying the log
+    pub fn take(&self) -> Vec<ConsoleMessage> {
+        std::mem::take(&mut *lock(&self.messages))
+    }
+}
+
+/// Lock `messages`, even if a thread panicked while holding it
+fn lock(messages: &Mutex<Vec<ConsoleMessage>>) -> MutexGuard<'_, Vec<ConsoleMessage>> {
+    messages
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl Drop for ConsoleLog {
+    fn drop(&mut self) {
+        self.collector.abort();
+    }
+}
+
+fn parse(event: &CdpEvent) -> Option<ConsoleMessage> {
+    let params = &event.params;
+    match event.method.as_str() {
+        "Runtime.consoleAPICalled" => {
+            let level = match params["type"].as_str().unwrap_or_default() {
+                "error" | "assert" => LogLevel::Error,
+                "warning" => LogLevel::Warning,
+                "debug" | "trace" => LogLevel::Debug,
+                _ => LogLevel::Info,
+            };
+            let args = params["args"]
+                .as_array()
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let text: Vec<String> = args.iter().map(describe).collect();
+            let frame = &params["stackTrace"]["callFrames"][0];
+            Some(ConsoleMessage {
+                level,
+                source: ConsoleSource::Console,
+                text: text.join(" "),
+                url: non_empty(&frame["url"]),
+                line: line(&frame["lineNumber"]),
+                timestamp: params["timestamp"].as_f64().unwrap_or_default(),
+            })
+        }
+        "Runtime.exceptionThrown" => {
+            let details = &params["exceptionDetails"];
+            let text = details["exception"]["description"]
+                .as_str()
+                .or(details["text"].as_str())
+                .unwrap_or_default();
+            Some(ConsoleMessage {
+                level: LogLevel::Error,
+                source: ConsoleSource::Exception,
+                text: text.to_string(),
+                url: non_empty(&details["url"]),
+                line: line(&details["lineNumber"]),
+                timestamp: params["timestamp"].as_f64().unwrap_or_default(),
+            })
+        }
+        "Log.entryAdded" => {
+            let entry = &params["entry"];
+            let level = match entry["level"].as_str().unwrap_or_default() {
+                "error" => LogLevel::Error,
+                "warning" => LogLevel::Warning,
+                "verbose" => LogLevel::Debug,
+                _ => LogLevel::Info,
+            };
+            Some(ConsoleMessage {
+                level,
+                source: ConsoleSource::Browser,
+                text: entry["text"].as_str().unwrap_or_default().to_string(),
+                url: non_empty(&entry["url"]),
+                line: line(&entry["lineNumber"]),
+                timestamp: entry["timestamp"].as_f64().unwrap_or_default(),
+            })
+        }
+        // BiDi's `log` module
+        "log.entryAdded" => {
+            let level = match params["level"].as_str().unwrap_or_default() {
+                "error" => LogLevel::Error,
+                "warn" => LogLevel::Warning,
+                "debug" => LogLevel::Debug,
+                _ => LogLevel::Info,
+            };
+            let source = match params["type"].as_str() {
+                Some("javascript") => ConsoleSource::Exception,
+                _ => ConsoleSource::Console,
+            };
+            let frame = &params["stackTrace"]["callFrames"][0];
+            Some(ConsoleMessage {
+                level,
+                source,
+                text: params["text"].as_str().unwrap_or_default().to_string(),
+                url: non_empty(&frame["url"]),
+                line: line(&frame["lineNumber"]),
+                timestamp: params["timestamp"].as_f64().unwrap_or_default(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// A logged value as the DevTools console shows it
+fn describe(arg: &Value) -> String {
+    match &arg["value"] {
+        Value::String(text) => text.clone(),
+        Value::Null => arg["description"]
+            .as_str()
+            .or(arg["unserializableValue"].as_str())
+            .or(arg["subtype"].as_str())
+            .or(arg["type"].as_str())
+            .unwrap_or_default()
+            .to_string(),
+        value => value.to_string(),
+    }
+}
+
+fn non_empty(url: &Value) -> Option<String> {
+    url.as_str()
+        .filter(|url| !url.is_empty())
+        .map(str::to_string)
+}
+
+/// DevTools counts lines from 0
+fn line(number: &Value) -> Option<u32> {
+    number.as_u64().map(|line| line as u32 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn survives_a_panic_while_the_log_is_locked() {
+        let message = ConsoleMessage {
+            level: LogLevel::Error,
+            source: ConsoleSource::Exception,
+            text: "Uncaught TypeError".into(),
+            url: None,
+            line: None,
+            timestamp: 0.0,
+        };
+        let log = ConsoleLog {
+            messages: Arc::new(Mutex::new(vec![message.clone()])),
+            collector: rt::spawn(async {}),
+        };
+        let messages = Arc::clone(&log.messages);
+        let panicked = std::thread::spawn(move || {
+            let _guard = messages.lock().unwrap();
+            panic!("callback failed");
+        })
+        .join();
+        assert!(panicked.is_err() && log.messages.is_poisoned());
+
+        let expected = vec![message];
+        assert_eq!(log.messages(), expected);
+        assert_eq!(log.errors(), expected);
+        assert_eq!(log.take(), expected);
+        assert!(log.messages().is_empty());
+    }
+}
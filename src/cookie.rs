@@ -0,0 +1,95 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    error::{DriverError, Result},
+    http, Session,
+};
+
+/// A browser cookie, as exchanged with [`Session::get_cookies`] and [`Session::add_cookie`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Cookie {
+    /// Cookie name
+    pub name: String,
+    /// Cookie value
+    pub value: String,
+    /// Defaults to `/` when adding
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Defaults to the current page's domain when adding
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    /// Only sent over HTTPS
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secure: Option<bool>,
+    /// Hidden from page scripts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_only: Option<bool>,
+    /// Expiry in seconds since the Unix epoch; `None` for a session cookie
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiry: Option<u64>,
+    /// Whether the cookie is sent with cross-site requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub same_site: Option<SameSite>,
+}
+
+/// `SameSite` policy of a [`Cookie`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SameSite {
+    Strict,
+    Lax,
+    None,
+}
+
+impl Cookie {
+    /// A session cookie for the current page's domain
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Cookie {
+        Cookie {
+            name: name.into(),
+            value: value.into(),
+            path: None,
+            domain: None,
+            secure: None,
+            http_only: None,
+            expiry: None,
+            same_site: None,
+        }
+    }
+}
+
+impl Session {
+    /// Cookies visible to the current page
+    pub async fn get_cookies(&self) -> Result<Vec<Cookie>> {
+        let cookies = self.command(Method::GET, "/cookie", None).await?;
+        serde_json::from_value(cookies).map_err(|e| DriverError::WebDriver {
+            command: "get all cookies".into(),
+            error: "invalid response".into(),
+            message: e.to_string(),
+        })
+    }
+
+    /// Set `cookie` for the current page.
+    ///
+    /// * The page must already be on the cookie's domain; navigate there first, e.g. to
+    ///   restore a saved login.
+    pub async fn add_cookie(&self, cookie: &Cookie) -> Result<()> {
+        self.command(Method::POST, "/cookie", Some(json!({ "cookie": cookie })))
+            .await
+            .map(drop)
+    }
+
+    /// Delete the cookie called `name`; deleting a missing cookie succeeds
+    pub async fn delete_cookie(&self, name: &str) -> Result<()> {
+        let path = format!("/cookie/{}", http::path_segment(name));
+        self.command(Method::DELETE, &path, None).await.map(drop)
+    }
+
+    /// Delete every cookie visible to the current page
+    pub async fn delete_all_cookies(&self) -> Result<()> {
+        self.command(Method::DELETE, "/cookie", None)
+            .await
+            .map(drop)
+    }
+}
@@ -0,0 +1,314 @@
+use reqwest::Method;
+use serde_json::{json, Value};
+
+use crate::{
+    error::{DriverError, Result},
+    http, Session,
+};
+
+/// Key under which WebDriver serializes element references
+pub(crate) const ELEMENT_KEY: &str = "element-6066-11e4-a52e-4f735466cecf";
+
+/// How [`Session::find_element`] locates elements
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum By {
+    /// CSS selector, e.g. `form > button.primary`
+    Css(String),
+    /// XPath expression, e.g. `//button[text()="Save"]`
+    XPath(String),
+    /// `<a>` elements whose visible text is exactly this
+    LinkText(String),
+    /// `<a>` elements whose visible text contains this
+    PartialLinkText(String),
+    /// Elements with this tag name, e.g. `input`
+    TagName(String),
+    /// The element with this `id` attribute
+    Id(String),
+    /// Elements with this `name` attribute
+    Name(String),
+    /// Elements with this CSS class
+    ClassName(String),
+}
+
+impl By {
+    /// [`By::Css`]
+    pub fn css(selector: impl Into<String>) -> By {
+        By::Css(selector.into())
+    }
+
+    /// [`By::XPath`]
+    pub fn xpath(expression: impl Into<String>) -> By {
+        By::XPath(expression.into())
+    }
+
+    /// [`By::LinkText`]
+    pub fn link_text(text: impl Into<String>) -> By {
+        By::LinkText(text.into())
+    }
+
+    /// [`By::PartialLinkText`]
+    pub fn partial_link_text(text: impl Into<String>) -> By {
+        By::PartialLinkText(text.into())
+    }
+
+    /// [`By::TagName`]
+    pub fn tag_name(name: impl Into<String>) -> By {
+        By::TagName(name.into())
+    }
+
+    /// [`By::Id`]
+    pub fn id(id: impl Into<String>) -> By {
+        By::Id(id.into())
+    }
+
+    /// [`By::Name`]
+    pub fn name(name: impl Into<String>) -> By {
+        By::Name(name.into())
+    }
+
+    /// [`By::ClassName`]
+    pub fn class_name(class: impl Into<String>) -> By {
+        By::ClassName(class.into())
+    }
+
+    /// The `using`/`value` pair sent to the driver; WebDriver itself only knows CSS,
+    /// XPath, link text and tag names, so ids, names and classes become CSS
+    pub(crate) fn to_json(&self) -> Value {
+        let (using, value) = match self {
+            By::Css(selector) => ("css selector", selector.clone()),
+            By::XPath(expression) => ("xpath", expression.clone()),
+            By::LinkText(text) => ("link text", text.clone()),
+            By::PartialLinkText(text) => ("partial link text", text.clone()),
+            By::TagName(name) => ("tag name", name.clone()),
+            By::Id(id) => ("css selector", format!("[id=\"{}\"]", css_escape(id))),
+            By::Name(name) => ("css selector", format!("[name=\"{}\"]", css_escape(name))),
+            By::ClassName(class) => (
+                "css selector",
+                format!("[class~=\"{}\"]", css_escape(class)),
+            ),
+        };
+        json!({ "using": using, "value": value })
+    }
+}
+
+/// Escape `value` for a double-quoted CSS attribute selector, following `CSS.escape`:
+/// `NUL` becomes U+FFFD, other control characters a `\a `-style code point and
+/// `"`/`\` get a backslash
+fn css_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\0' => escaped.push('\u{FFFD}'),
+            '\u{1}'..='\u{1f}' | '\u{7f}' => escaped.push_str(&format!("\\{:x} ", c as u32)),
+            '"' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// An element of the current page, found through [`Session::find_element`].
+///
+/// * The handle goes stale once the page navigates or the element is removed; commands
+///   then fail with the `stale element reference` error.
+#[derive(Debug, Clone)]
+pub struct Element {
+    session: Session,
+    id: String,
+}
+
+impl Element {
+    /// Reference assigned by the driver
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Session the element belongs to
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    /// Click the element's center, scrolling it into view first
+    pub async fn click(&self) -> Result<()> {
+        self.command(Method::POST, "/click", None).await.map(drop)
+    }
+
+    /// Type `text` into the element, e.g. an `<input>`
+    pub async fn send_keys(&self, text: &str) -> Result<()> {
+        self.command(Method::POST, "/value", Some(json!({ "text": text })))
+            .await
+            .map(drop)
+    }
+
+    /// Empty an editable element
+    pub async fn clear(&self) -> Result<()> {
+        self.command(Method::POST, "/clear", None).await.map(drop)
+    }
+
+    /// Visible text, as rendered
+    pub async fn text(&self) -> Result<String> {
+        let text = self.command(Method::GET, "/text", None).await?;
+        Ok(text.as_str().unwrap_or_default().to_string())
+    }
+
+    /// Value of the attribute `name`, or `None` if it isn't set
+    pub async fn attribute(&self, name: &str) -> Result<Option<String>> {
+        let value = self
+            .command(
+                Method::GET,
+                &format!("/attribute/{}", http::path_segment(name)),
+                None,
+            )
+            .await?;
+        Ok(value.as_str().map(str::to_string))
+    }
+
+    /// Whether the element is visible to the user
+    pub async fn is_displayed(&self) -> Result<bool> {
+        let displayed = self.command(Method::GET, "/displayed", None).await?;
+        Ok(displayed.as_bool().unwrap_or(false))
+    }
+
+    /// Whether the element accepts input, i.e. isn't `disabled`
+    pub async fn is_enabled(&self) -> Result<bool> {
+        let enabled = self.command(Method::GET, "/enabled", None).await?;
+        Ok(enabled.as_bool().unwrap_or(false))
+    }
+
+    /// First descendant matching `by`
+    pub async fn find_element(&self, by: By) -> Result<Element> {
+        let found = self
+            .command(Method::POST, "/element", Some(by.to_json()))
+            .await?;
+        Element::from_json(&self.session, &found)
+    }
+
+    /// All descendants matching `by`, in document order
+    pub async fn find_elements(&self, by: By) -> Result<Vec<Element>> {
+        let found = self
+            .command(Method::POST, "/elements", Some(by.to_json()))
+            .await?;
+        Element::from_json_list(&self.session, &found)
+    }
+
+    /// Send a command for this element; `path` is relative to `/element/{id}`
+    pub(crate) async fn command(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Value>,
+    ) -> Result<Value> {
+        let path = format!("/element/{}{path}", self.id);
+        self.session.command(method, &path, body).await
+    }
+
+    /// Element reference returned by the driver, e.g. `{"element-6066-...": "f.1.2"}`
+    pub(crate) fn from_json(session: &Session, value: &Value) -> Result<Element> {
+        let id = value[ELEMENT_KEY]
+            .as_str()
+            .ok_or_else(|| DriverError::WebDriver {
+                command: "find element".into(),
+                error: "invalid response".into(),
+                message: format!("not an element reference: {value}"),
+            })?;
+        Ok(Element {
+            session: session.clone(),
+            id: id.to_string(),
+        })
+    }
+
+    pub(crate) fn from_json_list(session: &Session, value: &Value) -> Result<Vec<Element>> {
+        value
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|element| Element::from_json(session, element))
+            .collect()
+    }
+}
+
+impl Session {
+    /// First element of the page matching `by`.
+    ///
+    /// * Fails with the `no such element` [`DriverError::WebDriver`] error if there is none.
+    pub async fn find_element(&self, by: By) -> Result<Element> {
+        let found = self
+            .command(Method::POST, "/element", Some(by.to_json()))
+            .await?;
+        Element::from_json(self, &found)
+    }
+
+    /// All elements of the page matching `by`, in document order; empty if there are none
+    pub async fn find_elements(&self, by: By) -> Result<Vec<Element>> {
+        let found = self
+            .command(Method::POST, "/elements", Some(by.to_json()))
+            .await?;
+        Element::from_json_list(self, &found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passes_native_strategies_through() {
+        assert_eq!(
+            By::css("form > button.primary").to_json(),
+            json!({ "using": "css selector", "value": "form > button.primary" })
+        );
+        assert_eq!(
+            By::xpath("//button[text()=\"Save\"]").to_json(),
+            json!({ "using": "xpath", "value": "//button[text()=\"Save\"]" })
+        );
+        assert_eq!(
+            By::link_text("Docs").to_json(),
+            json!({ "using": "link text", "value": "Docs" })
+        );
+        assert_eq!(
+            By::partial_link_text("Do").to_json(),
+            json!({ "using": "partial link text", "value": "Do" })
+        );
+        assert_eq!(
+            By::tag_name("input").to_json(),
+            json!({ "using": "tag name", "value": "input" })
+        );
+    }
+
+    #[test]
+    fn maps_attribute_strategies_to_css() {
+        assert_eq!(
+            By::id("main").to_json(),
+            json!({ "using": "css selector", "value": "[id=\"main\"]" })
+        );
+        assert_eq!(
+            By::name("q").to_json(),
+            json!({ "using": "css selector", "value": "[name=\"q\"]" })
+        );
+        assert_eq!(
+            By::class_name("primary").to_json(),
+            json!({ "using": "css selector", "value": "[class~=\"primary\"]" })
+        );
+    }
+
+    #[test]
+    fn escapes_quotes_and_backslashes() {
+        assert_eq!(
+            By::id(r#"a"b\c"#).to_json(),
+            json!({ "using": "css selector", "value": r#"[id="a\"b\\c"]"# })
+        );
+        assert_eq!(
+            By::name("a\nb\tc\u{7f}").to_json()["value"],
+            json!(r#"[name="a\a b\9 c\7f "]"#)
+        );
+        assert_eq!(
+            By::class_name("a\0b").to_json()["value"],
+            json!("[class~=\"a\u{FFFD}b\"]")
+        );
+        // Ids that aren't valid CSS identifiers still work inside the attribute selector
+        assert_eq!(By::id("1:2.3").to_json()["value"], json!("[id=\"1:2.3\"]"));
+    }
+}
@@ -0,0 +1,154 @@
+use std::{fmt, io, path::PathBuf, process::ExitStatus, time::Duration};
+
+/// Errors returned by this crate
+#[derive(Debug)]
+pub enum DriverError {
+    /// An HTTP request failed or returned an error status
+    Network { url: String, source: reqwest::Error },
+    /// The installer configuration is invalid (e.g. a malformed proxy URL)
+    Config(String),
+    /// A downloaded archive doesn't match its expected SHA-256
+    ChecksumMismatch {
+        url: String,
+        expected: String,
+        actual: String,
+    },
+    /// An archive entry would be written outside the extraction directory
+    UnsafeArchiveEntry { path: PathBuf, entry: String },
+    /// An installed driver doesn't run or reports a different version
+    VersionMismatch {
+        path: PathBuf,
+        expected: String,
+        found: Option<String>,
+    },
+    /// A driver doesn't support the browser it is paired with
+    IncompatibleVersions { driver: String, chrome: String },
+    /// A spawned driver exited before it got ready
+    DriverExited { path: PathBuf, status: ExitStatus },
+    /// A WebDriver command was rejected; `error` is the W3C code, e.g. `no such element`
+    WebDriver {
+        command: String,
+        error: String,
+        message: String,
+    },
+    /// A DevTools protocol command failed or its reply couldn't be read
+    DevTools { method: String, message: String },
+    /// An operation exceeded its configured deadline
+    Timeout { operation: String, after: Duration },
+    /// The install was aborted through its cancellation token
+    Cancelled,
+    /// [`crate::rollback`] found no version older than the current driver
+    NoPreviousVersion { out_dir: PathBuf },
+    /// Offline mode is on and nothing suitable is installed
+    Offline(String),
+    /// A version could not be resolved from the metadata endpoints
+    VersionLookup(String),
+    /// A version string was malformed
+    InvalidVersion(String),
+    /// No build is published for this OS/architecture
+    UnsupportedPlatform(String),
+    /// No local Chrome/Chromium installation was found
+    ChromeNotFound,
+    /// A downloaded archive could not be extracted
+    Extraction {
+        path: PathBuf,
+        source: zip::result::ZipError,
+    },
+    /// A filesystem or process operation failed
+    Io { context: String, source: io::Error },
+}
+
+/// Result type used across this crate
+pub type Result<T, E = DriverError> = std::result::Result<T, E>;
+
+impl DriverError {
+    pub(crate) fn io(context: impl Into<String>, source: io::Error) -> Self {
+        DriverError::Io {
+            context: context.into(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for DriverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DriverError::Network { url, source } => write!(f, "request to {url} failed: {source}"),
+            DriverError::Config(msg) => write!(f, "invalid configuration: {msg}"),
+            DriverError::ChecksumMismatch {
+                url,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "checksum mismatch for {url}: expected sha256 {expected}, got {actual}"
+            ),
+            DriverError::UnsafeArchiveEntry { path, entry } => write!(
+                f,
+                "refusing to extract {entry:?}: it escapes {}",
+                path.display()
+            ),
+            DriverError::VersionMismatch {
+                path,
+                expected,
+                found: Some(found),
+            } => write!(
+                f,
+                "{} reports version {found}, expected {expected}",
+                path.display()
+            ),
+            DriverError::VersionMismatch {
+                path,
+                expected,
+                found: None,
+            } => write!(
+                f,
+                "{} did not report a version, expected {expected}",
+                path.display()
+            ),
+            DriverError::IncompatibleVersions { driver, chrome } => write!(
+                f,
+                "ChromeDriver {driver} does not support Chrome {chrome}; install ChromeDriver {}",
+                chrome.split('.').next().unwrap_or(chrome)
+            ),
+            DriverError::DriverExited { path, status } => {
+                write!(f, "{} exited during startup ({status})", path.display())
+            }
+            DriverError::WebDriver {
+                command,
+                error,
+                message,
+            } => write!(f, "{command} failed: {error}: {message}"),
+            DriverError::DevTools { method, message } => write!(f, "{method} failed: {message}"),
+            DriverError::Timeout { operation, after } => {
+                write!(f, "{operation} timed out after {after:?}")
+            }
+            DriverError::Cancelled => write!(f, "install was cancelled"),
+            DriverError::NoPreviousVersion { out_dir } => write!(
+                f,
+                "no driver older than the current one is installed in {}",
+                out_dir.display()
+            ),
+            DriverError::Offline(msg) => write!(f, "offline: {msg}"),
+            DriverError::VersionLookup(msg) => write!(f, "version lookup failed: {msg}"),
+            DriverError::InvalidVersion(version) => write!(f, "invalid version: {version}"),
+            DriverError::UnsupportedPlatform(msg) => write!(f, "unsupported platform: {msg}"),
+            DriverError::ChromeNotFound => write!(f, "Chrome installation not found"),
+            DriverError::Extraction { path, source } => {
+                write!(f, "failed to extract into {}: {source}", path.display())
+            }
+            DriverError::Io { context, source } => write!(f, "{context}: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for DriverError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DriverError::Network { source, .. } => Some(source),
+            DriverError::Extraction { source, .. } => Some(source),
+            DriverError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use serde_json::{json, Map, Value};
+use std::{env, fmt::Write};
+
+use crate::{BrowserInfo, ChromeOptions, DriverInfo};
+
+/// Sessions a node offers unless told otherwise, Selenium's default for Chrome
+const DEFAULT_MAX_SESSIONS: u32 = 1;
+
+/// Configuration of a Selenium Grid node serving Chrome through an installed driver.
+///
+/// * [`node_toml`](Self::node_toml): the node launches the driver itself.
+/// * [`relay_toml`](Self::relay_toml): the node relays to a driver already running, e.g. one
+///   started with [`crate::DriverProcessBuilder::grid_relay`].
+/// * [`stereotype`](Self::stereotype): the capabilities the node advertises to the hub.
+#[derive(Debug, Clone)]
+pub struct GridNode {
+    driver_path: String,
+    driver_version: String,
+    browser: Option<BrowserInfo>,
+    max_sessions: u32,
+    options: Option<Map<String, Value>>,
+}
+
+impl GridNode {
+    /// A node for `driver`, using the system Chrome
+    pub fn new(driver: &DriverInfo) -> GridNode {
+        GridNode {
+            driver_path: driver.driver_path.clone(),
+            driver_version: driver.version.clone(),
+            browser: None,
+            max_sessions: DEFAULT_MAX_SESSIONS,
+            options: None,
+        }
+    }
+
+    /// Serve a browser installed by [`crate::ensure_chrome_browser`] instead of the system
+    /// Chrome
+    pub fn browser(mut self, browser: &BrowserInfo) -> Self {
+        self.browser = Some(browser.clone());
+        self
+    }
+
+    /// Concurrent sessions the node accepts (default 1)
+    pub fn max_sessions(mut self, sessions: u32) -> Self {
+        self.max_sessions = sessions.max(1);
+        self
+    }
+
+    /// Advertise `goog:chromeOptions` built from `options`, e.g. to match sessions asking
+    /// for a headless browser.
+    ///
+    /// * Only the `goog:chromeOptions` part is used; invalid options are ignored.
+    pub fn options(mut self, options: &ChromeOptions) -> Self {
+        self.options =
+            options.to_capabilities().ok().and_then(|mut capabilities| {
+                match capabilities["goog:chromeOptions"].take() {
+                    Value::Object(chrome) => Some(chrome),
+                    _ => None,
+                }
+            });
+        self
+    }
+
+    /// Capabilities the node registers with the hub, e.g.
+    /// `{"browserName": "chrome", "browserVersion": "130", "platformName": "linux"}`
+    pub fn stereotype(&self) -> Value {
+        let version = match &self.browser {
+            Some(browser) => &browser.version,
+            None => &self.driver_version,
+        };
+        let milestone = version.split('.').next().unwrap_or(version);
+        let mut stereotype = json!({
+            "browserName": "chrome",
+            "browserVersion": milestone,
+            "platformName": platform_name(),
+        });
+        let mut chrome = self.options.clone().unwrap_or_default();
+        if let Some(browser) = &self.browser {
+            chrome.insert("binary".into(), json!(browser.browser_path));
+        }
+        if !chrome.is_empty() {
+            stereotype["goog:chromeOptions"] = Value::Object(chrome);
+        }
+        stereotype
+    }
+
+    /// Node TOML (`java -jar selenium-server.jar node --config node.toml`) in which the
+    /// node starts the installed driver for each session
+    pub fn node_toml(&self) -> String {
+        let mut toml = String::new();
+        let _ = writeln!(toml, "[node]");
+        let _ = writeln!(toml, "detect-drivers = false");
+        let _ = writeln!(toml, "max-sessions = {}", self.max_sessions);
+        let _ = writeln!(toml);
+        let _ = writeln!(toml, "[[node.driver-configuration]]");
+        let _ = writeln!(toml, "display-name = {}", quote(&self.display_name()));
+        let _ = writeln!(toml, "webdriver-executable = {}", quote(&self.driver_path));
+        let _ = writeln!(toml, "max-sessions = {}", self.max_sessions);
+        let _ = writeln!(
+            toml,
+            "stereotype = {}",
+            quote(&self.stereotype().to_string())
+        );
+        toml
+    }
+
+    /// Node TOML relaying sessions to the driver already listening at `driver_url`, e.g.
+    /// [`crate::DriverProcess::base_url`]
+    pub fn relay_toml(&self, driver_url: &str) -> String {
+        let mut toml = String::new();
+        let _ = writeln!(toml, "[node]");
+        let _ = writeln!(toml, "detect-drivers = false");
+        let _ = writeln!(toml);
+        let _ = writeln!(toml, "[relay]");
+        let _ = writeln!(toml, "url = {}", quote(driver_url.trim_end_matches('/')));
+        let _ = writeln!(toml, "status-endpoint = \"/status\"");
+        let _ = writeln!(
+            toml,
+            "configs = [{}, {}]",
+            quote(&self.max_sessions.to_string()),
+            quote(&self.stereotype().to_string())
+        );
+        toml
+    }
+
+    fn display_name(&self) -> String {
+        match &self.browser {
+            Some(browser) => format!("Chrome for Testing {}", browser.version),
+            None => format!("Chrome (ChromeDriver {})", self.driver_version),
+        }
+    }
+}
+
+/// `platformName` as Selenium spells it
+fn platform_name() -> &'static str {
+    match env::consts::OS {
+        "macos" => "mac",
+        "windows" => "windows",
+        _ => "linux",
+    }
+}
+
+/// A TOML basic string; JSON escapes are valid TOML escapes
+fn quote(text: &str) -> String {
+    Value::from(text).to_string()
+}
@@ -0,0 +1,272 @@
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use reqwest::{
+    header::{CONTENT_LENGTH, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, RANGE},
+    StatusCode,
+};
+use sha2::{Digest, Sha256};
+use std::{path::Path, time::Duration};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tracing::info;
+
+use crate::{
+    error::{DriverError, Result},
+    progress::Progress,
+    rt,
+};
+
+/// GET `url`, failing on error statuses
+async fn get(
+    client: &reqwest::Client,
+    url: &str,
+    timeout: Option<Duration>,
+) -> Result<reqwest::Response> {
+    let mut request = client.get(url);
+    if let Some(timeout) = timeout {
+        request = request.timeout(timeout);
+    }
+    request
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|source| network(url, source))
+}
+
+/// GET `url` as text, giving up after `timeout`
+pub(crate) async fn get_text(
+    client: &reqwest::Client,
+    url: &str,
+    timeout: Duration,
+) -> Result<String> {
+    let (client, url) = (client.clone(), url.to_string());
+    rt::io(async move {
+        get(&client, &url, Some(timeout))
+            .await?
+            .text()
+            .await
+            .map_err(|source| network(&url, source))
+    })
+    .await
+}
+
+/// A changed body returned by [`get_if_modified`], with its validators
+pub(crate) struct Modified {
+    pub(crate) body: String,
+    pub(crate) etag: Option<String>,
+    pub(crate) last_modified: Option<String>,
+}
+
+/// Conditional GET of `url`, giving up after `timeout`.
+///
+/// * Sends `If-None-Match`/`If-Modified-Since` for the validators of a cached copy.
+/// * Returns `None` when the server answers `304 Not Modified`.
+pub(crate) async fn get_if_modified(
+    client: &reqwest::Client,
+    url: &str,
+    timeout: Duration,
+    etag: Option<&str>,
+    last_modified: Option<&str>,
+) -> Result<Option<Modified>> {
+    let (client, url) = (client.clone(), url.to_string());
+    let (etag, last_modified) = (etag.map(str::to_string), last_modified.map(str::to_string));
+    rt::io(async move {
+        let mut request = client.get(&url).timeout(timeout);
+        if let Some(etag) = etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+        let response = request
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|source| network(&url, source))?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(None);
+        }
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        let (etag, last_modified) = (header(ETAG), header(LAST_MODIFIED));
+        let body = response
+            .text()
+            .await
+            .map_err(|source| network(&url, source))?;
+        Ok(Some(Modified {
+            body,
+            etag,
+            last_modified,
+        }))
+    })
+    .await
+}
+
+/// Size of the body at `url` according to a `HEAD` request, if the server reports it
+pub(crate) async fn content_length(
+    client: &reqwest::Client,
+    url: &str,
+    timeout: Duration,
+) -> Option<u64> {
+    let (client, url) = (client.clone(), url.to_string());
+    rt::io(async move {
+        let response = client.head(&url).timeout(timeout).send().await.ok()?;
+        response
+            .error_for_status()
+            .ok()?
+            .headers()
+            .get(CONTENT_LENGTH)?
+            .to_str()
+            .ok()?
+            .parse()
+            .ok()
+    })
+    .await
+}
+
+/// Stream `url` into the file at `path`, returning the body's hex SHA-256.
+///
+/// * A partial file left by an interrupted attempt is resumed with a `Range` request.
+/// * Servers without range support get a full download instead.
+pub(crate) async fn download_to_file(
+    client: &reqwest::Client,
+    url: &str,
+    path: &Path,
+    progress: Option<Progress>,
+) -> Result<String> {
+    let (client, url, path) = (client.clone(), url.to_string(), path.to_owned());
+    rt::io(async move {
+        let write_error = |e| DriverError::io(format!("writing {}", path.display()), e);
+
+        // 1️⃣ Hash what an earlier attempt already downloaded
+        let mut hasher = Sha256::new();
+        let resume_from = hash_partial(&path, &mut hasher).await.unwrap_or(0);
+
+        // 2️⃣ Ask for the remainder, or everything if there is nothing to resume
+        let mut request = client.get(&url);
+        if resume_from > 0 {
+            request = request.header(RANGE, format!("bytes={resume_from}-"));
+        }
+        let mut response = request
+            .send()
+            .await
+            .map_err(|source| network(&url, source))?;
+
+        let resumed = resume_from > 0 && response.status() == StatusCode::PARTIAL_CONTENT;
+        let mut file = if resumed {
+            info!("⏯️ Resuming download at {resume_from} bytes");
+            tokio::fs::OpenOptions::new()
+                .append(true)
+                .open(&path)
+                .await
+                .map_err(write_error)?
+        } else {
+            if resume_from > 0 {
+                hasher = Sha256::new();
+                if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+                    response = get(&client, &url, None).await?;
+                }
+            }
+            response = response
+                .error_for_status()
+                .map_err(|source| network(&url, source))?;
+            tokio::fs::File::create(&path).await.map_err(write_error)?
+        };
+
+        // 3️⃣ Stream the body, keeping what arrived if the connection drops
+        let mut downloaded = if resumed { resume_from } else { 0 };
+        let total = response.content_length().map(|length| downloaded + length);
+        let report = |downloaded| {
+            if let Some(progress) = &progress {
+                progress.report(downloaded, total);
+            }
+        };
+        report(downloaded);
+        let streamed = async {
+            while let Some(chunk) = response
+                .chunk()
+                .await
+                .map_err(|source| network(&url, source))?
+            {
+                hasher.update(&chunk);
+                file.write_all(&chunk).await.map_err(write_error)?;
+                downloaded += chunk.len() as u64;
+                report(downloaded);
+            }
+            Ok(())
+        }
+        .await;
+        file.flush().await.map_err(write_error)?;
+        streamed.map(|()| hex(&hasher.finalize()))
+    })
+    .await
+}
+
+/// Feed an existing partial download into `hasher`, returning its length
+async fn hash_partial(path: &Path, hasher: &mut Sha256) -> std::io::Result<u64> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut buffer = vec![0; 64 * 1024];
+    let mut length = 0;
+    loop {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            return Ok(length);
+        }
+        hasher.update(&buffer[..read]);
+        length += read as u64;
+    }
+}
+
+/// Lowercase hex encoding of `bytes`
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Characters escaped in a URL path segment, per the WHATWG URL standard plus `/` and `%`
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}')
+    .add(b'/')
+    .add(b'%');
+
+/// `value` percent-encoded for use as one segment of a URL path, e.g. a cookie name
+pub(crate) fn path_segment(value: &str) -> String {
+    utf8_percent_encode(value, PATH_SEGMENT).to_string()
+}
+
+pub(crate) fn network(url: &str, source: reqwest::Error) -> DriverError {
+    DriverError::Network {
+        url: url.to_string(),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_segment_keeps_plain_names() {
+        assert_eq!(path_segment("session_id"), "session_id");
+        assert_eq!(path_segment("data-test.id"), "data-test.id");
+    }
+
+    #[test]
+    fn path_segment_escapes_reserved_characters() {
+        assert_eq!(path_segment("a/b"), "a%2Fb");
+        assert_eq!(path_segment("a?b#c"), "a%3Fb%23c");
+        assert_eq!(path_segment("100%"), "100%25");
+        assert_eq!(path_segment("my cookie"), "my%20cookie");
+        assert_eq!(path_segment("ü"), "%C3%BC");
+    }
+}
@@ -0,0 +1,78 @@
+use serde_json::{Map, Value};
+use std::ops::Deref;
+use tracing::info;
+
+use crate::{
+    ensure_latest_driver,
+    error::{DriverError, Result},
+    ChromeOptions, DriverProcess,
+};
+
+/// A freshly installed driver running in the background, with the capabilities to start
+/// Chrome through it, see [`launch`].
+///
+/// * Hand [`url`](Self::url) and [`capabilities`](Self::capabilities) to any WebDriver
+///   client, e.g. `thirtyfour::WebDriver::new` or `fantoccini::ClientBuilder`.
+/// * Dereferences to its [`DriverProcess`]; dropping it stops the driver.
+#[derive(Debug)]
+pub struct Launched {
+    process: DriverProcess,
+    capabilities: Map<String, Value>,
+}
+
+/// Install the latest driver into `out_dir`, start it and build the session capabilities
+/// of `options`, i.e. the glue every WebDriver client project repeats.
+///
+/// * The driver listens on a free port, so several launches can run side by side.
+pub async fn launch(out_dir: &str, options: &ChromeOptions) -> Result<Launched> {
+    // Build the capabilities first, so a missing extension fails before any download
+    launch_with_capabilities(out_dir, options.to_capabilities()?).await
+}
+
+/// [`launch`] with raw `capabilities`, e.g. a map already configured for `fantoccini`.
+///
+/// * `capabilities` must be a JSON object.
+pub async fn launch_with_capabilities(out_dir: &str, capabilities: Value) -> Result<Launched> {
+    let Value::Object(capabilities) = capabilities else {
+        return Err(DriverError::Config(format!(
+            "capabilities must be a JSON object, got {capabilities}"
+        )));
+    };
+    let driver = ensure_latest_driver(out_dir).await?;
+    let process = DriverProcess::builder(&driver).port(0).start().await?;
+    info!(
+        "🚀 ChromeDriver {} ready at {}",
+        driver.version,
+        process.base_url()
+    );
+    Ok(Launched {
+        process,
+        capabilities,
+    })
+}
+
+impl Launched {
+    /// Where the driver listens, e.g. `http://127.0.0.1:41327`
+    pub fn url(&self) -> &str {
+        self.process.base_url()
+    }
+
+    /// Capabilities built from the [`ChromeOptions`] passed to [`launch`], or those passed to
+    /// [`launch_with_capabilities`]
+    pub fn capabilities(&self) -> &Map<String, Value> {
+        &self.capabilities
+    }
+
+    /// Stop the driver now instead of on drop
+    pub async fn stop(self) -> Result<()> {
+        self.process.stop().await
+    }
+}
+
+impl Deref for Launched {
+    type Target = DriverProcess;
+
+    fn deref(&self) -> &DriverProcess {
+        &self.process
+    }
+}
@@ -0,0 +1,121 @@
+use std::{env, path::Path};
+use tracing::info;
+
+use crate::{
+    error::{DriverError, Result},
+    http, lock, manifest, DriverInfo, DriverManager, Platform,
+};
+
+/// First milestone published through Chrome for Testing
+pub(crate) const FIRST_CFT_MILESTONE: u32 = 115;
+
+/// Bucket serving ChromeDriver releases before milestone 115
+const LEGACY_BASE: &str = "https://chromedriver.storage.googleapis.com";
+
+/// Whether `version` predates Chrome for Testing
+pub(crate) fn is_legacy(version: &str) -> bool {
+    version
+        .split('.')
+        .next()
+        .and_then(|major| major.parse::<u32>().ok())
+        .is_some_and(|major| major < FIRST_CFT_MILESTONE)
+}
+
+/// Resolve the newest legacy version for a milestone (`"109"`) or build (`"109.0.5414"`)
+pub(crate) async fn fetch_legacy_version(manager: &DriverManager, prefix: &str) -> Result<String> {
+    let url = format!("{LEGACY_BASE}/LATEST_RELEASE_{prefix}");
+    let body = manager
+        .retrying(|| http::get_text(manager.client(), &url, manager.metadata_timeout()))
+        .await
+        .map_err(|_| DriverError::VersionLookup(format!("no legacy ChromeDriver for {prefix}")))?;
+    Ok(body.trim().to_string())
+}
+
+/// Download URL of legacy `version` for this machine, and the executable name inside the zip
+pub(crate) fn download_url(version: &str) -> Result<(String, &'static str)> {
+    download_url_for(version, current_platform()?)
+}
+
+/// Download URL of legacy `version` for `platform`, and the executable name inside the zip
+pub(crate) fn download_url_for(
+    version: &str,
+    platform: Platform,
+) -> Result<(String, &'static str)> {
+    let platform = platform_name(version, platform)?;
+    let exec_name = if platform == "win32" {
+        "chromedriver.exe"
+    } else {
+        "chromedriver"
+    };
+    let url = format!("{LEGACY_BASE}/{version}/chromedriver_{platform}.zip");
+    Ok((url, exec_name))
+}
+
+/// Legacy bucket platform name of the current machine
+pub(crate) fn legacy_platform(version: &str) -> Result<&'static str> {
+    platform_name(version, current_platform()?)
+}
+
+/// This machine as a Chrome for Testing platform, if legacy builds exist for it
+fn current_platform() -> Result<Platform> {
+    Platform::current().map_err(|_| {
+        DriverError::UnsupportedPlatform(format!(
+            "no legacy ChromeDriver for {} ({})",
+            env::consts::OS,
+            env::consts::ARCH
+        ))
+    })
+}
+
+/// Legacy bucket name of `platform`
+fn platform_name(version: &str, platform: Platform) -> Result<&'static str> {
+    let major: u32 = version
+        .split('.')
+        .next()
+        .unwrap_or_default()
+        .parse()
+        .map_err(|_| DriverError::InvalidVersion(version.to_string()))?;
+    Ok(match platform {
+        // Apple Silicon builds were renamed in milestone 106
+        Platform::MacArm64 if major >= 106 => "mac_arm64",
+        Platform::MacArm64 => "mac64_m1",
+        Platform::MacX64 => "mac64",
+        Platform::Linux64 => "linux64",
+        // Only 32-bit Windows builds exist before Chrome for Testing
+        Platform::Win32 | Platform::Win64 => "win32",
+    })
+}
+
+/// Install a pre-115 ChromeDriver from the legacy bucket.
+///
+/// Legacy zips keep `chromedriver` at the archive root, so they are extracted
+/// into `{out_dir}/chromedriver_{platform}/`.
+pub(crate) async fn install_legacy_driver(
+    manager: &DriverManager,
+    version: &str,
+    out_dir: &str,
+) -> Result<DriverInfo> {
+    let (url, exec_name) = download_url(version)?;
+    let platform = legacy_platform(version)?;
+    let zip_name = format!("chromedriver_{platform}");
+    let install_dir = Path::new(out_dir).join(&zip_name);
+    let driver_path = format!("{out_dir}/{zip_name}/{exec_name}");
+    let _lock = lock::lock(Path::new(out_dir), &zip_name).await?;
+
+    if !manager.force() && manifest::is_installed(&install_dir, Path::new(&driver_path)).await {
+        info!("✅ Already installed: {driver_path}");
+        return Ok(DriverInfo::new(driver_path, version));
+    }
+
+    let source = manager
+        .install_archive(&[url], &install_dir, false, exec_name, Some(version))
+        .await?;
+    manifest::record(&install_dir, version, platform, &source, None).await?;
+
+    info!("🚀 ChromeDriver ready at: {}", driver_path);
+
+    Ok(DriverInfo {
+        downloaded: true,
+        ..DriverInfo::new(driver_path, version)
+    })
+}
@@ -1,101 +1,582 @@
-use serde_json::Value;
-#[cfg(unix)]
-use std::os::unix::fs::PermissionsExt;
-use std::{env, fs, path::Path};
-use tokio::{fs as tokio_fs, task};
-use zip::ZipArchive;
+use std::{env, path::Path};
+use tracing::{debug, info};
+
+mod actions;
+mod alert;
+mod archive;
+mod bidi;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod browser;
+mod cache;
+mod cdp;
+mod checksum;
+mod console;
+mod cookie;
+mod element;
+mod error;
+mod grid;
+mod http;
+mod launch;
+mod legacy;
+mod linux_arm64;
+mod lock;
+mod manager;
+mod manifest;
+mod metadata;
+mod mirror;
+mod network;
+mod options;
+mod output;
+mod overrides;
+mod performance;
+mod plan;
+mod pool;
+mod print;
+mod process;
+mod progress;
+mod proxy;
+mod retry;
+mod rpc;
+mod rt;
+mod screenshot;
+mod script;
+mod session;
+mod shadow;
+mod supervise;
+mod upload;
+mod verbosity;
+mod version;
+mod versions;
+mod wait;
+mod websocket;
+mod window;
+
+pub use actions::{Actions, Key, MouseButton};
+pub use bidi::{Bidi, BidiEvent, BidiEvents, BrowsingContext};
+#[cfg(feature = "blocking")]
+pub use blocking::{
+    check_version_blocking, cleanup_old_drivers_blocking, current_driver_blocking,
+    detect_chrome_version_blocking, driver_status_blocking, driver_version_blocking,
+    ensure_artifact_blocking, ensure_chrome_browser_blocking, ensure_driver_blocking,
+    ensure_driver_for_installed_chrome_blocking, ensure_driver_for_milestone_blocking,
+    ensure_driver_version_blocking, ensure_latest_driver_blocking,
+    ensure_latest_driver_default_blocking, ensure_matched_pair_blocking,
+    find_chrome_installs_blocking, find_system_driver_blocking, install_from_zip_blocking,
+    latest_version_blocking, launch_blocking, launch_with_capabilities_blocking,
+    list_available_versions_blocking, list_installed_blocking, rollback_blocking,
+    uninstall_all_blocking, uninstall_blocking, verify_compatibility_blocking,
+};
+pub use browser::{detect_chrome_version, find_chrome_installs, find_system_driver, ChromeInstall};
+pub use cache::{
+    cleanup_old_drivers, current_driver, default_cache_dir, list_installed, rollback, uninstall,
+    uninstall_all,
+};
+pub use cdp::{Cdp, CdpCommand, CdpEvent, CdpEvents};
+pub use console::{ConsoleEvents, ConsoleLog, ConsoleMessage, ConsoleSource, LogLevel};
+pub use cookie::{Cookie, SameSite};
+pub use element::{By, Element};
+pub use error::{DriverError, Result};
+pub use grid::GridNode;
+pub use launch::{launch, launch_with_capabilities, Launched};
+pub use linux_arm64::LINUX_ARM64_URL_ENV;
+pub use manager::{DriverManager, DriverManagerBuilder, VersionSelector};
+pub use manifest::{InstallManifest, ManifestFile};
+pub use mirror::Mirror;
+pub use network::{
+    Interception, NetworkEvent, NetworkEvents, NetworkRequest, NetworkResponse, PausedRequest,
+    ResourceTiming,
+};
+pub use options::{ChromeOptions, MobileEmulation};
+pub use output::OutputStream;
+pub use overrides::{
+    CHROMEDRIVER_PATH_ENV, CHROMEDRIVER_SKIP_DOWNLOAD_ENV, CHROMEDRIVER_VERSION_ENV,
+};
+pub use performance::{PerformanceMetrics, Trace};
+pub use plan::InstallPlan;
+pub use pool::{DriverPool, PooledDriver};
+pub use print::{Margins, Orientation, PageSize, PrintOptions};
+pub use process::{DriverArgs, DriverProcess, DriverProcessBuilder, DEFAULT_DRIVER_PORT};
+pub use proxy::Proxy;
+pub use retry::RetryPolicy;
+pub use script::ScriptValue;
+pub use session::{driver_status, DriverStatus, Session};
+pub use shadow::ShadowRoot;
+pub use supervise::{RestartEvent, RestartPolicy};
+pub use tokio_util::sync::CancellationToken;
+pub use verbosity::Verbosity;
+pub use version::{driver_version, is_compatible, verify_compatibility, DriverVersion};
+pub use versions::{list_available_versions, AvailableVersion, Download};
+pub use wait::{ElementWait, Wait};
+pub use window::{Frame, WindowRect, WindowType};
 
 /// Information about the installed ChromeDriver
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DriverInfo {
     /// Path to the ChromeDriver executable
     pub driver_path: String,
     /// Installed version
     pub version: String,
+    /// Platform the driver was built for, e.g. `linux64`; `None` if this crate didn't install it
+    pub platform: Option<String>,
+    /// CPU architecture of this machine, e.g. `x86_64`
+    pub arch: String,
+    /// URL the driver was downloaded from
+    pub source_url: Option<String>,
+    /// Hex SHA-256 of the downloaded archive
+    pub sha256: Option<String>,
+    /// Install time in seconds since the Unix epoch
+    pub installed_at: Option<u64>,
+    /// Whether this call downloaded the driver, rather than finding it installed
+    pub downloaded: bool,
+}
+
+impl DriverInfo {
+    /// Driver at `driver_path` without provenance
+    pub(crate) fn new(driver_path: impl Into<String>, version: impl Into<String>) -> Self {
+        DriverInfo {
+            driver_path: driver_path.into(),
+            version: version.into(),
+            platform: None,
+            arch: env::consts::ARCH.to_string(),
+            source_url: None,
+            sha256: None,
+            installed_at: None,
+            downloaded: false,
+        }
+    }
+
+    /// Fill in the provenance from the `manifest.json` next to the driver, if there is one
+    pub(crate) fn with_manifest(mut self) -> Self {
+        let dir = Path::new(&self.driver_path).parent();
+        if let Some(manifest) = dir.and_then(|dir| InstallManifest::load(dir).ok().flatten()) {
+            self.platform = Some(manifest.platform);
+            self.source_url = Some(manifest.url);
+            self.sha256 = Some(manifest.sha256);
+            self.installed_at = Some(manifest.installed_at);
+        }
+        self
+    }
+}
+
+/// Chrome for Testing release channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Channel {
+    Stable,
+    Beta,
+    Dev,
+    Canary,
+}
+
+impl Channel {
+    /// Key used for this channel in the Chrome for Testing JSON endpoints
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Channel::Stable => "Stable",
+            Channel::Beta => "Beta",
+            Channel::Dev => "Dev",
+            Channel::Canary => "Canary",
+        }
+    }
+
+    /// Parse a channel name as used by [`Channel::as_str`], ignoring case, e.g. `beta`
+    pub fn from_name(name: &str) -> Option<Channel> {
+        [
+            Channel::Stable,
+            Channel::Beta,
+            Channel::Dev,
+            Channel::Canary,
+        ]
+        .into_iter()
+        .find(|channel| channel.as_str().eq_ignore_ascii_case(name))
+    }
 }
 
 /// Check and install the latest ChromeDriver asynchronously.
 ///
 /// * If the latest version is already installed, the download is skipped.
-/// * Supports macOS (Intel/ARM) and Windows.
-pub async fn ensure_latest_driver(
-    out_dir: &str,
-) -> Result<DriverInfo, Box<dyn std::error::Error + Send + Sync + 'static>> {
-    // 1️⃣ Fetch the latest version info
-    let versions_url =
-        "https://googlechromelabs.github.io/chrome-for-testing/last-known-good-versions.json";
-    let body = reqwest::get(versions_url).await?.text().await?;
-    let json: Value = serde_json::from_str(&body)?;
-    let version = json["channels"]["Stable"]["version"]
-        .as_str()
-        .ok_or("Failed to read version")?;
-    println!("🌐 Latest ChromeDriver version: {version}");
-
-    // 2️⃣ Detect platform
-    let (platform, exec_name, zip_name) = match env::consts::OS {
-        "macos" => {
-            let arch = env::consts::ARCH;
-            if arch == "aarch64" {
-                ("mac-arm64", "chromedriver", "chromedriver-mac-arm64")
-            } else {
-                ("mac-x64", "chromedriver", "chromedriver-mac-x64")
+/// * Supports macOS (Intel/ARM), Linux x64 and Windows.
+/// * Shorthand for [`DriverManager`] with its default configuration.
+pub async fn ensure_latest_driver(out_dir: &str) -> Result<DriverInfo> {
+    ensure_driver(Channel::Stable, out_dir).await
+}
+
+/// [`ensure_latest_driver`] into the per-user [`default_cache_dir`].
+///
+/// * Every project on the machine shares one set of downloaded drivers.
+pub async fn ensure_latest_driver_default() -> Result<DriverInfo> {
+    let out_dir = default_cache_dir()?;
+    ensure_latest_driver(&out_dir.to_string_lossy()).await
+}
+
+/// Check and install the latest ChromeDriver of the given release channel.
+pub async fn ensure_driver(channel: Channel, out_dir: &str) -> Result<DriverInfo> {
+    DriverManager::builder()
+        .out_dir(out_dir)
+        .channel(channel)
+        .build()?
+        .install()
+        .await
+}
+
+/// The current version of `channel`, from the metadata endpoint alone.
+///
+/// * No filesystem access: nothing is installed or cached.
+/// * Lets monitoring jobs notice a new release before the next install.
+pub async fn latest_version(channel: Channel) -> Result<String> {
+    DriverManager::builder()
+        .channel(channel)
+        .metadata_cache(false)
+        .env_overrides(false)
+        .build()?
+        .resolve_version()
+        .await
+}
+
+/// Information about an installed Chrome for Testing browser
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BrowserInfo {
+    /// Path to the browser executable
+    pub browser_path: String,
+    /// Installed version
+    pub version: String,
+}
+
+/// Downloadable Chrome for Testing artifact
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Artifact {
+    #[cfg_attr(feature = "serde", serde(rename = "chromedriver"))]
+    ChromeDriver,
+    #[cfg_attr(feature = "serde", serde(rename = "chrome"))]
+    Chrome,
+    #[cfg_attr(feature = "serde", serde(rename = "chrome-headless-shell"))]
+    HeadlessShell,
+}
+
+impl Artifact {
+    /// Artifact name used in Chrome for Testing zip names and JSON endpoints
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Artifact::ChromeDriver => "chromedriver",
+            Artifact::Chrome => "chrome",
+            Artifact::HeadlessShell => "chrome-headless-shell",
+        }
+    }
+
+    /// Parse an artifact name as used by [`Artifact::as_str`]
+    pub fn from_name(name: &str) -> Option<Artifact> {
+        match name {
+            "chromedriver" => Some(Artifact::ChromeDriver),
+            "chrome" => Some(Artifact::Chrome),
+            "chrome-headless-shell" => Some(Artifact::HeadlessShell),
+            _ => None,
+        }
+    }
+
+    /// Path of the executable inside the extracted `{artifact}-{platform}` folder
+    pub(crate) fn executable(&self, platform: &str) -> &'static str {
+        let windows = platform.starts_with("win");
+        match self {
+            Artifact::ChromeDriver if windows => "chromedriver.exe",
+            Artifact::ChromeDriver => "chromedriver",
+            Artifact::Chrome if windows => "chrome.exe",
+            Artifact::Chrome if platform.starts_with("mac") => {
+                "Google Chrome for Testing.app/Contents/MacOS/Google Chrome for Testing"
             }
+            Artifact::Chrome => "chrome",
+            Artifact::HeadlessShell if windows => "chrome-headless-shell.exe",
+            Artifact::HeadlessShell => "chrome-headless-shell",
+        }
+    }
+}
+
+/// Platform a Chrome for Testing build is published for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Platform {
+    #[cfg_attr(feature = "serde", serde(rename = "linux64"))]
+    Linux64,
+    #[cfg_attr(feature = "serde", serde(rename = "mac-x64"))]
+    MacX64,
+    #[cfg_attr(feature = "serde", serde(rename = "mac-arm64"))]
+    MacArm64,
+    #[cfg_attr(feature = "serde", serde(rename = "win32"))]
+    Win32,
+    #[cfg_attr(feature = "serde", serde(rename = "win64"))]
+    Win64,
+}
+
+impl Platform {
+    /// Every published platform
+    pub const ALL: [Platform; 5] = [
+        Platform::Linux64,
+        Platform::MacX64,
+        Platform::MacArm64,
+        Platform::Win32,
+        Platform::Win64,
+    ];
+
+    /// Name used in download URLs, e.g. `mac-arm64`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Platform::Linux64 => "linux64",
+            Platform::MacX64 => "mac-x64",
+            Platform::MacArm64 => "mac-arm64",
+            Platform::Win32 => "win32",
+            Platform::Win64 => "win64",
         }
-        "windows" => ("win64", "chromedriver.exe", "chromedriver-win64"),
-        other => return Err(format!("Unsupported OS: {}", other).into()),
-    };
-
-    // 3️⃣ Check if already installed
-    let driver_path = format!("{}/{}/{}", out_dir, zip_name, exec_name);
-    if Path::new(&driver_path).exists() {
-        println!("✅ Already installed: {driver_path}");
-        return Ok(DriverInfo {
-            driver_path,
-            version: version.to_string(),
-        });
-    }
-
-    // 4️⃣ Build download URL
-    let url = format!(
-        "https://edgedl.me.gvt1.com/edgedl/chrome/chrome-for-testing/{}/{}/{}.zip",
-        version, platform, zip_name
-    );
-    println!("⬇️ Downloading from: {url}");
-
-    // 5️⃣ Download zip
-    let bytes = reqwest::get(&url).await?.bytes().await?;
-
-    // 6️⃣ Extract archive (ZipArchive is blocking → use spawn_blocking)
-    tokio_fs::create_dir_all(out_dir).await?;
-    let out_dir_owned = out_dir.to_owned();
-    task::spawn_blocking(move || -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
-        let reader = std::io::Cursor::new(bytes);
-        let mut archive = ZipArchive::new(reader)?;
-        archive.extract(&out_dir_owned)?;
-        Ok(())
-    })
-    .await??;
-
-    // 7️⃣ Set execute permissions (Unix only)
-    #[cfg(unix)]
-    {
-        let full_path = Path::new(out_dir).join(zip_name).join(exec_name);
-        fs::set_permissions(&full_path, fs::Permissions::from_mode(0o755))?;
-    }
-
-    println!("🚀 ChromeDriver ready at: {}", driver_path);
-
-    Ok(DriverInfo {
-        driver_path,
-        version: version.to_string(),
-    })
-}
-
-/// Check the installed driver version (async)
-pub async fn check_version(driver_path: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let status = tokio::process::Command::new(driver_path)
-        .arg("--version")
-        .status()
-        .await?;
-    println!("Driver check finished with status: {status}");
+    }
+
+    /// Parse a platform name as used by [`Platform::as_str`]
+    pub fn from_name(name: &str) -> Option<Platform> {
+        Platform::ALL
+            .into_iter()
+            .find(|platform| platform.as_str() == name)
+    }
+
+    /// The platform of this machine; linux-arm64 has no Chrome for Testing build
+    pub fn current() -> Result<Platform> {
+        cft_platform().map(|name| Platform::from_name(name).expect("known platform name"))
+    }
+}
+
+/// Information about an installed artifact
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArtifactInfo {
+    /// Which artifact was installed
+    pub artifact: Artifact,
+    /// Path to the artifact's executable
+    pub path: String,
+    /// Installed version
+    pub version: String,
+    /// Whether this call downloaded the artifact, rather than finding it installed
+    pub downloaded: bool,
+}
+
+impl From<ArtifactInfo> for DriverInfo {
+    fn from(info: ArtifactInfo) -> Self {
+        DriverInfo {
+            downloaded: info.downloaded,
+            ..DriverInfo::new(info.path, info.version)
+        }
+    }
+}
+
+impl From<ArtifactInfo> for BrowserInfo {
+    fn from(info: ArtifactInfo) -> Self {
+        BrowserInfo {
+            browser_path: info.path,
+            version: info.version,
+        }
+    }
+}
+
+/// Check and install the latest Stable Chrome for Testing browser.
+///
+/// * Uses the same version and endpoints as [`ensure_latest_driver`].
+/// * If the latest version is already installed, the download is skipped.
+pub async fn ensure_chrome_browser(out_dir: &str) -> Result<BrowserInfo> {
+    ensure_artifact(Artifact::Chrome, Channel::Stable, out_dir)
+        .await
+        .map(BrowserInfo::from)
+}
+
+/// Chrome for Testing browser and ChromeDriver of the identical version
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchedPair {
+    /// The installed driver
+    pub driver: DriverInfo,
+    /// The installed browser
+    pub browser: BrowserInfo,
+}
+
+/// Install the latest Stable browser and driver of the same version in one go.
+///
+/// * The version is resolved once, so a Stable roll between both installs
+///   can't cause a version skew.
+/// * Both artifacts are placed side by side in `out_dir`.
+pub async fn ensure_matched_pair(out_dir: &str) -> Result<MatchedPair> {
+    DriverManager::builder()
+        .out_dir(out_dir)
+        .build()?
+        .install_matched_pair()
+        .await
+}
+
+/// Check and install the latest `artifact` of the given release channel.
+///
+/// * [`Artifact::HeadlessShell`] is a much smaller browser for headless workloads.
+pub async fn ensure_artifact(
+    artifact: Artifact,
+    channel: Channel,
+    out_dir: &str,
+) -> Result<ArtifactInfo> {
+    DriverManager::builder()
+        .out_dir(out_dir)
+        .channel(channel)
+        .build()?
+        .install_artifact(artifact)
+        .await
+}
+
+/// Install exactly ChromeDriver `version` (e.g. `"126.0.6478.126"`).
+///
+/// * Skips the last-known-good lookup entirely.
+/// * Installs under `{out_dir}/{version}/{platform}/` like every version, so pins don't collide.
+/// * Versions before 115 come from the legacy `chromedriver.storage.googleapis.com` bucket.
+pub async fn ensure_driver_version(version: &str, out_dir: &str) -> Result<DriverInfo> {
+    DriverManager::builder()
+        .out_dir(out_dir)
+        .version(version)
+        .build()?
+        .install()
+        .await
+}
+
+/// Install the ChromeDriver matching the locally installed Chrome.
+///
+/// * Picks the newest driver of the same `MAJOR.MINOR.BUILD`.
+/// * Falls back to the newest driver of the same milestone.
+/// * Chrome older than 115 is matched against the legacy download bucket.
+pub async fn ensure_driver_for_installed_chrome(out_dir: &str) -> Result<DriverInfo> {
+    DriverManager::builder()
+        .out_dir(out_dir)
+        .match_installed_chrome()
+        .build()?
+        .install()
+        .await
+}
+
+/// Install the latest ChromeDriver of a milestone (major version), e.g. `124`.
+///
+/// * Installs under `{out_dir}/{version}/{platform}/` like [`ensure_driver_version`].
+/// * Milestones before 115 resolve through the legacy download bucket.
+pub async fn ensure_driver_for_milestone(milestone: u32, out_dir: &str) -> Result<DriverInfo> {
+    DriverManager::builder()
+        .out_dir(out_dir)
+        .milestone(milestone)
+        .build()?
+        .install()
+        .await
+}
+
+/// Install ChromeDriver from a previously downloaded `chromedriver-*.zip`.
+///
+/// * Extracts into `out_dir` and applies execute permissions, without any network access.
+/// * Useful for artifact caches, vendored zips and hermetic builds.
+pub async fn install_from_zip(
+    zip_path: impl AsRef<Path>,
+    out_dir: impl AsRef<Path>,
+) -> Result<DriverInfo> {
+    manager::install_driver_zip(zip_path.as_ref(), out_dir.as_ref()).await
+}
+
+/// The exact URL the default configuration downloads `artifact` at `version` from for
+/// `platform`, without any network access.
+///
+/// * Pre-115 ChromeDrivers map to the legacy `chromedriver.storage.googleapis.com` bucket.
+/// * Use [`DriverManager::resolve_download_url`] to apply configured [`mirrors`](DriverManagerBuilder::mirrors).
+pub fn resolve_download_url(
+    version: &str,
+    artifact: Artifact,
+    platform: Platform,
+) -> Result<String> {
+    download_url(&Mirror::google(), version, artifact, platform)
+}
+
+/// URL of `artifact` at `version` for `platform` on `mirror`
+fn download_url(
+    mirror: &Mirror,
+    version: &str,
+    artifact: Artifact,
+    platform: Platform,
+) -> Result<String> {
+    if version.is_empty() || !version.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return Err(DriverError::InvalidVersion(version.to_string()));
+    }
+    if artifact == Artifact::ChromeDriver && legacy::is_legacy(version) {
+        return legacy::download_url_for(version, platform).map(|(url, _)| url);
+    }
+    Ok(mirror.download_url(artifact, version, platform.as_str()))
+}
+
+/// Base URL of the Chrome for Testing JSON endpoints
+pub const DEFAULT_METADATA_BASE_URL: &str = "https://googlechromelabs.github.io/chrome-for-testing";
+
+/// URL template of Chrome for Testing downloads.
+///
+/// `{version}`, `{platform}` and `{artifact}` are substituted per download.
+pub const DEFAULT_DOWNLOAD_URL_TEMPLATE: &str = "https://edgedl.me.gvt1.com/edgedl/chrome/chrome-for-testing/{version}/{platform}/{artifact}-{platform}.zip";
+
+/// Chrome for Testing platform name of the current machine
+fn cft_platform() -> Result<&'static str> {
+    match (env::consts::OS, env::consts::ARCH) {
+        ("macos", "aarch64") => Ok("mac-arm64"),
+        ("macos", _) => Ok("mac-x64"),
+        ("linux", "x86_64") => Ok("linux64"),
+        ("windows", _) => Ok("win64"),
+        (os, arch) => Err(DriverError::UnsupportedPlatform(format!("{os} ({arch})"))),
+    }
+}
+
+/// Directory name of the current machine in the `{out_dir}/{version}/{platform}/` layout
+fn platform_dir() -> Result<&'static str> {
+    match (env::consts::OS, env::consts::ARCH) {
+        ("linux", "aarch64") => Ok("linux-arm64"),
+        _ => cft_platform(),
+    }
+}
+
+/// Check the installed driver version (async); [`driver_version`] returns it instead
+pub async fn check_version(driver_path: &str) -> Result<()> {
+    let output = rt::command_output(driver_path, &["--version"])
+        .await
+        .map_err(|e| DriverError::io(format!("running {driver_path}"), e))?;
+    info!("🔎 {}", String::from_utf8_lossy(&output.stdout).trim());
+    debug!("Driver check finished with status: {}", output.status);
     Ok(())
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn driver_info_round_trips_through_json() {
+        let info = DriverInfo {
+            platform: Some("linux64".into()),
+            source_url: Some("https://example.com/chromedriver-linux64.zip".into()),
+            sha256: Some("00ff".into()),
+            installed_at: Some(1_700_000_000),
+            downloaded: true,
+            ..DriverInfo::new("/opt/driver/chromedriver", "130.0.6723.58")
+        };
+        let json = serde_json::to_string(&info).unwrap();
+        let parsed: DriverInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            serde_json::to_value(&parsed).unwrap(),
+            serde_json::to_value(&info).unwrap()
+        );
+        assert_eq!(parsed.driver_path, "/opt/driver/chromedriver");
+        assert_eq!(parsed.version, "130.0.6723.58");
+        assert_eq!(parsed.arch, env::consts::ARCH);
+        assert_eq!(parsed.installed_at, Some(1_700_000_000));
+        assert!(parsed.downloaded);
+    }
+
+    #[test]
+    fn driver_info_round_trips_without_provenance() {
+        let info = DriverInfo::new("chromedriver", "114.0.5735.90");
+        let parsed: DriverInfo =
+            serde_json::from_value(serde_json::to_value(&info).unwrap()).unwrap();
+        assert_eq!(parsed.platform, None);
+        assert_eq!(parsed.source_url, None);
+        assert!(!parsed.downloaded);
+    }
+}
@@ -0,0 +1,128 @@
+use std::{env, path::Path};
+use tracing::{info, warn};
+
+use crate::{
+    error::{DriverError, Result},
+    is_compatible, lock, manifest, rt, DriverInfo, DriverManager, DriverVersion, InstallManifest,
+};
+
+/// Locations where distributions install their `chromium-driver` package.
+const SYSTEM_DRIVER_PATHS: &[&str] = &[
+    "/usr/bin/chromedriver",
+    "/usr/lib/chromium/chromedriver",
+    "/usr/lib/chromium-browser/chromedriver",
+    "/snap/bin/chromium.chromedriver",
+];
+
+/// Environment variable holding an alternate zip URL for Linux ARM64.
+///
+/// * `{version}` in the value is replaced with the resolved Chrome version, for mirrors
+///   publishing drivers under Chrome versions.
+/// * Electron numbers its builds by Electron release, so its URLs name a fixed release whose
+///   Chromium milestone matches the browser instead.
+pub const LINUX_ARM64_URL_ENV: &str = "CHROMEDRIVER_LINUX_ARM64_URL";
+
+/// Installed distro driver, if any
+pub(crate) fn system_driver() -> Option<&'static str> {
+    SYSTEM_DRIVER_PATHS
+        .iter()
+        .copied()
+        .find(|path| Path::new(path).exists())
+}
+
+/// Chrome for Testing publishes no linux-arm64 builds, so on aarch64 Linux:
+///
+/// * A distro-provided `chromium-driver` is used when one is installed and matches the
+///   milestone of `version`.
+/// * Otherwise the zip at [`LINUX_ARM64_URL_ENV`] is downloaded
+///   (e.g. electron's `chromedriver-v30.0.0-linux-arm64.zip` for Chromium 124) into
+///   `{out_dir}/chromedriver-linux-arm64/`; `out_dir` is per version, e.g.
+///   `{out_dir}/{version}/linux-arm64`.
+pub(crate) async fn ensure_linux_arm64_driver(
+    manager: &DriverManager,
+    out_dir: &str,
+    version: &str,
+) -> Result<DriverInfo> {
+    // 1️⃣ Prefer the distro driver
+    if let Some(driver) = matching_system_driver(version).await {
+        return Ok(driver);
+    }
+
+    // 2️⃣ Fall back to the configured alternate source
+    let template = env::var(LINUX_ARM64_URL_ENV).map_err(|_| {
+        DriverError::UnsupportedPlatform(format!(
+            "linux-arm64 has no Chrome for Testing build; \
+             install chromium-driver {} or set {LINUX_ARM64_URL_ENV}",
+            milestone(version)
+        ))
+    })?;
+    let install_dir = Path::new(out_dir).join("chromedriver-linux-arm64");
+    let driver_path = format!("{out_dir}/chromedriver-linux-arm64/chromedriver");
+    let _lock = lock::lock(Path::new(out_dir), "chromedriver-linux-arm64").await?;
+    // Only reuse an install of this very version
+    let recorded = InstallManifest::load(&install_dir).ok().flatten();
+    let same_version = recorded.is_none_or(|manifest| manifest.version == version);
+    if !manager.force()
+        && same_version
+        && manifest::is_installed(&install_dir, Path::new(&driver_path)).await
+    {
+        info!("✅ Already installed: {driver_path}");
+        return Ok(DriverInfo::new(driver_path, version));
+    }
+
+    // Alternate zips keep `chromedriver` at the archive root
+    let url = template.replace("{version}", version);
+    let source = manager
+        .install_archive(&[url], &install_dir, false, "chromedriver", None)
+        .await?;
+    manifest::record(&install_dir, version, "linux-arm64", &source, None).await?;
+
+    info!("🚀 ChromeDriver ready at: {}", driver_path);
+
+    Ok(DriverInfo {
+        downloaded: true,
+        ..DriverInfo::new(driver_path, version)
+    })
+}
+
+/// The distro driver, if one is installed, runs and supports the milestone of `version`
+async fn matching_system_driver(version: &str) -> Option<DriverInfo> {
+    let path = system_driver()?;
+    let installed = match rt::command_output(path, &["--version"]).await {
+        Ok(output) if output.status.success() => {
+            DriverVersion::from_output(&String::from_utf8_lossy(&output.stdout))
+        }
+        Ok(output) => {
+            warn!(
+                "⚠️ {path} --version failed ({}), ignoring it",
+                output.status
+            );
+            return None;
+        }
+        Err(e) => {
+            warn!("⚠️ Couldn't run {path}: {e}, ignoring it");
+            return None;
+        }
+    };
+    match installed {
+        Ok(installed) if is_compatible(&installed.to_string(), version) => {
+            info!("✅ Using system chromium-driver {installed}: {path}");
+            Some(DriverInfo::new(path, installed.to_string()))
+        }
+        Ok(installed) => {
+            warn!(
+                "⚠️ System chromium-driver {installed} doesn't match milestone {}, ignoring it",
+                milestone(version)
+            );
+            None
+        }
+        Err(e) => {
+            warn!("⚠️ {path} reported no version ({e}), ignoring it");
+            None
+        }
+    }
+}
+
+fn milestone(version: &str) -> &str {
+    version.split('.').next().unwrap_or(version)
+}
@@ -0,0 +1,44 @@
+use std::{
+    fs::{self, File, TryLockError},
+    path::Path,
+};
+use tracing::info;
+
+use crate::{
+    error::{DriverError, Result},
+    rt,
+};
+
+/// Advisory lock serializing installs of one artifact into one directory.
+///
+/// * Works across processes, e.g. parallel `cargo test` binaries.
+/// * Released when dropped, or by the OS if the process dies.
+pub(crate) struct InstallLock {
+    _file: File,
+}
+
+/// Lock `{dir}/.{name}.lock`, waiting for other installers to finish first
+pub(crate) async fn lock(dir: &Path, name: &str) -> Result<InstallLock> {
+    let path = dir.join(format!(".{name}.lock"));
+    rt::spawn_blocking(move || {
+        let context = || format!("locking {}", path.display());
+        fs::create_dir_all(path.parent().unwrap_or(Path::new(".")))
+            .map_err(|e| DriverError::io(context(), e))?;
+        let file = File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .map_err(|e| DriverError::io(context(), e))?;
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                info!("⏳ Waiting for another install in {}", path.display());
+                file.lock().map_err(|e| DriverError::io(context(), e))?;
+            }
+            Err(TryLockError::Error(e)) => return Err(DriverError::io(context(), e)),
+        }
+        Ok(InstallLock { _file: file })
+    })
+    .await
+}
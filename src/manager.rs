@@ -0,0 +1,1513 @@
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+use std::{
+    env, fs,
+    future::Future,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, info_span, instrument::WithSubscriber, warn, Instrument};
+use zip::ZipArchive;
+
+use crate::{
+    archive::{self, extract},
+    browser::{parse_version, version_key},
+    cache, cft_platform,
+    checksum::{self, Checksum},
+    detect_chrome_version,
+    error::{DriverError, Result},
+    find_system_driver, http, legacy, linux_arm64, lock,
+    manifest::{self, Source},
+    metadata::{self, CachedMetadata},
+    overrides,
+    plan::{self, InstallPlan},
+    platform_dir,
+    progress::Progress,
+    retry::{self, RetryPolicy},
+    rt,
+    verbosity::Verbosity,
+    versions, Artifact, ArtifactInfo, AvailableVersion, Channel, DriverInfo, MatchedPair, Mirror,
+    Platform,
+};
+
+/// Which version a [`DriverManager`] installs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionSelector {
+    /// Latest version of a release channel
+    Latest(Channel),
+    /// Exactly this version, e.g. `126.0.6478.126`
+    Exact(String),
+    /// Latest version of a milestone (major version)
+    Milestone(u32),
+    /// The version matching the locally installed Chrome
+    InstalledChrome,
+}
+
+/// Configurable ChromeDriver installer.
+///
+/// * Created through [`DriverManager::builder`].
+/// * [`DriverManager::install`] performs the version lookup, download and extraction.
+#[derive(Debug, Clone)]
+pub struct DriverManager {
+    out_dir: String,
+    selector: VersionSelector,
+    client: reqwest::Client,
+    mirrors: Vec<Mirror>,
+    offline: bool,
+    checksum: Option<Checksum>,
+    progress: Option<Progress>,
+    retry: RetryPolicy,
+    metadata_timeout: Duration,
+    metadata_ttl: Duration,
+    metadata_cache: bool,
+    download_timeout: Duration,
+    cancel: Option<CancellationToken>,
+    flat: bool,
+    force: bool,
+    prefer_system: bool,
+    driver_override: Option<PathBuf>,
+    verbosity: Verbosity,
+    dry_run: bool,
+}
+
+/// Fluent builder for [`DriverManager`]
+#[derive(Debug, Clone)]
+pub struct DriverManagerBuilder {
+    out_dir: String,
+    selector: VersionSelector,
+    client: Option<reqwest::Client>,
+    proxy: Option<String>,
+    no_proxy: Vec<String>,
+    env_proxy: bool,
+    mirrors: Vec<Mirror>,
+    offline: bool,
+    checksum: Option<Checksum>,
+    progress: Option<Progress>,
+    retry: RetryPolicy,
+    connect_timeout: Duration,
+    read_timeout: Duration,
+    metadata_timeout: Duration,
+    metadata_ttl: Duration,
+    metadata_cache: bool,
+    download_timeout: Duration,
+    cancel: Option<CancellationToken>,
+    flat: bool,
+    force: bool,
+    prefer_system: bool,
+    env_overrides: bool,
+    verbosity: Verbosity,
+    dry_run: bool,
+}
+
+impl Default for DriverManagerBuilder {
+    fn default() -> Self {
+        DriverManagerBuilder {
+            out_dir: "./driver".to_string(),
+            selector: VersionSelector::Latest(Channel::Stable),
+            client: None,
+            proxy: None,
+            no_proxy: Vec::new(),
+            env_proxy: true,
+            mirrors: vec![Mirror::google()],
+            offline: false,
+            checksum: None,
+            progress: None,
+            retry: RetryPolicy::default(),
+            connect_timeout: Duration::from_secs(10),
+            read_timeout: Duration::from_secs(30),
+            metadata_timeout: Duration::from_secs(30),
+            metadata_ttl: Duration::ZERO,
+            metadata_cache: true,
+            download_timeout: Duration::from_secs(10 * 60),
+            cancel: None,
+            flat: false,
+            force: false,
+            prefer_system: false,
+            env_overrides: true,
+            verbosity: Verbosity::default(),
+            dry_run: false,
+        }
+    }
+}
+
+impl DriverManagerBuilder {
+    /// Directory the artifacts are installed into (default `./driver`)
+    pub fn out_dir(mut self, out_dir: impl Into<String>) -> Self {
+        self.out_dir = out_dir.into();
+        self
+    }
+
+    /// Install the latest version of `channel` (the default is Stable)
+    pub fn channel(mut self, channel: Channel) -> Self {
+        self.selector = VersionSelector::Latest(channel);
+        self
+    }
+
+    /// Install exactly `version`
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.selector = VersionSelector::Exact(version.into());
+        self
+    }
+
+    /// Install the latest version of `milestone`
+    pub fn milestone(mut self, milestone: u32) -> Self {
+        self.selector = VersionSelector::Milestone(milestone);
+        self
+    }
+
+    /// Install the version matching the locally installed Chrome
+    pub fn match_installed_chrome(mut self) -> Self {
+        self.selector = VersionSelector::InstalledChrome;
+        self
+    }
+
+    /// Set the version selection directly
+    pub fn selector(mut self, selector: VersionSelector) -> Self {
+        self.selector = selector;
+        self
+    }
+
+    /// Reuse an existing HTTP client (connection pool, proxies, TLS settings)
+    /// for the version lookups and downloads
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Send all requests through this proxy, e.g. `http://proxy.corp:3128`.
+    ///
+    /// * Takes precedence over `HTTPS_PROXY`/`HTTP_PROXY`.
+    /// * Ignored when a custom [`client`](Self::client) is set.
+    pub fn proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Hosts that bypass the proxy, in addition to those in `NO_PROXY`
+    pub fn no_proxy<I, S>(mut self, hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.no_proxy.extend(hosts.into_iter().map(Into::into));
+        self
+    }
+
+    /// Honor `HTTPS_PROXY`/`HTTP_PROXY`/`NO_PROXY` (default `true`)
+    pub fn env_proxy(mut self, enabled: bool) -> Self {
+        self.env_proxy = enabled;
+        self
+    }
+
+    /// Serve the version JSON endpoints of the primary mirror from elsewhere.
+    ///
+    /// The mirror must expose the same file names as [`crate::DEFAULT_METADATA_BASE_URL`],
+    /// e.g. `{url}/last-known-good-versions.json`.
+    pub fn metadata_base_url(mut self, url: impl Into<String>) -> Self {
+        let primary = self.primary_mirror();
+        primary.metadata_base_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Download artifacts of the primary mirror from elsewhere.
+    ///
+    /// `{version}`, `{platform}` and `{artifact}` are substituted, see
+    /// [`crate::DEFAULT_DOWNLOAD_URL_TEMPLATE`].
+    pub fn download_url_template(mut self, template: impl Into<String>) -> Self {
+        self.primary_mirror().download_url_template = template.into();
+        self
+    }
+
+    /// Replace the mirror list; mirrors are tried in order until one succeeds.
+    ///
+    /// [`Mirror::builtin`] fails over from Google to npmmirror.com.
+    pub fn mirrors(mut self, mirrors: impl IntoIterator<Item = Mirror>) -> Self {
+        self.mirrors = mirrors.into_iter().collect();
+        self
+    }
+
+    /// Append a mirror tried after the existing ones fail
+    pub fn fallback_mirror(mut self, mirror: Mirror) -> Self {
+        self.mirrors.push(mirror);
+        self
+    }
+
+    /// Never touch the network: only reuse what is already installed in `out_dir`
+    /// and fail fast with [`DriverError::Offline`] otherwise
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Verify the downloaded archive against this hex SHA-256 before extracting it
+    pub fn sha256(mut self, digest: impl Into<String>) -> Self {
+        self.checksum = Some(Checksum::Sha256(digest.into()));
+        self
+    }
+
+    /// Verify downloads against a `sha256sum`-style manifest, looked up by zip file name
+    pub fn checksum_manifest_url(mut self, url: impl Into<String>) -> Self {
+        self.checksum = Some(Checksum::ManifestUrl(url.into()));
+        self
+    }
+
+    /// Call `callback(bytes_downloaded, total_bytes)` as archives download.
+    ///
+    /// `total_bytes` is `None` when the server doesn't announce a size.
+    pub fn on_progress(
+        mut self,
+        callback: impl Fn(u64, Option<u64>) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(Progress::new(callback));
+        self
+    }
+
+    /// Render a terminal progress bar (speed, ETA) on stderr while downloading and
+    /// extracting; replaces any [`on_progress`](Self::on_progress) callback
+    #[cfg(feature = "progress-bar")]
+    pub fn progress_bar(mut self) -> Self {
+        self.progress = Some(Progress::bar());
+        self
+    }
+
+    /// Retry transient failures of lookups and downloads, see [`RetryPolicy`].
+    ///
+    /// Defaults to [`RetryPolicy::default`]; [`RetryPolicy::none`] disables retries.
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Give up connecting to a host after `timeout` (default 10s).
+    ///
+    /// Like [`read_timeout`](Self::read_timeout), not applied to a [`client`](Self::client)
+    /// passed in by the caller.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Fail a request when no data arrives for `timeout` (default 30s)
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = timeout;
+        self
+    }
+
+    /// Deadline of each version lookup request (default 30s)
+    pub fn metadata_timeout(mut self, timeout: Duration) -> Self {
+        self.metadata_timeout = timeout;
+        self
+    }
+
+    /// Reuse cached version lookups younger than `ttl` without asking the endpoint at all
+    /// (default zero: always revalidate).
+    ///
+    /// * e.g. `Duration::from_secs(3600)` checks for a new Stable at most once per hour.
+    /// * Lookups are cached per `out_dir` and mirror.
+    pub fn metadata_ttl(mut self, ttl: Duration) -> Self {
+        self.metadata_ttl = ttl;
+        self
+    }
+
+    /// Cache version lookups in `{out_dir}/.metadata/` (default on).
+    ///
+    /// * Off, every lookup is a plain request and nothing is read from or written to disk.
+    pub fn metadata_cache(mut self, enabled: bool) -> Self {
+        self.metadata_cache = enabled;
+        self
+    }
+
+    /// Overall deadline of an archive download including retries (default 10 minutes)
+    pub fn download_timeout(mut self, timeout: Duration) -> Self {
+        self.download_timeout = timeout;
+        self
+    }
+
+    /// Abort lookups and downloads once `token` is cancelled (e.g. on Ctrl-C).
+    ///
+    /// The install then fails with [`DriverError::Cancelled`] and removes its partial download.
+    pub fn cancel_token(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Extract only the `chromedriver` executable, to `{out_dir}/{version}/chromedriver`.
+    ///
+    /// Skips LICENSE files and gives every version the same predictable layout.
+    /// Browser artifacts always need their full archive and are unaffected.
+    pub fn flat(mut self, flat: bool) -> Self {
+        self.flat = flat;
+        self
+    }
+
+    /// Re-download even if the selected version is already installed.
+    ///
+    /// The existing install is replaced once the new download is verified.
+    pub fn force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Use a `chromedriver` on `PATH` when it fits, downloading only otherwise.
+    ///
+    /// * It must match the milestone of the pinned version, or of the local Chrome.
+    /// * See [`crate::find_system_driver`].
+    pub fn prefer_system(mut self, prefer: bool) -> Self {
+        self.prefer_system = prefer;
+        self
+    }
+
+    /// How much to print to stderr besides the `tracing` events (default [`Verbosity::Silent`])
+    pub fn verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// Resolve the version and report what would be downloaded where, without downloading
+    /// artifacts or writing to disk.
+    ///
+    /// * Installs return the would-be paths; see [`DriverManager::plan`] for the details.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Honor `CHROMEDRIVER_PATH`, `CHROMEDRIVER_VERSION` and `CHROMEDRIVER_SKIP_DOWNLOAD`
+    /// (default on).
+    ///
+    /// * `CHROMEDRIVER_PATH` uses that binary as is and skips the install entirely.
+    /// * `CHROMEDRIVER_VERSION` replaces the configured version selection.
+    /// * A truthy `CHROMEDRIVER_SKIP_DOWNLOAD` turns on [`offline`](Self::offline).
+    pub fn env_overrides(mut self, enabled: bool) -> Self {
+        self.env_overrides = enabled;
+        self
+    }
+
+    fn primary_mirror(&mut self) -> &mut Mirror {
+        if self.mirrors.is_empty() {
+            self.mirrors.push(Mirror::google());
+        }
+        &mut self.mirrors[0]
+    }
+
+    /// Finish the configuration
+    pub fn build(mut self) -> Result<DriverManager> {
+        if self.mirrors.is_empty() {
+            return Err(DriverError::Config("no mirrors configured".to_string()));
+        }
+        let mut driver_override = None;
+        if self.env_overrides {
+            driver_override = overrides::driver_path();
+            if let Some(version) = overrides::version() {
+                self.selector = VersionSelector::Exact(version);
+            }
+            self.offline |= overrides::skip_download();
+        }
+        let client = match self.client.clone() {
+            Some(client) => client,
+            None => self.build_client()?,
+        };
+        Ok(DriverManager {
+            out_dir: self.out_dir,
+            selector: self.selector,
+            client,
+            mirrors: self.mirrors,
+            offline: self.offline,
+            checksum: self.checksum,
+            progress: self.progress,
+            retry: self.retry,
+            metadata_timeout: self.metadata_timeout,
+            metadata_ttl: self.metadata_ttl,
+            metadata_cache: self.metadata_cache,
+            download_timeout: self.download_timeout,
+            cancel: self.cancel,
+            flat: self.flat,
+            force: self.force,
+            prefer_system: self.prefer_system,
+            driver_override,
+            verbosity: self.verbosity,
+            dry_run: self.dry_run,
+        })
+    }
+
+    /// Build the HTTP client from the proxy settings
+    fn build_client(&self) -> Result<reqwest::Client> {
+        let config = |e: reqwest::Error| DriverError::Config(e.to_string());
+        let env = |names: &[&str]| names.iter().find_map(|name| env::var(name).ok());
+
+        let mut bypass = self.no_proxy.clone();
+        if self.env_proxy {
+            bypass.extend(env(&["NO_PROXY", "no_proxy"]));
+        }
+        let bypass = reqwest::NoProxy::from_string(&bypass.join(","));
+
+        let mut proxies = Vec::new();
+        if let Some(url) = &self.proxy {
+            proxies.push(reqwest::Proxy::all(url).map_err(config)?);
+        } else if self.env_proxy {
+            if let Some(url) = env(&["HTTPS_PROXY", "https_proxy"]) {
+                proxies.push(reqwest::Proxy::https(url).map_err(config)?);
+            }
+            if let Some(url) = env(&["HTTP_PROXY", "http_proxy"]) {
+                proxies.push(reqwest::Proxy::http(url).map_err(config)?);
+            }
+        }
+
+        // Disable reqwest's own env lookup so only the settings above apply
+        let mut builder = reqwest::Client::builder()
+            .no_proxy()
+            .connect_timeout(self.connect_timeout)
+            .read_timeout(self.read_timeout);
+        for proxy in proxies {
+            builder = builder.proxy(proxy.no_proxy(bypass.clone()));
+        }
+        builder.build().map_err(config)
+    }
+}
+
+impl DriverManager {
+    /// Start configuring a new installer
+    pub fn builder() -> DriverManagerBuilder {
+        DriverManagerBuilder::default()
+    }
+
+    /// Install ChromeDriver.
+    ///
+    /// * If the selected version is already installed, the download is skipped.
+    /// * Every version goes under `{out_dir}/{version}/{platform}/`, so versions coexist.
+    /// * `{out_dir}/current` then points at the installed driver, see [`crate::current_driver`].
+    /// * A `CHROMEDRIVER_PATH` override is returned as is, see
+    ///   [`DriverManagerBuilder::env_overrides`].
+    pub async fn install(&self) -> Result<DriverInfo> {
+        self.reporting(async {
+            if let Some(driver) = self.preinstalled_driver().await? {
+                return Ok(driver);
+            }
+            if self.dry_run {
+                let plan = self.plan_artifact(Artifact::ChromeDriver).await?;
+                let source_url = Some(plan.url.clone()).filter(|url| !url.is_empty());
+                return Ok(DriverInfo {
+                    source_url,
+                    ..ArtifactInfo::from(plan).into()
+                });
+            }
+            let driver = if self.offline {
+                DriverInfo::from(self.find_offline(Artifact::ChromeDriver).await?)
+            } else {
+                let version = self.resolve_version().await?;
+                self.install_driver(&version, &self.install_dir(&version)?)
+                    .await?
+            };
+            let driver = driver.with_manifest();
+            cache::set_current(Path::new(&self.out_dir), Path::new(&driver.driver_path))?;
+            Ok(driver)
+        })
+        .await
+    }
+
+    /// Install `artifact` of the selected version.
+    ///
+    /// * [`Artifact::HeadlessShell`] is a much smaller browser for headless workloads.
+    pub async fn install_artifact(&self, artifact: Artifact) -> Result<ArtifactInfo> {
+        self.reporting(async {
+            if artifact == Artifact::ChromeDriver {
+                if let Some(driver) = self.preinstalled_driver().await? {
+                    return Ok(ArtifactInfo {
+                        artifact,
+                        path: driver.driver_path,
+                        version: driver.version,
+                        downloaded: false,
+                    });
+                }
+            }
+            if self.dry_run {
+                return self.plan_artifact(artifact).await.map(ArtifactInfo::from);
+            }
+            let info = if self.offline {
+                self.find_offline(artifact).await?
+            } else {
+                let version = self.resolve_version().await?;
+                self.install_artifact_version(artifact, &version, &self.install_dir(&version)?)
+                    .await?
+            };
+            if artifact == Artifact::ChromeDriver {
+                cache::set_current(Path::new(&self.out_dir), Path::new(&info.path))?;
+            }
+            Ok(info)
+        })
+        .await
+    }
+
+    /// Install the browser and driver of the selected version in one go.
+    ///
+    /// * The version is resolved once, so a Stable roll between both installs
+    ///   can't cause a version skew.
+    /// * Both artifacts are placed side by side.
+    pub async fn install_matched_pair(&self) -> Result<MatchedPair> {
+        self.reporting(async {
+            if self.dry_run {
+                let version = self.resolve_version().await?;
+                let browser = plan::plan(self, Artifact::Chrome, &version).await?;
+                let driver = plan::plan(self, Artifact::ChromeDriver, &version).await?;
+                return Ok(MatchedPair {
+                    driver: ArtifactInfo::from(driver).into(),
+                    browser: ArtifactInfo::from(browser).into(),
+                });
+            }
+            let mut pair = if self.offline {
+                MatchedPair {
+                    driver: self.find_offline(Artifact::ChromeDriver).await?.into(),
+                    browser: self.find_offline(Artifact::Chrome).await?.into(),
+                }
+            } else {
+                let version = self.resolve_version().await?;
+                let dir = self.install_dir(&version)?;
+
+                let browser = self
+                    .install_artifact_version(Artifact::Chrome, &version, &dir)
+                    .await?;
+                let driver = self
+                    .install_artifact_version(Artifact::ChromeDriver, &version, &dir)
+                    .await?;
+                MatchedPair {
+                    driver: driver.into(),
+                    browser: browser.into(),
+                }
+            };
+            pair.driver = pair.driver.with_manifest();
+            cache::set_current(
+                Path::new(&self.out_dir),
+                Path::new(&pair.driver.driver_path),
+            )?;
+            Ok(pair)
+        })
+        .await
+    }
+
+    /// What [`install`](Self::install) would download and where, without doing it
+    pub async fn plan(&self) -> Result<InstallPlan> {
+        self.reporting(self.plan_artifact(Artifact::ChromeDriver))
+            .await
+    }
+
+    /// Resolve the version of `artifact` and plan its install
+    async fn plan_artifact(&self, artifact: Artifact) -> Result<InstallPlan> {
+        let version = if self.offline {
+            self.find_offline(artifact).await?.version
+        } else {
+            self.resolve_version().await?
+        };
+        plan::plan(self, artifact, &version).await
+    }
+
+    /// The exact URL `artifact` at `version` is downloaded from for `platform`, using the
+    /// first configured mirror; see [`crate::resolve_download_url`]
+    pub fn resolve_download_url(
+        &self,
+        version: &str,
+        artifact: Artifact,
+        platform: Platform,
+    ) -> Result<String> {
+        crate::download_url(&self.mirrors[0], version, artifact, platform)
+    }
+
+    /// List every known-good Chrome for Testing version, see [`crate::list_available_versions`]
+    pub async fn available_versions(&self) -> Result<Vec<AvailableVersion>> {
+        self.reporting(async { versions::fetch_available_versions(self).await })
+            .await
+    }
+
+    /// HTTP client used for all requests
+    pub(crate) fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    /// Expected checksum of downloaded archives, if configured
+    pub(crate) fn checksum(&self) -> Option<&Checksum> {
+        self.checksum.as_ref()
+    }
+
+    /// Directory the artifacts are installed into
+    pub(crate) fn out_dir(&self) -> &str {
+        &self.out_dir
+    }
+
+    /// Download sources, first one preferred
+    pub(crate) fn mirrors(&self) -> &[Mirror] {
+        &self.mirrors
+    }
+
+    /// Whether only the driver executable is installed
+    pub(crate) fn flat(&self) -> bool {
+        self.flat
+    }
+
+    /// Whether existing installs are replaced
+    pub(crate) fn force(&self) -> bool {
+        self.force
+    }
+
+    /// Deadline of each metadata request
+    pub(crate) fn metadata_timeout(&self) -> Duration {
+        self.metadata_timeout
+    }
+
+    /// Run `future` printing its events as configured by [`DriverManagerBuilder::verbosity`]
+    async fn reporting<F: Future>(&self, future: F) -> F::Output {
+        future.with_subscriber(self.verbosity.dispatch()).await
+    }
+
+    /// Run `future` unless the cancellation token fires first
+    pub(crate) async fn cancellable<T>(
+        &self,
+        future: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let Some(token) = &self.cancel else {
+            return future.await;
+        };
+        if token.is_cancelled() {
+            return Err(DriverError::Cancelled);
+        }
+        rt::until(future, token.cancelled())
+            .await
+            .unwrap_or(Err(DriverError::Cancelled))
+    }
+
+    /// Run `op` under the configured [`RetryPolicy`]
+    pub(crate) async fn retrying<T, F, Fut>(&self, op: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        self.cancellable(retry::retry(&self.retry, op)).await
+    }
+
+    /// Fetch a JSON endpoint, failing over through the mirror list.
+    ///
+    /// * Responses are cached in `{out_dir}/.metadata/` with their `ETag`/`Last-Modified`,
+    ///   so unchanged endpoints answer `304 Not Modified` without a body.
+    /// * Within the [`metadata_ttl`](DriverManagerBuilder::metadata_ttl) the cached copy is
+    ///   used without a request.
+    /// * When every mirror fails, the cached copy is used.
+    pub(crate) async fn fetch_metadata(&self, file: &str) -> Result<Value> {
+        let cached = if self.metadata_cache {
+            metadata::load(&self.out_dir, file).await
+        } else {
+            None
+        };
+        let mut last_error = None;
+        for mirror in &self.mirrors {
+            let url = mirror.metadata_url(file);
+            let validators = cached.as_ref().filter(|cached| cached.url == url);
+            if let Some(cached) = validators.filter(|cached| cached.age() < self.metadata_ttl) {
+                debug!("🔁 Using {url} from the cache");
+                return Ok(cached.body.clone());
+            }
+            let fetched = self
+                .retrying(|| {
+                    http::get_if_modified(
+                        &self.client,
+                        &url,
+                        self.metadata_timeout,
+                        validators.and_then(|cached| cached.etag.as_deref()),
+                        validators.and_then(|cached| cached.last_modified.as_deref()),
+                    )
+                })
+                .await;
+            let error = match fetched {
+                Ok(None) => match validators {
+                    Some(cached) => {
+                        debug!("🔁 {url} not modified");
+                        if self.metadata_cache && !self.dry_run {
+                            metadata::store(&self.out_dir, file, cached.refreshed()).await;
+                        }
+                        return Ok(cached.body.clone());
+                    }
+                    None => DriverError::VersionLookup(format!("unexpected 304 from {url}")),
+                },
+                Ok(Some(modified)) => match serde_json::from_str::<Value>(&modified.body) {
+                    Ok(body) => {
+                        if self.metadata_cache && !self.dry_run {
+                            let fresh = CachedMetadata {
+                                url,
+                                etag: modified.etag,
+                                last_modified: modified.last_modified,
+                                fetched_at: metadata::now(),
+                                body: body.clone(),
+                            };
+                            metadata::store(&self.out_dir, file, fresh).await;
+                        }
+                        return Ok(body);
+                    }
+                    Err(e) => DriverError::VersionLookup(format!("invalid JSON from {url}: {e}")),
+                },
+                Err(DriverError::Cancelled) => return Err(DriverError::Cancelled),
+                Err(e) => e,
+            };
+            warn!("⚠️ {error}");
+            last_error = Some(error);
+        }
+        if let (Some(cached), Some(_)) = (cached, &last_error) {
+            warn!("⚠️ Metadata endpoints unreachable, using the cached {file}");
+            return Ok(cached.body);
+        }
+        Err(last_error.unwrap_or_else(|| DriverError::Config("no mirrors configured".into())))
+    }
+
+    /// Resolve the concrete version this manager installs
+    pub async fn resolve_version(&self) -> Result<String> {
+        self.reporting(async {
+            match &self.selector {
+                VersionSelector::Latest(channel) => {
+                    let version = self.fetch_latest_version(*channel).await?;
+                    info!("🌐 Latest {} version: {version}", channel.as_str());
+                    Ok(version)
+                }
+                VersionSelector::Exact(version) => {
+                    if version.is_empty()
+                        || !version.chars().all(|c| c.is_ascii_digit() || c == '.')
+                    {
+                        return Err(DriverError::InvalidVersion(version.clone()));
+                    }
+                    Ok(version.clone())
+                }
+                VersionSelector::Milestone(milestone) => {
+                    let version = self.fetch_milestone_version(*milestone).await?;
+                    info!("🌐 Latest version for milestone {milestone}: {version}");
+                    Ok(version)
+                }
+                VersionSelector::InstalledChrome => self.fetch_installed_chrome_match().await,
+            }
+        })
+        .await
+    }
+
+    /// A driver that makes installing unnecessary: the `CHROMEDRIVER_PATH` override,
+    /// or with [`DriverManagerBuilder::prefer_system`] a fitting one on `PATH`
+    async fn preinstalled_driver(&self) -> Result<Option<DriverInfo>> {
+        if let Some(path) = &self.driver_override {
+            return self.use_override(path).await.map(Some);
+        }
+        if !self.prefer_system {
+            return Ok(None);
+        }
+        let Some(driver) = find_system_driver().await else {
+            return Ok(None);
+        };
+
+        // The milestone the driver has to match, if any can be told without the network
+        let milestone = |version: &str| version.split('.').next().unwrap_or_default().to_string();
+        let wanted = match &self.selector {
+            VersionSelector::Exact(version) => Some(milestone(version)),
+            VersionSelector::Milestone(major) => Some(major.to_string()),
+            VersionSelector::Latest(_) | VersionSelector::InstalledChrome => {
+                detect_chrome_version()
+                    .await
+                    .ok()
+                    .map(|chrome| milestone(&chrome.version))
+            }
+        };
+        match wanted {
+            Some(wanted) if wanted != milestone(&driver.version) => {
+                warn!(
+                    "⚠️ System chromedriver {} doesn't match milestone {wanted}, installing",
+                    driver.version
+                );
+                Ok(None)
+            }
+            _ => {
+                info!("✅ Using system chromedriver: {}", driver.driver_path);
+                Ok(Some(driver))
+            }
+        }
+    }
+
+    /// Use the driver named by `CHROMEDRIVER_PATH` instead of installing one
+    async fn use_override(&self, path: &Path) -> Result<DriverInfo> {
+        if !path.is_file() {
+            return Err(DriverError::Config(format!(
+                "{} points at {}, which does not exist",
+                overrides::CHROMEDRIVER_PATH_ENV,
+                path.display()
+            )));
+        }
+        let version = read_binary_version(path)
+            .await
+            .unwrap_or_else(|| "unknown".to_string());
+        info!(
+            "✅ Using {} from {}",
+            path.display(),
+            overrides::CHROMEDRIVER_PATH_ENV
+        );
+        Ok(DriverInfo::new(
+            path.to_string_lossy().into_owned(),
+            version,
+        ))
+    }
+
+    /// Directory a resolved `version` is installed into: `{out_dir}/{version}/{platform}`
+    pub(crate) fn install_dir(&self, version: &str) -> Result<String> {
+        Ok(format!("{}/{version}/{}", self.out_dir, platform_dir()?))
+    }
+
+    /// Find an installed `artifact` matching the selection without network access
+    async fn find_offline(&self, artifact: Artifact) -> Result<ArtifactInfo> {
+        // Path below `{out_dir}/{version}/`
+        let relative = if artifact == Artifact::ChromeDriver && self.flat {
+            Artifact::ChromeDriver
+                .executable(cft_platform()?)
+                .to_string()
+        } else if artifact == Artifact::ChromeDriver
+            && env::consts::OS == "linux"
+            && env::consts::ARCH == "aarch64"
+        {
+            "linux-arm64/chromedriver-linux-arm64/chromedriver".to_string()
+        } else {
+            let platform = cft_platform()?;
+            format!(
+                "{platform}/{}-{platform}/{}",
+                artifact.as_str(),
+                artifact.executable(platform)
+            )
+        };
+        let out_dir = Path::new(&self.out_dir);
+
+        let found = match &self.selector {
+            VersionSelector::Latest(_) => newest_installed(out_dir, &relative, ""),
+            VersionSelector::Exact(version) => {
+                let path = out_dir.join(version).join(&relative);
+                path.exists().then(|| (version.clone(), path))
+            }
+            VersionSelector::Milestone(milestone) => {
+                newest_installed(out_dir, &relative, &format!("{milestone}."))
+            }
+            VersionSelector::InstalledChrome => {
+                let chrome = detect_chrome_version().await?;
+                let milestone = chrome.version.split('.').next().unwrap_or_default();
+                newest_installed(out_dir, &relative, &format!("{milestone}."))
+            }
+        };
+
+        let (version, path) = found.ok_or_else(|| {
+            DriverError::Offline(format!(
+                "no {} matching {:?} installed in {}",
+                artifact.as_str(),
+                self.selector,
+                self.out_dir
+            ))
+        })?;
+        info!("✅ Already installed: {}", path.display());
+        Ok(ArtifactInfo {
+            artifact,
+            path: path.to_string_lossy().into_owned(),
+            version,
+            downloaded: false,
+        })
+    }
+
+    /// Read the latest version of `channel` from the last-known-good endpoint
+    async fn fetch_latest_version(&self, channel: Channel) -> Result<String> {
+        let json = self.fetch_metadata("last-known-good-versions.json").await?;
+        json["channels"][channel.as_str()]["version"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| {
+                DriverError::VersionLookup(format!("no {} version published", channel.as_str()))
+            })
+    }
+
+    /// Read the latest version of `milestone` from the per-milestone endpoint
+    async fn fetch_milestone_version(&self, milestone: u32) -> Result<String> {
+        if milestone < legacy::FIRST_CFT_MILESTONE {
+            return legacy::fetch_legacy_version(self, &milestone.to_string()).await;
+        }
+        let json = self
+            .fetch_metadata("latest-versions-per-milestone.json")
+            .await?;
+        json["milestones"][milestone.to_string()]["version"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| {
+                DriverError::VersionLookup(format!("no ChromeDriver for milestone {milestone}"))
+            })
+    }
+
+    /// Resolve the driver version matching the locally installed Chrome.
+    ///
+    /// * Picks the newest driver of the same `MAJOR.MINOR.BUILD`.
+    /// * Falls back to the newest driver of the same milestone.
+    /// * Chrome older than 115 is matched against the legacy download bucket.
+    async fn fetch_installed_chrome_match(&self) -> Result<String> {
+        // 1️⃣ Detect local Chrome
+        let chrome = detect_chrome_version().await?;
+        let chrome_version = chrome.version;
+        debug!("🔍 Installed Chrome {chrome_version} at: {}", chrome.path);
+
+        // 2️⃣ Resolve the nearest driver build
+        let milestone = chrome_version.split('.').next().unwrap_or_default();
+        let build = chrome_version
+            .rsplit_once('.')
+            .map(|(build, _)| build)
+            .unwrap_or(&chrome_version);
+
+        let version = if legacy::is_legacy(&chrome_version) {
+            match legacy::fetch_legacy_version(self, build).await {
+                Ok(version) => version,
+                Err(_) => legacy::fetch_legacy_version(self, milestone).await?,
+            }
+        } else {
+            let json = self
+                .fetch_metadata("latest-patch-versions-per-build.json")
+                .await?;
+            match json["builds"][build]["version"].as_str() {
+                Some(version) => version.to_string(),
+                None => {
+                    let milestone = milestone
+                        .parse()
+                        .map_err(|_| DriverError::InvalidVersion(chrome_version.clone()))?;
+                    self.fetch_milestone_version(milestone).await?
+                }
+            }
+        };
+        info!("🎯 Matching ChromeDriver version: {version}");
+        Ok(version)
+    }
+
+    /// Install ChromeDriver `version` into `out_dir`, skipping the download if present.
+    async fn install_driver(&self, version: &str, out_dir: &str) -> Result<DriverInfo> {
+        if self.flat {
+            return self.install_flat_driver(version).await;
+        }
+        self.install_artifact_version(Artifact::ChromeDriver, version, out_dir)
+            .await
+            .map(DriverInfo::from)
+    }
+
+    /// Install `artifact` at `version` into `out_dir`, skipping the download if present.
+    ///
+    /// * ChromeDriver older than 115 comes from the legacy bucket, for every entry point.
+    async fn install_artifact_version(
+        &self,
+        artifact: Artifact,
+        version: &str,
+        out_dir: &str,
+    ) -> Result<ArtifactInfo> {
+        // 1️⃣ Detect platform
+        if artifact == Artifact::ChromeDriver && self.flat {
+            let info = self.install_flat_driver(version).await?;
+            return Ok(ArtifactInfo {
+                artifact,
+                path: info.driver_path,
+                version: info.version,
+                downloaded: info.downloaded,
+            });
+        }
+        if artifact == Artifact::ChromeDriver && legacy::is_legacy(version) {
+            let info = legacy::install_legacy_driver(self, version, out_dir).await?;
+            return Ok(ArtifactInfo {
+                artifact,
+                path: info.driver_path,
+                version: info.version,
+                downloaded: info.downloaded,
+            });
+        }
+        if artifact == Artifact::ChromeDriver
+            && env::consts::OS == "linux"
+            && env::consts::ARCH == "aarch64"
+        {
+            let info = linux_arm64::ensure_linux_arm64_driver(self, out_dir, version).await?;
+            return Ok(ArtifactInfo {
+                artifact,
+                path: info.driver_path,
+                version: info.version,
+                downloaded: info.downloaded,
+            });
+        }
+        let platform = cft_platform()?;
+        let zip_name = format!("{}-{platform}", artifact.as_str());
+
+        // 2️⃣ Check if already installed, waiting for concurrent installers first
+        let _lock = lock::lock(Path::new(out_dir), &zip_name).await?;
+        let install_dir = Path::new(out_dir).join(&zip_name);
+        let path = format!("{}/{}/{}", out_dir, zip_name, artifact.executable(platform));
+        if !self.force && manifest::is_installed(&install_dir, Path::new(&path)).await {
+            info!("✅ Already installed: {path}");
+            return Ok(ArtifactInfo {
+                artifact,
+                path,
+                version: version.to_string(),
+                downloaded: false,
+            });
+        }
+
+        // 3️⃣ Build download URL
+        let urls: Vec<String> = self
+            .mirrors
+            .iter()
+            .map(|mirror| mirror.download_url(artifact, version, platform))
+            .collect();
+
+        // 4️⃣ Download, extract, verify and move into place
+        let verify = (artifact == Artifact::ChromeDriver).then_some(version);
+        let source = self
+            .install_archive(
+                &urls,
+                &install_dir,
+                true,
+                artifact.executable(platform),
+                verify,
+            )
+            .await?;
+        manifest::record(&install_dir, version, platform, &source, None).await?;
+
+        info!("🚀 {} ready at: {}", artifact.as_str(), path);
+
+        Ok(ArtifactInfo {
+            artifact,
+            path,
+            version: version.to_string(),
+            downloaded: true,
+        })
+    }
+
+    /// Install only the driver executable as `{out_dir}/{version}/chromedriver`
+    async fn install_flat_driver(&self, version: &str) -> Result<DriverInfo> {
+        // 1️⃣ Detect platform and source
+        let dir = Path::new(&self.out_dir).join(version);
+        if env::consts::OS == "linux" && env::consts::ARCH == "aarch64" {
+            let dir = dir.to_string_lossy();
+            return linux_arm64::ensure_linux_arm64_driver(self, &dir, version).await;
+        }
+        let (urls, executable, platform) = if legacy::is_legacy(version) {
+            let (url, executable) = legacy::download_url(version)?;
+            (vec![url], executable, legacy::legacy_platform(version)?)
+        } else {
+            let platform = cft_platform()?;
+            let urls = self
+                .mirrors
+                .iter()
+                .map(|mirror| mirror.download_url(Artifact::ChromeDriver, version, platform))
+                .collect();
+            (urls, Artifact::ChromeDriver.executable(platform), platform)
+        };
+
+        // 2️⃣ Check if already installed, waiting for concurrent installers first
+        let _lock = lock::lock(&dir, executable).await?;
+        let path = dir.join(executable);
+        let downloaded = self.force || !manifest::is_installed(&dir, &path).await;
+        if downloaded {
+            // 3️⃣ Download, pick the executable out of the zip and check it
+            let (archive, origin) = self.download_archive(&urls, &dir).await?;
+            let staging = dir.join(format!(".{executable}.staging"));
+            let (source, target) = (archive.clone(), staging.clone());
+            let span = info_span!("extract", dest = %staging.display());
+            let extracted = rt::spawn_blocking(move || {
+                span.in_scope(|| archive::extract_file(&source, executable, &target))
+            })
+            .await;
+            let _ = fs::remove_file(&archive);
+            extracted?;
+
+            let checked = match set_executable(&staging) {
+                Ok(()) => verify_driver(&staging, version).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = checked {
+                let _ = fs::remove_file(&staging);
+                return Err(e);
+            }
+
+            // 4️⃣ Move into place
+            if path.exists() {
+                fs::remove_file(&path)
+                    .map_err(|e| DriverError::io(format!("removing {}", path.display()), e))?;
+            }
+            fs::rename(&staging, &path)
+                .map_err(|e| DriverError::io(format!("renaming {}", staging.display()), e))?;
+            manifest::record(&dir, version, platform, &origin, Some(executable)).await?;
+            info!("🚀 chromedriver ready at: {}", path.display());
+        } else {
+            info!("✅ Already installed: {}", path.display());
+        }
+
+        Ok(DriverInfo {
+            downloaded,
+            ..DriverInfo::new(path.to_string_lossy().into_owned(), version)
+        })
+    }
+
+    /// Install the zip at `urls` as `install_dir`, all or nothing, returning where it came from.
+    ///
+    /// * Extracts into a hidden staging sibling and renames it into place only once
+    ///   `executable` is marked executable and, if `verify` is set, reports that version.
+    /// * A crash mid-way leaves just the staging directory, never a half-filled `install_dir`;
+    ///   errors remove the staging directory again.
+    /// * `nested` archives hold a top-level folder named like `install_dir`
+    ///   (Chrome for Testing); others keep their files at the root.
+    pub(crate) async fn install_archive(
+        &self,
+        urls: &[String],
+        install_dir: &Path,
+        nested: bool,
+        executable: &str,
+        verify: Option<&str>,
+    ) -> Result<Source> {
+        let name = install_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let staging = install_dir.with_file_name(format!(".{name}.staging"));
+        let io_error = |action: &str, path: &Path, e| {
+            DriverError::io(format!("{action} {}", path.display()), e)
+        };
+
+        // 1️⃣ Extract into a fresh staging directory, removed again however this returns
+        if staging.exists() {
+            fs::remove_dir_all(&staging).map_err(|e| io_error("removing", &staging, e))?;
+        }
+        let staging = Staging(staging);
+        let source = self.download_and_extract(urls, &staging.0).await?;
+        let extracted = if nested {
+            staging.0.join(&name)
+        } else {
+            staging.0.clone()
+        };
+
+        // 2️⃣ Check the executable before anyone can see it
+        set_executable(&extracted.join(executable))?;
+        if let Some(version) = verify {
+            verify_driver(&extracted.join(executable), version).await?;
+        }
+
+        // 3️⃣ Replace any broken leftover and move into place
+        if install_dir.exists() {
+            fs::remove_dir_all(install_dir).map_err(|e| io_error("removing", install_dir, e))?;
+        }
+        fs::rename(&extracted, install_dir).map_err(|e| io_error("renaming", &extracted, e))?;
+        Ok(source)
+    }
+
+    /// Download the zip from the first working of `urls` and extract it into `dest`.
+    ///
+    /// The body is streamed to a temporary file next to `dest`, so large archives
+    /// are never held in memory and survive a wiped `dest` for resumption.
+    pub(crate) async fn download_and_extract(
+        &self,
+        urls: &[String],
+        dest: &Path,
+    ) -> Result<Source> {
+        let (archive, source) = self
+            .download_archive(urls, dest.parent().unwrap_or(dest))
+            .await?;
+
+        if let Some(progress) = &self.progress {
+            progress.extracting(false);
+        }
+
+        // ZipArchive is blocking → use spawn_blocking
+        let dest = dest.to_owned();
+        let span = info_span!("extract", dest = %dest.display());
+        let extracted = rt::spawn_blocking(move || {
+            let _entered = span.enter();
+            let result = fs::File::open(&archive)
+                .map_err(|e| DriverError::io(format!("opening {}", archive.display()), e))
+                .and_then(|file| {
+                    ZipArchive::new(file).map_err(|source| DriverError::Extraction {
+                        path: dest.clone(),
+                        source,
+                    })
+                })
+                .and_then(|zip| extract(zip, &dest));
+            let _ = fs::remove_file(&archive);
+            result
+        })
+        .await;
+
+        if let (Some(progress), Ok(())) = (&self.progress, &extracted) {
+            progress.extracting(true);
+        }
+        extracted.map(|()| source)
+    }
+
+    /// Download the zip from the first working of `urls` into a temporary file in `part_dir`.
+    ///
+    /// The caller removes the returned file once it is done with it.
+    async fn download_archive(
+        &self,
+        urls: &[String],
+        part_dir: &Path,
+    ) -> Result<(PathBuf, Source)> {
+        fs::create_dir_all(part_dir)
+            .map_err(|e| DriverError::io(format!("creating {}", part_dir.display()), e))?;
+
+        let mut last_error = None;
+        let mut archive = None;
+        for url in urls {
+            info!("⬇️ Downloading from: {url}");
+            let part = part_dir.join(partial_file_name(url));
+            let download = async {
+                let download = self.retrying(|| {
+                    http::download_to_file(&self.client, url, &part, self.progress.clone())
+                });
+                let digest = rt::timeout(self.download_timeout, download)
+                    .await
+                    .ok_or_else(|| DriverError::Timeout {
+                        operation: format!("download of {url}"),
+                        after: self.download_timeout,
+                    })??;
+                if let Some(expected) = checksum::expected(self, url).await? {
+                    checksum::verify(url, &digest, &expected)?;
+                }
+                Ok(digest)
+            }
+            .instrument(info_span!("download", %url));
+            match download.await {
+                Ok(sha256) => {
+                    let source = Source {
+                        url: url.clone(),
+                        sha256,
+                    };
+                    archive = Some((part, source));
+                    break;
+                }
+                Err(e) => {
+                    // Keep interrupted downloads around so a retry can resume them
+                    if !matches!(e, DriverError::Network { .. } | DriverError::Timeout { .. }) {
+                        let _ = fs::remove_file(&part);
+                    }
+                    if matches!(e, DriverError::Cancelled) {
+                        warn!("🛑 Download cancelled");
+                        return Err(e);
+                    }
+                    warn!("⚠️ {e}");
+                    last_error = Some(e);
+                }
+            }
+        }
+        match (archive, last_error) {
+            (Some(archive), _) => Ok(archive),
+            (None, Some(e)) => Err(e),
+            (None, None) => Err(DriverError::Config("no download URLs".to_string())),
+        }
+    }
+}
+
+/// Staging directory of [`DriverManager::install_archive`], deleted on drop
+struct Staging(PathBuf);
+
+impl Drop for Staging {
+    fn drop(&mut self) {
+        // Already gone once a flat archive was renamed into place
+        if self.0.exists() {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+}
+
+/// Name of the temporary download file of `url`, unique per URL so that only
+/// the same version from the same source is ever resumed
+fn partial_file_name(url: &str) -> String {
+    let file_name = url.rsplit('/').next().unwrap_or("download.zip");
+    let tag = http::hex(&Sha256::digest(url.as_bytes())[..6]);
+    format!(".{file_name}.{tag}.part")
+}
+
+/// Install ChromeDriver from a local zip, see [`crate::install_from_zip`]
+pub(crate) async fn install_driver_zip(zip_path: &Path, out_dir: &Path) -> Result<DriverInfo> {
+    // 1️⃣ Find the driver inside the zip and extract it
+    let (zip_path, dest) = (zip_path.to_owned(), out_dir.to_owned());
+    let path = rt::spawn_blocking(move || -> Result<PathBuf> {
+        let file = fs::File::open(&zip_path)
+            .map_err(|e| DriverError::io(format!("opening {}", zip_path.display()), e))?;
+        let archive = ZipArchive::new(file).map_err(|source| DriverError::Extraction {
+            path: dest.clone(),
+            source,
+        })?;
+        let entry = archive
+            .file_names()
+            .find(|name| {
+                let file_name = Path::new(name).file_name().unwrap_or_default();
+                file_name == "chromedriver" || file_name == "chromedriver.exe"
+            })
+            .map(str::to_string)
+            .ok_or_else(|| {
+                DriverError::io(
+                    format!("looking for chromedriver in {}", zip_path.display()),
+                    std::io::ErrorKind::NotFound.into(),
+                )
+            })?;
+        extract(archive, &dest)?;
+        Ok(dest.join(entry))
+    })
+    .await?;
+
+    // 2️⃣ Set execute permissions (Unix only)
+    set_executable(&path)?;
+
+    // 3️⃣ Ask the driver for its version
+    let version = read_binary_version(&path)
+        .await
+        .unwrap_or_else(|| "unknown".to_string());
+    info!("🚀 chromedriver ready at: {}", path.display());
+
+    Ok(DriverInfo::new(
+        path.to_string_lossy().into_owned(),
+        version,
+    ))
+}
+
+/// Newest `{out_dir}/{version}/{relative}` whose version starts with `prefix`
+fn newest_installed(out_dir: &Path, relative: &str, prefix: &str) -> Option<(String, PathBuf)> {
+    fs::read_dir(out_dir)
+        .ok()?
+        .filter_map(|entry| parse_version(&entry.ok()?.file_name().to_string_lossy()))
+        .filter(|version| version.starts_with(prefix))
+        .map(|version| {
+            let path = out_dir.join(&version).join(relative);
+            (version, path)
+        })
+        .filter(|(_, path)| path.exists())
+        .max_by_key(|(version, _)| version_key(version))
+}
+
+/// Run the freshly extracted driver and confirm it reports `expected`.
+///
+/// Catches truncated downloads and partially extracted archives.
+pub(crate) async fn verify_driver(path: &Path, expected: &str) -> Result<()> {
+    let found = read_binary_version(path).await;
+    if found.as_deref() == Some(expected) {
+        debug!("✅ Verified chromedriver {expected}");
+        return Ok(());
+    }
+    Err(DriverError::VersionMismatch {
+        path: path.to_owned(),
+        expected: expected.to_string(),
+        found,
+    })
+}
+
+/// Ask an installed binary for its version
+pub(crate) async fn read_binary_version(path: &Path) -> Option<String> {
+    if !path.exists() {
+        return None;
+    }
+    let output = rt::command_output(path, &["--version"]).await.ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .find_map(parse_version)
+}
+
+/// Set execute permissions (Unix only)
+pub(crate) fn set_executable(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+        .map_err(|e| DriverError::io(format!("setting permissions on {}", path.display()), e))?;
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+    use zip::{write::SimpleFileOptions, ZipWriter};
+
+    use super::*;
+    use crate::{RetryPolicy, Verbosity};
+
+    /// URL of a one-shot HTTP server answering with `body`
+    async fn serve(body: Vec<u8>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!(
+            "http://{}/chromedriver-linux64.zip",
+            listener.local_addr().unwrap()
+        );
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let read = stream.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            let head = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            stream.write_all(head.as_bytes()).await.unwrap();
+            stream.write_all(&body).await.unwrap();
+        });
+        url
+    }
+
+    fn manager(out_dir: &Path) -> DriverManager {
+        DriverManager::builder()
+            .out_dir(out_dir.to_string_lossy())
+            .env_overrides(false)
+            .retry(RetryPolicy::none())
+            .verbosity(Verbosity::Silent)
+            .build()
+            .unwrap()
+    }
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "chrome-driver-rs-manager-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Entries of `dir`, e.g. to spot a leftover staging directory
+    fn entries(dir: &Path) -> Vec<String> {
+        let mut names: Vec<_> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[tokio::test]
+    async fn removes_staging_when_the_executable_is_missing() {
+        let dir = scratch("missing-executable");
+        let mut writer = ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        writer
+            .start_file("LICENSE", SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"license").unwrap();
+        let url = serve(writer.finish().unwrap().into_inner()).await;
+
+        let install_dir = dir.join("chromedriver-linux64");
+        let result = manager(&dir)
+            .install_archive(&[url], &install_dir, false, "chromedriver", None)
+            .await;
+        assert!(result.is_err());
+        assert_eq!(entries(&dir), Vec::<String>::new());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn removes_staging_when_extraction_fails() {
+        let dir = scratch("corrupt");
+        let url = serve(b"not a zip".to_vec()).await;
+
+        let install_dir = dir.join("chromedriver-linux64");
+        let result = manager(&dir)
+            .install_archive(&[url], &install_dir, true, "chromedriver", None)
+            .await;
+        assert!(matches!(result, Err(DriverError::Extraction { .. })));
+        assert_eq!(entries(&dir), Vec::<String>::new());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
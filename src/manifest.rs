@@ -0,0 +1,283 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::warn;
+
+use crate::{
+    error::{DriverError, Result},
+    http, rt,
+};
+
+/// File name of the manifest inside every install directory
+pub(crate) const MANIFEST_FILE: &str = "manifest.json";
+
+/// Where an installed archive was downloaded from
+#[derive(Debug, Clone)]
+pub(crate) struct Source {
+    pub(crate) url: String,
+    pub(crate) sha256: String,
+}
+
+/// Provenance of an install, stored as `manifest.json` in its directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstallManifest {
+    /// Installed version
+    pub version: String,
+    /// Platform the archive was built for, e.g. `linux64`
+    pub platform: String,
+    /// URL the archive was downloaded from
+    pub url: String,
+    /// Hex SHA-256 of the downloaded archive
+    pub sha256: String,
+    /// Install time in seconds since the Unix epoch
+    pub installed_at: u64,
+    /// Every installed file
+    pub files: Vec<ManifestFile>,
+}
+
+/// A file recorded in an [`InstallManifest`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestFile {
+    /// Path relative to the install directory, `/`-separated
+    pub path: String,
+    /// Size in bytes
+    pub size: u64,
+    /// Hex SHA-256 of the contents; `None` in manifests of older versions of this crate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+impl InstallManifest {
+    /// Read the manifest of the install in `install_dir`, if it has one
+    pub fn load(install_dir: impl AsRef<Path>) -> Result<Option<Self>> {
+        let path = install_dir.as_ref().join(MANIFEST_FILE);
+        let json = match fs::read_to_string(&path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(DriverError::io(format!("reading {}", path.display()), e)),
+        };
+        serde_json::from_str(&json).map(Some).map_err(|e| {
+            DriverError::io(
+                format!("parsing {}", path.display()),
+                io::Error::new(io::ErrorKind::InvalidData, e),
+            )
+        })
+    }
+
+    /// Whether every recorded file is still present with its recorded size.
+    ///
+    /// * Only reads metadata, so it is cheap enough for every cache hit; see
+    ///   [`verify`](Self::verify) to check contents.
+    pub fn is_intact(&self, install_dir: impl AsRef<Path>) -> bool {
+        let install_dir = install_dir.as_ref();
+        self.files.iter().all(|file| {
+            fs::metadata(install_dir.join(&file.path))
+                .is_ok_and(|metadata| metadata.is_file() && metadata.len() == file.size)
+        })
+    }
+
+    /// Whether every recorded file is still present with its recorded size and SHA-256.
+    ///
+    /// * Reads every file, so it takes a moment for a browser install.
+    pub fn verify(&self, install_dir: impl AsRef<Path>) -> bool {
+        let install_dir = install_dir.as_ref();
+        self.is_intact(install_dir)
+            && self.files.iter().all(|file| {
+                file.sha256.as_ref().is_none_or(|expected| {
+                    sha256(&install_dir.join(&file.path)).is_ok_and(|actual| actual == *expected)
+                })
+            })
+    }
+}
+
+/// Hex SHA-256 of the file at `path`
+fn sha256(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+    Ok(http::hex(&hasher.finalize()))
+}
+
+/// Write `manifest.json` for the freshly installed files of `install_dir`.
+///
+/// `only` limits the file list to a single file, for directories shared with other installs.
+pub(crate) async fn record(
+    install_dir: &Path,
+    version: &str,
+    platform: &str,
+    source: &Source,
+    only: Option<&str>,
+) -> Result<()> {
+    let (install_dir, version, platform) = (
+        install_dir.to_owned(),
+        version.to_string(),
+        platform.to_string(),
+    );
+    let (source, only) = (source.clone(), only.map(str::to_string));
+    rt::spawn_blocking(move || write(&install_dir, &version, &platform, &source, only.as_deref()))
+        .await
+}
+
+fn write(
+    install_dir: &Path,
+    version: &str,
+    platform: &str,
+    source: &Source,
+    only: Option<&str>,
+) -> Result<()> {
+    let mut files = Vec::new();
+    let listed = match only {
+        Some(name) => {
+            manifest_file(&install_dir.join(name), name.to_string()).map(|file| files.push(file))
+        }
+        None => list_files(install_dir, "", &mut files),
+    };
+    listed.map_err(|e| DriverError::io(format!("listing {}", install_dir.display()), e))?;
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let manifest = InstallManifest {
+        version: version.to_string(),
+        platform: platform.to_string(),
+        url: source.url.clone(),
+        sha256: source.sha256.clone(),
+        installed_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default(),
+        files,
+    };
+    let path = install_dir.join(MANIFEST_FILE);
+    let json = serde_json::to_string_pretty(&manifest).expect("manifest is serializable");
+    fs::write(&path, json).map_err(|e| DriverError::io(format!("writing {}", path.display()), e))
+}
+
+/// Whether the install in `install_dir` can be reused without downloading again.
+///
+/// * Installs with a manifest must still hold every recorded file at its recorded size,
+///   see [`InstallManifest::is_intact`]; contents aren't hashed on this path.
+/// * Installs without one (older versions of this crate) only need their `executable`.
+pub(crate) async fn is_installed(install_dir: &Path, executable: &Path) -> bool {
+    let (install_dir, executable): (PathBuf, PathBuf) =
+        (install_dir.to_owned(), executable.to_owned());
+    rt::spawn_blocking(move || match InstallManifest::load(&install_dir) {
+        Ok(Some(manifest)) if manifest.is_intact(&install_dir) => true,
+        Ok(Some(_)) | Err(_) => {
+            warn!("⚠️ {} is damaged, reinstalling", install_dir.display());
+            false
+        }
+        Ok(None) => executable.exists(),
+    })
+    .await
+}
+
+/// Size and digest of the file at `path`, recorded as `name`
+fn manifest_file(path: &Path, name: String) -> io::Result<ManifestFile> {
+    Ok(ManifestFile {
+        path: name,
+        size: fs::metadata(path)?.len(),
+        sha256: Some(sha256(path)?),
+    })
+}
+
+/// Regular files below `dir`, skipping hidden entries such as locks and staging directories
+fn list_files(dir: &Path, prefix: &str, files: &mut Vec<ManifestFile>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') || (prefix.is_empty() && name == MANIFEST_FILE) {
+            continue;
+        }
+        let path = format!("{prefix}{name}");
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            list_files(&entry.path(), &format!("{path}/"), files)?;
+        } else if file_type.is_file() {
+            files.push(manifest_file(&entry.path(), path)?);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An install directory holding `chromedriver-linux64/chromedriver` and its manifest
+    fn install(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "chrome-driver-rs-manifest-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("chromedriver-linux64")).unwrap();
+        fs::write(dir.join("chromedriver-linux64/chromedriver"), "binary").unwrap();
+        let source = Source {
+            url: "https://example.com/chromedriver-linux64.zip".into(),
+            sha256: "00".into(),
+        };
+        write(&dir, "130.0.6723.58", "linux64", &source, None).unwrap();
+        dir
+    }
+
+    #[test]
+    fn records_size_and_digest_of_every_file() {
+        let dir = install("record");
+        let manifest = InstallManifest::load(&dir).unwrap().unwrap();
+        assert_eq!(
+            manifest.files,
+            [ManifestFile {
+                path: "chromedriver-linux64/chromedriver".into(),
+                size: 6,
+                sha256: Some(
+                    "9a3a45d01531a20e89ac6ae10b0b0beb0492acd7216a368aa062d1a5fecaf9cd".into()
+                ),
+            }]
+        );
+        assert!(manifest.is_intact(&dir));
+        assert!(manifest.verify(&dir));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn verify_detects_files_changed_in_place() {
+        let dir = install("changed");
+        // Same size, different contents
+        fs::write(dir.join("chromedriver-linux64/chromedriver"), "BINARY").unwrap();
+        let manifest = InstallManifest::load(&dir).unwrap().unwrap();
+        assert!(manifest.is_intact(&dir), "is_intact only compares sizes");
+        assert!(!manifest.verify(&dir));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn detects_resized_files() {
+        let dir = install("resized");
+        fs::write(dir.join("chromedriver-linux64/chromedriver"), "bin").unwrap();
+        let manifest = InstallManifest::load(&dir).unwrap().unwrap();
+        assert!(!manifest.is_intact(&dir));
+        assert!(!manifest.verify(&dir));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn detects_missing_files() {
+        let dir = install("missing");
+        fs::remove_file(dir.join("chromedriver-linux64/chromedriver")).unwrap();
+        let manifest = InstallManifest::load(&dir).unwrap().unwrap();
+        assert!(!manifest.is_intact(&dir));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn verifies_only_sizes_of_manifests_without_digests() {
+        let dir = install("legacy");
+        let mut manifest = InstallManifest::load(&dir).unwrap().unwrap();
+        manifest.files[0].sha256 = None;
+        fs::write(dir.join("chromedriver-linux64/chromedriver"), "BINARY").unwrap();
+        assert!(manifest.verify(&dir));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
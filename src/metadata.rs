@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    fs,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::debug;
+
+use crate::rt;
+
+/// Hidden directory of `out_dir` holding the cached metadata responses
+const METADATA_DIR: &str = ".metadata";
+
+/// A metadata endpoint response kept for conditional requests and outages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CachedMetadata {
+    /// URL the response came from
+    pub(crate) url: String,
+    /// `ETag` header of the response
+    pub(crate) etag: Option<String>,
+    /// `Last-Modified` header of the response
+    pub(crate) last_modified: Option<String>,
+    /// When the response was last fetched or revalidated, in seconds since the Unix epoch
+    #[serde(default)]
+    pub(crate) fetched_at: u64,
+    /// The parsed body
+    pub(crate) body: Value,
+}
+
+impl CachedMetadata {
+    /// Time since the response was last fetched or revalidated
+    pub(crate) fn age(&self) -> Duration {
+        Duration::from_secs(now().saturating_sub(self.fetched_at))
+    }
+
+    /// This response, revalidated now
+    pub(crate) fn refreshed(&self) -> Self {
+        CachedMetadata {
+            fetched_at: now(),
+            ..self.clone()
+        }
+    }
+}
+
+/// Seconds since the Unix epoch
+pub(crate) fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+/// Path of the cached copy of the endpoint `file`
+fn cache_path(out_dir: &str, file: &str) -> PathBuf {
+    PathBuf::from(out_dir).join(METADATA_DIR).join(file)
+}
+
+/// The cached response of the endpoint `file`, if one was stored and is readable
+pub(crate) async fn load(out_dir: &str, file: &str) -> Option<CachedMetadata> {
+    let path = cache_path(out_dir, file);
+    rt::spawn_blocking(move || {
+        let json = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&json).ok()
+    })
+    .await
+}
+
+/// Store the response of the endpoint `file`.
+///
+/// * Failures only cost the next run a full download, so they are logged, not returned.
+pub(crate) async fn store(out_dir: &str, file: &str, cached: CachedMetadata) {
+    let path = cache_path(out_dir, file);
+    rt::spawn_blocking(move || {
+        let staging = path.with_extension("staging");
+        let written = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| {
+                let json = serde_json::to_string(&cached).expect("metadata is serializable");
+                fs::write(&staging, json)
+            })
+            .and_then(|()| fs::rename(&staging, &path));
+        if let Err(e) = written {
+            debug!("could not cache {}: {e}", path.display());
+        }
+    })
+    .await
+}
@@ -0,0 +1,62 @@
+use crate::{Artifact, DEFAULT_DOWNLOAD_URL_TEMPLATE, DEFAULT_METADATA_BASE_URL};
+
+/// A source of Chrome for Testing metadata and artifacts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mirror {
+    /// Base URL of the JSON endpoints, e.g. `{base}/last-known-good-versions.json`
+    pub metadata_base_url: String,
+    /// Download URL with `{version}`, `{platform}` and `{artifact}` placeholders
+    pub download_url_template: String,
+}
+
+impl Mirror {
+    /// A mirror serving the Chrome for Testing layout
+    pub fn new(
+        metadata_base_url: impl Into<String>,
+        download_url_template: impl Into<String>,
+    ) -> Self {
+        Mirror {
+            metadata_base_url: metadata_base_url.into().trim_end_matches('/').to_string(),
+            download_url_template: download_url_template.into(),
+        }
+    }
+
+    /// The official Google endpoints
+    pub fn google() -> Self {
+        Mirror::new(DEFAULT_METADATA_BASE_URL, DEFAULT_DOWNLOAD_URL_TEMPLATE)
+    }
+
+    /// npmmirror.com's Chrome for Testing mirror
+    pub fn npmmirror() -> Self {
+        Mirror::new(
+            "https://cdn.npmmirror.com/binaries/chrome-for-testing",
+            "https://cdn.npmmirror.com/binaries/chrome-for-testing/{version}/{platform}/{artifact}-{platform}.zip",
+        )
+    }
+
+    /// Built-in failover list: Google first, then npmmirror.com.
+    ///
+    /// Not enabled by default, since it downloads executables from a third-party host.
+    pub fn builtin() -> Vec<Mirror> {
+        vec![Mirror::google(), Mirror::npmmirror()]
+    }
+
+    /// URL of a JSON endpoint under the metadata base
+    pub fn metadata_url(&self, file: &str) -> String {
+        format!("{}/{file}", self.metadata_base_url)
+    }
+
+    /// Download URL of `artifact` at `version` for `platform`
+    pub fn download_url(&self, artifact: Artifact, version: &str, platform: &str) -> String {
+        self.download_url_template
+            .replace("{version}", version)
+            .replace("{platform}", platform)
+            .replace("{artifact}", artifact.as_str())
+    }
+}
+
+impl Default for Mirror {
+    fn default() -> Self {
+        Mirror::google()
+    }
+}
@@ -0,0 +1,390 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use tracing::debug;
+
+use crate::{
+    error::{DriverError, Result},
+    Cdp, CdpEvents,
+};
+
+/// A request the page sent, from `Network.requestWillBeSent`
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkRequest {
+    /// Id shared with the request's [`NetworkResponse`] and [`Cdp::response_body`]
+    pub request_id: String,
+    /// Requested URL, without the fragment
+    pub url: String,
+    /// HTTP method, e.g. `POST`
+    pub method: String,
+    /// Request headers as sent by the page
+    pub headers: HashMap<String, String>,
+    /// Body of form submissions and `fetch`/XHR posts
+    pub post_data: Option<String>,
+    /// What requested it, e.g. `Document`, `XHR`, `Fetch` or `Image`
+    pub resource_type: Option<String>,
+    /// Seconds on the browser's monotonic clock
+    pub timestamp: f64,
+}
+
+/// Headers of a response, from `Network.responseReceived`
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkResponse {
+    /// Id of the [`NetworkRequest`] answered
+    pub request_id: String,
+    /// Final URL, after redirects
+    pub url: String,
+    /// HTTP status, e.g. `200`
+    pub status: u16,
+    /// Reason phrase, e.g. `OK`; empty over HTTP/2
+    pub status_text: String,
+    /// Response headers
+    pub headers: HashMap<String, String>,
+    /// e.g. `application/json`
+    pub mime_type: String,
+    /// What requested it, see [`NetworkRequest::resource_type`]
+    pub resource_type: Option<String>,
+    /// Connection and transfer phases; missing for cached and data URLs
+    pub timing: Option<ResourceTiming>,
+    /// Seconds on the browser's monotonic clock
+    pub timestamp: f64,
+}
+
+/// Phases of a request; all but `request_time` are milliseconds after it, `-1` when skipped
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceTiming {
+    /// Seconds on the browser's monotonic clock when the request started
+    pub request_time: f64,
+    // Name lookup, connection and TLS handshake; `-1` for reused connections
+    pub dns_start: f64,
+    pub dns_end: f64,
+    pub connect_start: f64,
+    pub connect_end: f64,
+    pub ssl_start: f64,
+    pub ssl_end: f64,
+    // Writing the request
+    pub send_start: f64,
+    pub send_end: f64,
+    /// Time to first byte
+    pub receive_headers_end: f64,
+}
+
+/// Traffic of a page, received through [`NetworkEvents`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetworkEvent {
+    /// The page sent a request
+    Request(NetworkRequest),
+    /// Response headers arrived
+    Response(NetworkResponse),
+    /// The body was fully received
+    Finished {
+        /// See [`NetworkRequest::request_id`]
+        request_id: String,
+        /// Seconds on the browser's monotonic clock
+        timestamp: f64,
+        /// Bytes on the wire, including headers
+        encoded_data_length: u64,
+    },
+    /// The request failed, was cancelled or was blocked
+    Failed {
+        /// See [`NetworkRequest::request_id`]
+        request_id: String,
+        /// Seconds on the browser's monotonic clock
+        timestamp: f64,
+        /// e.g. `net::ERR_BLOCKED_BY_CLIENT`
+        error_text: String,
+        /// Cancelled by the page, e.g. by navigating away
+        canceled: bool,
+    },
+}
+
+/// Page traffic reported by [`Cdp::monitor_network`]; ends when the connection closes
+#[derive(Debug)]
+pub struct NetworkEvents {
+    events: CdpEvents,
+}
+
+/// A request stopped by [`Cdp::intercept`], waiting to be continued, aborted or fulfilled.
+///
+/// * The page hangs on it until one of them is called.
+#[derive(Debug, Clone)]
+pub struct PausedRequest {
+    cdp: Cdp,
+    id: String,
+    request: RawRequest,
+    resource_type: Option<String>,
+}
+
+/// Requests matching the patterns of [`Cdp::intercept`]
+#[derive(Debug)]
+pub struct Interception {
+    cdp: Cdp,
+    events: CdpEvents,
+}
+
+impl Cdp {
+    /// Report the page's requests and responses.
+    ///
+    /// * Bodies stay in the browser; fetch them with [`Cdp::response_body`].
+    pub async fn monitor_network(&self) -> Result<NetworkEvents> {
+        let events = self.subscribe_all();
+        self.send("Network.enable", ()).await?;
+        Ok(NetworkEvents { events })
+    }
+
+    /// Body of the response to `request_id`, once its [`NetworkEvent::Finished`] arrived
+    pub async fn response_body(&self, request_id: &str) -> Result<Vec<u8>> {
+        let method = "Network.getResponseBody";
+        let reply = self
+            .send(method, json!({ "requestId": request_id }))
+            .await?;
+        let body = reply["body"].as_str().unwrap_or_default();
+        if !reply["base64Encoded"].as_bool().unwrap_or(false) {
+            return Ok(body.as_bytes().to_vec());
+        }
+        STANDARD.decode(body).map_err(|e| DriverError::DevTools {
+            method: method.into(),
+            message: format!("invalid base64 body: {e}"),
+        })
+    }
+
+    /// Fail requests whose URL matches one of `patterns`, where `*` matches anything, e.g.
+    /// `["*.doubleclick.net*", "*.png"]`; an empty list unblocks everything
+    pub async fn block_urls(&self, patterns: &[&str]) -> Result<()> {
+        self.send("Network.enable", ()).await?;
+        self.send("Network.setBlockedURLs", json!({ "urls": patterns }))
+            .await
+            .map(drop)
+    }
+
+    /// Pause requests whose URL matches one of `patterns` (`*` for anything, `?` for one
+    /// character) so each can be continued, aborted or answered, see [`PausedRequest`]
+    pub async fn intercept(&self, patterns: &[&str]) -> Result<Interception> {
+        let events = self.subscribe("Fetch.requestPaused");
+        let patterns: Vec<Value> = patterns
+            .iter()
+            .map(|pattern| json!({ "urlPattern": pattern, "requestStage": "Request" }))
+            .collect();
+        self.send("Fetch.enable", json!({ "patterns": patterns }))
+            .await?;
+        Ok(Interception {
+            cdp: self.clone(),
+            events,
+        })
+    }
+}
+
+impl NetworkEvents {
+    /// Wait for the next request or response, or `None` once the browser is gone
+    pub async fn next(&mut self) -> Option<NetworkEvent> {
+        loop {
+            let event = self.events.next().await?;
+            let parsed = match event.method.as_str() {
+                "Network.requestWillBeSent" => event.convert().map(|sent: RequestWillBeSent| {
+                    NetworkEvent::Request(NetworkRequest {
+                        request_id: sent.request_id,
+                        url: sent.request.url,
+                        method: sent.request.method,
+                        headers: sent.request.headers,
+                        post_data: sent.request.post_data,
+                        resource_type: sent.resource_type,
+                        timestamp: sent.timestamp,
+                    })
+                }),
+                "Network.responseReceived" => event.convert().map(|received: ResponseReceived| {
+                    let response = received.response;
+                    NetworkEvent::Response(NetworkResponse {
+                        request_id: received.request_id,
+                        url: response.url,
+                        status: response.status,
+                        status_text: response.status_text,
+                        headers: response.headers,
+                        mime_type: response.mime_type,
+                        resource_type: received.resource_type,
+                        timing: response.timing,
+                        timestamp: received.timestamp,
+                    })
+                }),
+                "Network.loadingFinished" => {
+                    event
+                        .convert()
+                        .map(|finished: LoadingFinished| NetworkEvent::Finished {
+                            request_id: finished.request_id,
+                            timestamp: finished.timestamp,
+                            encoded_data_length: finished.encoded_data_length as u64,
+                        })
+                }
+                "Network.loadingFailed" => {
+                    event
+                        .convert()
+                        .map(|failed: LoadingFailed| NetworkEvent::Failed {
+                            request_id: failed.request_id,
+                            timestamp: failed.timestamp,
+                            error_text: failed.error_text,
+                            canceled: failed.canceled,
+                        })
+                }
+                _ => continue,
+            };
+            match parsed {
+                Ok(event) => return Some(event),
+                Err(e) => debug!("skipping network event: {e}"),
+            }
+        }
+    }
+}
+
+impl Interception {
+    /// Wait for the next paused request, or `None` once the browser is gone
+    pub async fn next(&mut self) -> Option<PausedRequest> {
+        loop {
+            let event = self.events.next().await?;
+            match event.convert::<RequestPaused>() {
+                Ok(paused) => {
+                    return Some(PausedRequest {
+                        cdp: self.cdp.clone(),
+                        id: paused.request_id,
+                        request: paused.request,
+                        resource_type: paused.resource_type,
+                    })
+                }
+                Err(e) => debug!("skipping paused request: {e}"),
+            }
+        }
+    }
+
+    /// Stop pausing requests
+    pub async fn stop(self) -> Result<()> {
+        self.cdp.send("Fetch.disable", ()).await.map(drop)
+    }
+}
+
+impl PausedRequest {
+    /// Requested URL
+    pub fn url(&self) -> &str {
+        &self.request.url
+    }
+
+    /// HTTP method, e.g. `GET`
+    pub fn method(&self) -> &str {
+        &self.request.method
+    }
+
+    /// Request headers as sent by the page
+    pub fn headers(&self) -> &HashMap<String, String> {
+        &self.request.headers
+    }
+
+    /// Body of form submissions and `fetch`/XHR posts
+    pub fn post_data(&self) -> Option<&str> {
+        self.request.post_data.as_deref()
+    }
+
+    /// What requested it, e.g. `Document`, `Script` or `Image`
+    pub fn resource_type(&self) -> Option<&str> {
+        self.resource_type.as_deref()
+    }
+
+    /// Let the request go to the network unchanged
+    pub async fn continue_request(self) -> Result<()> {
+        self.answer("Fetch.continueRequest", json!({ "requestId": self.id }))
+            .await
+    }
+
+    /// Fail the request as blocked, like an ad blocker would
+    pub async fn abort(self) -> Result<()> {
+        let params = json!({ "requestId": self.id, "errorReason": "BlockedByClient" });
+        self.answer("Fetch.failRequest", params).await
+    }
+
+    /// Answer the request without contacting the server, e.g. with a stubbed API response
+    pub async fn fulfill(self, status: u16, headers: &[(&str, &str)], body: &[u8]) -> Result<()> {
+        let headers: Vec<Value> = headers
+            .iter()
+            .map(|(name, value)| json!({ "name": name, "value": value }))
+            .collect();
+        let params = json!({
+            "requestId": self.id,
+            "responseCode": status,
+            "responseHeaders": headers,
+            "body": STANDARD.encode(body),
+        });
+        self.answer("Fetch.fulfillRequest", params).await
+    }
+
+    async fn answer(&self, method: &str, params: Value) -> Result<()> {
+        self.cdp.send(method, params).await.map(drop)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawRequest {
+    url: String,
+    method: String,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    post_data: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RequestWillBeSent {
+    request_id: String,
+    request: RawRequest,
+    timestamp: f64,
+    #[serde(rename = "type")]
+    resource_type: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawResponse {
+    url: String,
+    status: u16,
+    #[serde(default)]
+    status_text: String,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    #[serde(default)]
+    mime_type: String,
+    timing: Option<ResourceTiming>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ResponseReceived {
+    request_id: String,
+    response: RawResponse,
+    timestamp: f64,
+    #[serde(rename = "type")]
+    resource_type: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LoadingFinished {
+    request_id: String,
+    timestamp: f64,
+    encoded_data_length: f64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LoadingFailed {
+    request_id: String,
+    timestamp: f64,
+    error_text: String,
+    #[serde(default)]
+    canceled: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RequestPaused {
+    request_id: String,
+    request: RawRequest,
+    resource_type: Option<String>,
+}
@@ -0,0 +1,443 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::{json, Map, Value};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{DriverError, Result},
+    BrowserInfo, DriverProcess, Session,
+};
+
+/// Typed `goog:chromeOptions` for starting a [`Session`].
+///
+/// * [`to_capabilities`](Self::to_capabilities) turns them into session capabilities.
+/// * [`option`](Self::option) and [`capability`](Self::capability) set anything not
+///   covered by a dedicated method.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChromeOptions {
+    binary: Option<PathBuf>,
+    args: Vec<String>,
+    prefs: Map<String, Value>,
+    extensions: Vec<PathBuf>,
+    debugger_address: Option<String>,
+    exclude_switches: Vec<String>,
+    mobile_emulation: Option<MobileEmulation>,
+    /// Further `goog:chromeOptions` entries
+    options: Map<String, Value>,
+    /// Further top-level capabilities
+    capabilities: Map<String, Value>,
+}
+
+/// Device Chrome pretends to be, see [`ChromeOptions::mobile_emulation`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum MobileEmulation {
+    /// A device from Chrome DevTools' list, e.g. `"iPhone 12 Pro"` or `"Pixel 7"`
+    Device(String),
+    /// A custom screen, with viewport size in CSS pixels
+    Metrics {
+        width: u32,
+        height: u32,
+        pixel_ratio: f64,
+        /// Emit touch instead of mouse events
+        touch: bool,
+        /// Sent instead of Chrome's desktop user agent
+        user_agent: Option<String>,
+    },
+}
+
+impl MobileEmulation {
+    /// A device from Chrome DevTools' list, see [`MobileEmulation::Device`]
+    pub fn device(name: impl Into<String>) -> MobileEmulation {
+        MobileEmulation::Device(name.into())
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            MobileEmulation::Device(name) => json!({ "deviceName": name }),
+            MobileEmulation::Metrics {
+                width,
+                height,
+                pixel_ratio,
+                touch,
+                user_agent,
+            } => {
+                let mut emulation = json!({
+                    "deviceMetrics": {
+                        "width": width,
+                        "height": height,
+                        "pixelRatio": pixel_ratio,
+                        "touch": touch,
+                    }
+                });
+                if let Some(user_agent) = user_agent {
+                    emulation["userAgent"] = json!(user_agent);
+                }
+                emulation
+            }
+        }
+    }
+}
+
+impl ChromeOptions {
+    /// Options for a plain Chrome session
+    pub fn new() -> ChromeOptions {
+        ChromeOptions::default()
+    }
+
+    /// Launch the Chrome executable at `path` instead of the system Chrome
+    pub fn binary(mut self, path: impl AsRef<Path>) -> Self {
+        self.binary = Some(path.as_ref().to_owned());
+        self
+    }
+
+    /// Launch a browser installed by [`crate::ensure_chrome_browser`], so the driver
+    /// and browser versions match
+    pub fn browser(self, browser: &BrowserInfo) -> Self {
+        self.binary(&browser.browser_path)
+    }
+
+    /// Pass a command-line flag to Chrome, e.g. `--lang=de`
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Pass several command-line flags, see [`arg`](Self::arg)
+    pub fn args<I>(mut self, args: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Run without a visible window, in Chrome's current headless mode.
+    ///
+    /// * Adds `--headless=new`, `--disable-gpu`, `--disable-dev-shm-usage` (Docker's
+    ///   `/dev/shm` is too small for Chrome) and a 1920×1080 window.
+    /// * Running as root, as in many containers, also needs `.arg("--no-sandbox")`.
+    pub fn headless(self) -> Self {
+        self.without_headless()
+            .arg("--headless=new")
+            .container_flags()
+    }
+
+    /// Like [`headless`](Self::headless), but with the old headless implementation
+    /// (`--headless`), for Chrome before 109.
+    ///
+    /// * Chrome 132 removed it; use the `chrome-headless-shell` artifact instead.
+    pub fn headless_old(self) -> Self {
+        self.without_headless().arg("--headless").container_flags()
+    }
+
+    fn without_headless(mut self) -> Self {
+        self.args
+            .retain(|arg| arg != "--headless" && !arg.starts_with("--headless="));
+        self
+    }
+
+    /// Flags headless Chrome needs in containers, unless already set
+    fn container_flags(mut self) -> Self {
+        for flag in ["--disable-gpu", "--disable-dev-shm-usage"] {
+            if !self.args.iter().any(|arg| arg == flag) {
+                self.args.push(flag.into());
+            }
+        }
+        if !self
+            .args
+            .iter()
+            .any(|arg| arg.starts_with("--window-size="))
+        {
+            self.args.push("--window-size=1920,1080".into());
+        }
+        self
+    }
+
+    /// Set a user preference, e.g. `intl.accept_languages`; dots are kept as-is, Chrome
+    /// reads them as nested keys
+    pub fn pref(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.prefs.insert(key.into(), value.into());
+        self
+    }
+
+    /// Save downloads to `dir` without asking, e.g. to check downloaded files in a test.
+    ///
+    /// * Relative paths are resolved against the current directory, as Chrome wants
+    ///   absolute ones.
+    /// * The directory is read by the browser, so it must be on the driver's host.
+    pub fn download_dir(self, dir: impl AsRef<Path>) -> Self {
+        let dir = dir.as_ref();
+        let dir = std::path::absolute(dir).unwrap_or_else(|_| dir.to_owned());
+        self.pref("download.default_directory", dir.to_string_lossy())
+            .pref("download.prompt_for_download", false)
+            .pref("download.directory_upgrade", true)
+    }
+
+    /// Install the packed extension (`.crx`) at `path`
+    pub fn extension(mut self, path: impl AsRef<Path>) -> Self {
+        self.extensions.push(path.as_ref().to_owned());
+        self
+    }
+
+    /// Attach to an already running Chrome at `address`, e.g. `127.0.0.1:9222`, instead
+    /// of launching one
+    pub fn debugger_address(mut self, address: impl Into<String>) -> Self {
+        self.debugger_address = Some(address.into());
+        self
+    }
+
+    /// Drop one of the flags chromedriver passes by default, e.g. `enable-automation`
+    pub fn exclude_switch(mut self, switch: impl Into<String>) -> Self {
+        self.exclude_switches.push(switch.into());
+        self
+    }
+
+    /// Render pages as on a phone or tablet, e.g.
+    /// `mobile_emulation(MobileEmulation::device("iPhone 12 Pro"))`.
+    ///
+    /// * Chrome refuses to start the session for device names it doesn't know.
+    pub fn mobile_emulation(mut self, emulation: MobileEmulation) -> Self {
+        self.mobile_emulation = Some(emulation);
+        self
+    }
+
+    /// Set any other `goog:chromeOptions` entry, e.g. `detach`
+    pub fn option(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.options.insert(key.into(), value.into());
+        self
+    }
+
+    /// Set a top-level capability, e.g. `acceptInsecureCerts`
+    pub fn capability(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.capabilities.insert(key.into(), value.into());
+        self
+    }
+
+    /// Session capabilities for [`Session::new`].
+    ///
+    /// * Reads the [`extension`](Self::extension) files, which must exist.
+    pub fn to_capabilities(&self) -> Result<Value> {
+        let mut chrome = self.options.clone();
+        if let Some(binary) = &self.binary {
+            chrome.insert("binary".into(), json!(binary.to_string_lossy()));
+        }
+        if !self.args.is_empty() {
+            chrome.insert("args".into(), json!(self.args));
+        }
+        if !self.prefs.is_empty() {
+            chrome.insert("prefs".into(), Value::Object(self.prefs.clone()));
+        }
+        if !self.extensions.is_empty() {
+            let encoded = self
+                .extensions
+                .iter()
+                .map(|path| {
+                    fs::read(path)
+                        .map(|crx| STANDARD.encode(crx))
+                        .map_err(|e| DriverError::io(format!("reading {}", path.display()), e))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            chrome.insert("extensions".into(), json!(encoded));
+        }
+        if let Some(address) = &self.debugger_address {
+            chrome.insert("debuggerAddress".into(), json!(address));
+        }
+        if !self.exclude_switches.is_empty() {
+            chrome.insert("excludeSwitches".into(), json!(self.exclude_switches));
+        }
+        if let Some(emulation) = &self.mobile_emulation {
+            chrome.insert("mobileEmulation".into(), emulation.to_json());
+        }
+
+        let mut capabilities = self.capabilities.clone();
+        capabilities.insert("browserName".into(), json!("chrome"));
+        capabilities.insert("goog:chromeOptions".into(), Value::Object(chrome));
+        Ok(Value::Object(capabilities))
+    }
+}
+
+impl Session {
+    /// Start a Chrome session configured by `options` on the driver at `base_url`
+    pub async fn with_options(base_url: &str, options: &ChromeOptions) -> Result<Session> {
+        Session::new(base_url, options.to_capabilities()?).await
+    }
+}
+
+impl DriverProcess {
+    /// Start a Chrome session configured by `options` on this driver
+    pub async fn new_session_with_options(&self, options: &ChromeOptions) -> Result<Session> {
+        Session::with_options(self.base_url(), options).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chrome_options(options: &ChromeOptions) -> Value {
+        options.to_capabilities().unwrap()["goog:chromeOptions"].clone()
+    }
+
+    #[test]
+    fn plain_options_only_name_the_browser() {
+        assert_eq!(
+            ChromeOptions::new().to_capabilities().unwrap(),
+            json!({ "browserName": "chrome", "goog:chromeOptions": {} })
+        );
+    }
+
+    #[test]
+    fn serializes_args_prefs_and_experimental_options() {
+        let options = ChromeOptions::new()
+            .binary("/opt/chrome/chrome")
+            .arg("--lang=de")
+            .args(["--no-first-run", "--mute-audio"])
+            .pref("intl.accept_languages", "de")
+            .pref("profile.default_content_settings.popups", 0)
+            .debugger_address("127.0.0.1:9222")
+            .exclude_switch("enable-automation")
+            .option("detach", true)
+            .option("windowTypes", json!(["webview"]))
+            .capability("acceptInsecureCerts", true);
+        assert_eq!(
+            options.to_capabilities().unwrap(),
+            json!({
+                "browserName": "chrome",
+                "acceptInsecureCerts": true,
+                "goog:chromeOptions": {
+                    "binary": "/opt/chrome/chrome",
+                    "args": ["--lang=de", "--no-first-run", "--mute-audio"],
+                    "prefs": {
+                        "intl.accept_languages": "de",
+                        "profile.default_content_settings.popups": 0,
+                    },
+                    "debuggerAddress": "127.0.0.1:9222",
+                    "excludeSwitches": ["enable-automation"],
+                    "detach": true,
+                    "windowTypes": ["webview"],
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn typed_entries_win_over_raw_options() {
+        let options = ChromeOptions::new()
+            .option("args", json!(["--raw"]))
+            .arg("--typed");
+        assert_eq!(chrome_options(&options)["args"], json!(["--typed"]));
+    }
+
+    #[test]
+    fn encodes_extensions() {
+        let path = std::env::temp_dir().join(format!(
+            "chrome-driver-rs-options-{}.crx",
+            std::process::id()
+        ));
+        fs::write(&path, b"crx").unwrap();
+        let options = ChromeOptions::new().extension(&path);
+        assert_eq!(chrome_options(&options)["extensions"], json!(["Y3J4"]));
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            options.to_capabilities(),
+            Err(DriverError::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn headless_adds_container_flags_once() {
+        let options = ChromeOptions::new()
+            .arg("--disable-gpu")
+            .arg("--window-size=800,600")
+            .headless();
+        assert_eq!(
+            chrome_options(&options)["args"],
+            json!([
+                "--disable-gpu",
+                "--window-size=800,600",
+                "--headless=new",
+                "--disable-dev-shm-usage",
+            ])
+        );
+    }
+
+    #[test]
+    fn headless_presets_replace_each_other() {
+        let options = ChromeOptions::new().headless().headless_old();
+        assert_eq!(
+            chrome_options(&options)["args"],
+            json!([
+                "--disable-gpu",
+                "--disable-dev-shm-usage",
+                "--window-size=1920,1080",
+                "--headless",
+            ])
+        );
+        let options = options.headless();
+        let args = chrome_options(&options)["args"].clone();
+        assert_eq!(args.as_array().unwrap().len(), 4);
+        assert!(args.as_array().unwrap().contains(&json!("--headless=new")));
+        assert!(!args.as_array().unwrap().contains(&json!("--headless")));
+    }
+
+    #[test]
+    fn serializes_mobile_emulation() {
+        let device = ChromeOptions::new().mobile_emulation(MobileEmulation::device("Pixel 7"));
+        assert_eq!(
+            chrome_options(&device)["mobileEmulation"],
+            json!({ "deviceName": "Pixel 7" })
+        );
+
+        let metrics = ChromeOptions::new().mobile_emulation(MobileEmulation::Metrics {
+            width: 390,
+            height: 844,
+            pixel_ratio: 3.0,
+            touch: true,
+            user_agent: Some("Mozilla/5.0 (iPhone)".into()),
+        });
+        assert_eq!(
+            chrome_options(&metrics)["mobileEmulation"],
+            json!({
+                "deviceMetrics": { "width": 390, "height": 844, "pixelRatio": 3.0, "touch": true },
+                "userAgent": "Mozilla/5.0 (iPhone)",
+            })
+        );
+
+        let without_user_agent = ChromeOptions::new().mobile_emulation(MobileEmulation::Metrics {
+            width: 768,
+            height: 1024,
+            pixel_ratio: 2.0,
+            touch: false,
+            user_agent: None,
+        });
+        assert!(chrome_options(&without_user_agent)["mobileEmulation"]
+            .get("userAgent")
+            .is_none());
+    }
+
+    #[test]
+    fn download_dir_sets_the_download_prefs() {
+        let dir = std::env::temp_dir().join("downloads");
+        let options = ChromeOptions::new().download_dir(&dir);
+        assert_eq!(
+            chrome_options(&options)["prefs"],
+            json!({
+                "download.default_directory": dir.to_string_lossy(),
+                "download.prompt_for_download": false,
+                "download.directory_upgrade": true,
+            })
+        );
+    }
+
+    #[test]
+    fn download_dir_resolves_relative_paths() {
+        let options = ChromeOptions::new().download_dir("downloads");
+        let dir = chrome_options(&options)["prefs"]["download.default_directory"].clone();
+        let expected = std::env::current_dir().unwrap().join("downloads");
+        assert_eq!(dir, json!(expected.to_string_lossy()));
+    }
+}
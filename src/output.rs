@@ -0,0 +1,148 @@
+use std::{
+    fmt,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Read, Write},
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::{Arc, Mutex},
+    thread,
+};
+use tracing::{debug, Dispatch};
+
+use crate::error::{DriverError, Result};
+
+/// Output stream of a spawned driver
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+impl OutputStream {
+    /// `"stdout"` or `"stderr"`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputStream::Stdout => "stdout",
+            OutputStream::Stderr => "stderr",
+        }
+    }
+}
+
+/// User callback receiving every printed line
+type OutputCallback = Arc<dyn Fn(OutputStream, &str) + Send + Sync>;
+
+/// Where the lines a driver prints go, besides `tracing`
+#[derive(Clone, Default)]
+pub(crate) struct OutputSink {
+    callback: Option<OutputCallback>,
+    file: Option<PathBuf>,
+}
+
+impl OutputSink {
+    pub(crate) fn set_callback(
+        &mut self,
+        callback: impl Fn(OutputStream, &str) + Send + Sync + 'static,
+    ) {
+        self.callback = Some(Arc::new(callback));
+    }
+
+    pub(crate) fn set_file(&mut self, path: &Path) {
+        self.file = Some(path.to_owned());
+    }
+
+    /// Empty the log file, once per [`DriverProcessBuilder::start`](crate::DriverProcessBuilder::start)
+    pub(crate) fn truncate(&self) -> Result<()> {
+        if let Some(path) = &self.file {
+            File::create(path)
+                .map_err(|e| DriverError::io(format!("creating {}", path.display()), e))?;
+        }
+        Ok(())
+    }
+
+    /// Output handles of a driver that must not depend on this process: the log file
+    /// appended to by both streams, or nothing
+    pub(crate) fn direct(&self) -> Result<(Stdio, Stdio)> {
+        match &self.file {
+            Some(path) => {
+                let file = append(path)?;
+                let stderr = file
+                    .try_clone()
+                    .map_err(|e| DriverError::io(format!("opening {}", path.display()), e))?;
+                Ok((file.into(), stderr.into()))
+            }
+            None => Ok((Stdio::null(), Stdio::null())),
+        }
+    }
+
+    /// Forward the driver's `stdout` and `stderr` line by line on background threads.
+    ///
+    /// * Every line becomes a `debug` event with target `chromedriver`.
+    /// * The log file is appended to, so a restarted driver keeps the output of the one
+    ///   that crashed, and written unbuffered, so it survives crashes.
+    pub(crate) fn attach(
+        &self,
+        stdout: impl Read + Send + 'static,
+        stderr: impl Read + Send + 'static,
+    ) -> Result<()> {
+        let file = match &self.file {
+            Some(path) => Some(Arc::new(Mutex::new(append(path)?))),
+            None => None,
+        };
+        self.forward(OutputStream::Stdout, stdout, file.clone())?;
+        self.forward(OutputStream::Stderr, stderr, file)
+    }
+
+    fn forward(
+        &self,
+        stream: OutputStream,
+        reader: impl Read + Send + 'static,
+        file: Option<Arc<Mutex<File>>>,
+    ) -> Result<()> {
+        let callback = self.callback.clone();
+        // Keep reporting to the caller's subscriber
+        let dispatch = tracing::dispatcher::get_default(Dispatch::clone);
+        let forward = move || {
+            let mut reader = BufReader::new(reader);
+            let mut buffer = Vec::new();
+            while reader
+                .read_until(b'\n', &mut buffer)
+                .is_ok_and(|read| read > 0)
+            {
+                let text = String::from_utf8_lossy(&buffer);
+                let line = text.trim_end_matches(['\r', '\n']);
+                debug!(target: "chromedriver", stream = stream.as_str(), "{line}");
+                if let Some(callback) = &callback {
+                    callback(stream, line);
+                }
+                if let Some(file) = &file {
+                    let mut file = file.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                    let _ = writeln!(file, "{line}");
+                }
+                buffer.clear();
+            }
+        };
+        thread::Builder::new()
+            .name(format!("chromedriver-{}", stream.as_str()))
+            .spawn(move || tracing::dispatcher::with_default(&dispatch, forward))
+            .map(drop)
+            .map_err(|e| DriverError::io("starting the driver output thread", e))
+    }
+}
+
+/// Open `path` for appending, creating it if needed
+fn append(path: &Path) -> Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| DriverError::io(format!("opening {}", path.display()), e))
+}
+
+impl fmt::Debug for OutputSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OutputSink")
+            .field("callback", &self.callback.as_ref().map(|_| ".."))
+            .field("file", &self.file)
+            .finish()
+    }
+}
@@ -0,0 +1,38 @@
+use std::{env, path::PathBuf};
+
+/// Environment variable naming a ChromeDriver to use instead of installing one
+pub const CHROMEDRIVER_PATH_ENV: &str = "CHROMEDRIVER_PATH";
+
+/// Environment variable pinning the installed version, e.g. `130.0.6723.58`
+pub const CHROMEDRIVER_VERSION_ENV: &str = "CHROMEDRIVER_VERSION";
+
+/// Environment variable that, when truthy (`1`, `true`, `yes`), forbids downloads
+pub const CHROMEDRIVER_SKIP_DOWNLOAD_ENV: &str = "CHROMEDRIVER_SKIP_DOWNLOAD";
+
+/// Value of `name`, unless unset or blank
+fn var(name: &str) -> Option<String> {
+    env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Driver from [`CHROMEDRIVER_PATH_ENV`]
+pub(crate) fn driver_path() -> Option<PathBuf> {
+    var(CHROMEDRIVER_PATH_ENV).map(PathBuf::from)
+}
+
+/// Version from [`CHROMEDRIVER_VERSION_ENV`]
+pub(crate) fn version() -> Option<String> {
+    var(CHROMEDRIVER_VERSION_ENV)
+}
+
+/// Whether [`CHROMEDRIVER_SKIP_DOWNLOAD_ENV`] is truthy
+pub(crate) fn skip_download() -> bool {
+    var(CHROMEDRIVER_SKIP_DOWNLOAD_ENV).is_some_and(|value| {
+        matches!(
+            value.to_ascii_lowercase().as_str(),
+            "1" | "true" | "yes" | "on"
+        )
+    })
+}
@@ -0,0 +1,201 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::{json, Value};
+use std::{collections::HashMap, fs, path::Path, time::Duration};
+use tracing::debug;
+
+use crate::{
+    error::{DriverError, Result},
+    rt, Cdp, CdpEvents,
+};
+
+/// Categories recorded by [`Cdp::start_trace`] without explicit ones, matching the
+/// DevTools Performance panel
+const DEFAULT_TRACE_CATEGORIES: &[&str] = &[
+    "-*",
+    "devtools.timeline",
+    "disabled-by-default-devtools.timeline",
+    "disabled-by-default-devtools.timeline.frame",
+    "disabled-by-default-devtools.timeline.stack",
+    "toplevel",
+    "v8.execute",
+    "blink.console",
+    "blink.user_timing",
+    "latencyInfo",
+    "loading",
+];
+
+/// Navigation and paint timings of the current document, read from the page
+const PAGE_TIMINGS: &str = "JSON.stringify((() => {
+    const nav = performance.getEntriesByType('navigation')[0];
+    const fcp = performance.getEntriesByName('first-contentful-paint')[0];
+    return {
+        ttfb: nav ? nav.responseStart : null,
+        fcp: fcp ? fcp.startTime : null,
+        dcl: nav && nav.domContentLoadedEventEnd ? nav.domContentLoadedEventEnd : null,
+        load: nav && nav.loadEventEnd ? nav.loadEventEnd : null,
+    };
+})())";
+
+/// A snapshot of page performance, from [`Cdp::performance_metrics`].
+///
+/// * Timings count from the start of the navigation; `None` until the page reached them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PerformanceMetrics {
+    /// Time to first byte of the document
+    pub ttfb: Option<Duration>,
+    /// First contentful paint
+    pub first_contentful_paint: Option<Duration>,
+    /// End of the `DOMContentLoaded` handlers
+    pub dom_content_loaded: Option<Duration>,
+    /// End of the `load` handlers
+    pub load: Option<Duration>,
+    /// Bytes of JavaScript heap in use
+    pub js_heap_used: u64,
+    /// Bytes of JavaScript heap allocated
+    pub js_heap_total: u64,
+    /// DOM nodes alive
+    pub nodes: u64,
+    /// Layouts since the page was opened
+    pub layout_count: u64,
+    /// Time spent running scripts since the page was opened
+    pub script_duration: Duration,
+    /// Time spent on all browser tasks since the page was opened
+    pub task_duration: Duration,
+    /// Every metric of `Performance.getMetrics`, e.g. `RecalcStyleCount`
+    pub raw: HashMap<String, f64>,
+}
+
+/// A trace being recorded, see [`Cdp::start_trace`]
+#[derive(Debug)]
+pub struct Trace {
+    cdp: Cdp,
+    complete: CdpEvents,
+}
+
+impl Cdp {
+    /// Current metrics of the page, e.g. for performance regression checks
+    pub async fn performance_metrics(&self) -> Result<PerformanceMetrics> {
+        self.send("Performance.enable", ()).await?;
+        let reply = self.send("Performance.getMetrics", ()).await?;
+        let raw: HashMap<String, f64> = reply["metrics"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|metric| {
+                Some((
+                    metric["name"].as_str()?.to_string(),
+                    metric["value"].as_f64()?,
+                ))
+            })
+            .collect();
+        let params = json!({ "expression": PAGE_TIMINGS, "returnByValue": true });
+        let evaluated = self.send("Runtime.evaluate", params).await?;
+        let timings: Value = evaluated["result"]["value"]
+            .as_str()
+            .and_then(|timings| serde_json::from_str(timings).ok())
+            .unwrap_or_default();
+        let millis = |name: &str| {
+            timings[name]
+                .as_f64()
+                .filter(|ms| *ms >= 0.0)
+                .map(|ms| Duration::from_secs_f64(ms / 1000.0))
+        };
+        let count = |name: &str| raw.get(name).copied().unwrap_or_default() as u64;
+        let seconds = |name: &str| {
+            Duration::from_secs_f64(raw.get(name).copied().unwrap_or_default().max(0.0))
+        };
+        Ok(PerformanceMetrics {
+            ttfb: millis("ttfb"),
+            first_contentful_paint: millis("fcp"),
+            dom_content_loaded: millis("dcl"),
+            load: millis("load"),
+            js_heap_used: count("JSHeapUsedSize"),
+            js_heap_total: count("JSHeapTotalSize"),
+            nodes: count("Nodes"),
+            layout_count: count("LayoutCount"),
+            script_duration: seconds("ScriptDuration"),
+            task_duration: seconds("TaskDuration"),
+            raw,
+        })
+    }
+
+    /// Start recording a Chrome trace of `categories`, or of what the DevTools Performance
+    /// panel records when empty
+    pub async fn start_trace(&self, categories: &[&str]) -> Result<Trace> {
+        let categories = if categories.is_empty() {
+            DEFAULT_TRACE_CATEGORIES
+        } else {
+            categories
+        };
+        let complete = self.subscribe("Tracing.tracingComplete");
+        let params = json!({
+            "transferMode": "ReturnAsStream",
+            "traceConfig": { "includedCategories": categories },
+        });
+        self.send("Tracing.start", params).await?;
+        Ok(Trace {
+            cdp: self.clone(),
+            complete,
+        })
+    }
+}
+
+impl Trace {
+    /// Stop recording and return the trace as JSON, which Perfetto and the DevTools
+    /// Performance panel can open
+    pub async fn stop(mut self) -> Result<Vec<u8>> {
+        self.cdp.send("Tracing.end", ()).await?;
+        let complete = self
+            .complete
+            .next()
+            .await
+            .ok_or_else(|| DriverError::DevTools {
+                method: "Tracing.end".into(),
+                message: "the connection closed before the trace was complete".into(),
+            })?;
+        let Some(stream) = complete.params["stream"].as_str() else {
+            return Err(DriverError::DevTools {
+                method: "Tracing.end".into(),
+                message: "the trace has no stream handle".into(),
+            });
+        };
+
+        let mut trace = Vec::new();
+        loop {
+            let chunk = self
+                .cdp
+                .send("IO.read", json!({ "handle": stream }))
+                .await?;
+            let data = chunk["data"].as_str().unwrap_or_default();
+            if chunk["base64Encoded"].as_bool().unwrap_or(false) {
+                let decoded = STANDARD.decode(data).map_err(|e| DriverError::DevTools {
+                    method: "IO.read".into(),
+                    message: format!("invalid base64 chunk: {e}"),
+                })?;
+                trace.extend(decoded);
+            } else {
+                trace.extend(data.as_bytes());
+            }
+            if chunk["eof"].as_bool().unwrap_or(true) {
+                break;
+            }
+        }
+        self.cdp
+            .send("IO.close", json!({ "handle": stream }))
+            .await?;
+        debug!("trace of {} bytes recorded", trace.len());
+        Ok(trace)
+    }
+
+    /// Stop recording like [`stop`](Self::stop) and save the trace at `path`
+    pub async fn stop_to_file(self, path: impl AsRef<Path>) -> Result<()> {
+        let trace = self.stop().await?;
+        let path = path.as_ref().to_owned();
+        rt::spawn_blocking(move || {
+            fs::write(&path, trace)
+                .map_err(|e| DriverError::io(format!("writing {}", path.display()), e))
+        })
+        .await
+    }
+}
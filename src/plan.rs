@@ -0,0 +1,125 @@
+use std::{env, path::Path};
+use tracing::info;
+
+use crate::{
+    cft_platform,
+    error::{DriverError, Result},
+    http, legacy, linux_arm64, Artifact, ArtifactInfo, DriverManager,
+};
+
+/// What an install would do, without downloading or writing anything.
+///
+/// Returned by [`DriverManager::plan`]; [`dry_run`](crate::DriverManagerBuilder::dry_run)
+/// installs report it instead of installing.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InstallPlan {
+    /// Which artifact would be installed
+    pub artifact: Artifact,
+    /// Resolved version
+    pub version: String,
+    /// URL the archive would be downloaded from (the first mirror)
+    pub url: String,
+    /// Path the executable would be installed at
+    pub destination: String,
+    /// Archive size in bytes from a `HEAD` request, if the server reports it
+    pub size: Option<u64>,
+    /// Whether `destination` already exists, so nothing would be downloaded
+    pub installed: bool,
+}
+
+impl From<InstallPlan> for ArtifactInfo {
+    fn from(plan: InstallPlan) -> Self {
+        ArtifactInfo {
+            artifact: plan.artifact,
+            path: plan.destination,
+            version: plan.version,
+            downloaded: false,
+        }
+    }
+}
+
+/// Work out where `artifact` at `version` comes from and goes to, following the same
+/// layout rules as the installer
+pub(crate) async fn plan(
+    manager: &DriverManager,
+    artifact: Artifact,
+    version: &str,
+) -> Result<InstallPlan> {
+    // 1️⃣ Pick source and destination like the installer would
+    let out_dir = manager.out_dir();
+    let install_dir = manager.install_dir(version)?;
+    let driver = artifact == Artifact::ChromeDriver;
+    let arm64 = env::consts::OS == "linux" && env::consts::ARCH == "aarch64";
+    let (url, destination) = if driver && arm64 {
+        let dir = if manager.flat() {
+            format!("{out_dir}/{version}")
+        } else {
+            install_dir
+        };
+        if let Some(path) = linux_arm64::system_driver() {
+            (String::new(), path.to_string())
+        } else {
+            let template = env::var(linux_arm64::LINUX_ARM64_URL_ENV).map_err(|_| {
+                DriverError::UnsupportedPlatform(format!(
+                    "linux-arm64 has no Chrome for Testing build; \
+                     install chromium-driver or set {}",
+                    linux_arm64::LINUX_ARM64_URL_ENV
+                ))
+            })?;
+            let url = template.replace("{version}", version);
+            (url, format!("{dir}/chromedriver-linux-arm64/chromedriver"))
+        }
+    } else if driver && legacy::is_legacy(version) {
+        let (url, executable) = legacy::download_url(version)?;
+        let destination = if manager.flat() {
+            format!("{out_dir}/{version}/{executable}")
+        } else {
+            let platform = legacy::legacy_platform(version)?;
+            format!("{install_dir}/chromedriver_{platform}/{executable}")
+        };
+        (url, destination)
+    } else {
+        let platform = cft_platform()?;
+        let url = manager.mirrors()[0].download_url(artifact, version, platform);
+        let executable = artifact.executable(platform);
+        let destination = if driver && manager.flat() {
+            format!("{out_dir}/{version}/{executable}")
+        } else {
+            format!(
+                "{install_dir}/{}-{platform}/{executable}",
+                artifact.as_str()
+            )
+        };
+        (url, destination)
+    };
+
+    // 2️⃣ Ask for the size, unless nothing would be downloaded
+    let installed = Path::new(&destination).exists() && !manager.force();
+    let size = if installed || url.is_empty() {
+        None
+    } else {
+        http::content_length(manager.client(), &url, manager.metadata_timeout()).await
+    };
+
+    let plan = InstallPlan {
+        artifact,
+        version: version.to_string(),
+        url,
+        destination,
+        size,
+        installed,
+    };
+    if plan.installed {
+        info!("📝 Would keep {}", plan.destination);
+    } else {
+        info!(
+            "📝 Would download {} ({}) to {}",
+            plan.url,
+            plan.size
+                .map_or("unknown size".to_string(), |size| format!("{size} bytes")),
+            plan.destination
+        );
+    }
+    Ok(plan)
+}
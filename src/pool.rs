@@ -0,0 +1,156 @@
+use std::{
+    fmt,
+    ops::Deref,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::info;
+
+use crate::{
+    error::{DriverError, Result},
+    DriverProcess, DriverProcessBuilder,
+};
+
+/// A fixed set of warm drivers shared by parallel tests.
+///
+/// * [`DriverPool::checkout`] hands out an idle driver, waiting while all are in use.
+/// * Dropping the [`PooledDriver`] returns it to the pool.
+pub struct DriverPool {
+    inner: Arc<PoolInner>,
+    size: usize,
+}
+
+struct PoolInner {
+    idle: Mutex<Vec<DriverProcess>>,
+    available: Arc<Semaphore>,
+}
+
+/// A driver checked out of a [`DriverPool`]; dereferences to its [`DriverProcess`]
+pub struct PooledDriver {
+    process: Option<DriverProcess>,
+    pool: Arc<PoolInner>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl DriverPool {
+    /// Launch `size` drivers configured by `builder`.
+    ///
+    /// * Each driver gets its own free port, whatever [`port`](DriverProcessBuilder::port)
+    ///   the builder was given.
+    /// * Combine with [`restart`](DriverProcessBuilder::restart) to keep crashed drivers in
+    ///   service.
+    pub async fn start(builder: DriverProcessBuilder, size: usize) -> Result<DriverPool> {
+        if size == 0 {
+            return Err(DriverError::Config(
+                "a driver pool needs at least one driver".into(),
+            ));
+        }
+        let mut idle = Vec::with_capacity(size);
+        for _ in 0..size {
+            idle.push(builder.clone().port(0).start().await?);
+        }
+        info!("🚀 driver pool of {size} ready");
+        Ok(DriverPool {
+            inner: Arc::new(PoolInner {
+                idle: Mutex::new(idle),
+                available: Arc::new(Semaphore::new(size)),
+            }),
+            size,
+        })
+    }
+
+    /// Take an idle driver, waiting until one is returned if all are checked out
+    pub async fn checkout(&self) -> Result<PooledDriver> {
+        let permit = self
+            .inner
+            .available
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|_| DriverError::Config("the driver pool was shut down".into()))?;
+        Ok(self.inner.take(permit))
+    }
+
+    /// Take an idle driver if one is available right now
+    pub fn try_checkout(&self) -> Option<PooledDriver> {
+        let permit = self.inner.available.clone().try_acquire_owned().ok()?;
+        Some(self.inner.take(permit))
+    }
+
+    /// Number of drivers in the pool
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Number of drivers not checked out
+    pub fn idle(&self) -> usize {
+        self.inner.available.available_permits()
+    }
+
+    /// Wait until every driver is returned, then [`shutdown`](DriverProcess::shutdown) them all
+    pub async fn shutdown(self) -> Result<()> {
+        let all = u32::try_from(self.size).unwrap_or(u32::MAX);
+        let _permits = self
+            .inner
+            .available
+            .acquire_many(all)
+            .await
+            .map_err(|_| DriverError::Config("the driver pool was shut down".into()))?;
+        self.inner.available.close();
+        let processes = std::mem::take(&mut *self.inner.idle());
+        for process in processes {
+            process.shutdown().await?;
+        }
+        Ok(())
+    }
+}
+
+impl PoolInner {
+    fn idle(&self) -> std::sync::MutexGuard<'_, Vec<DriverProcess>> {
+        self.idle
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Take an idle driver, which `permit` guarantees exists
+    fn take(self: &Arc<Self>, permit: OwnedSemaphorePermit) -> PooledDriver {
+        let process = self.idle().pop().expect("a permit implies an idle driver");
+        PooledDriver {
+            process: Some(process),
+            pool: self.clone(),
+            _permit: permit,
+        }
+    }
+}
+
+impl Deref for PooledDriver {
+    type Target = DriverProcess;
+
+    fn deref(&self) -> &DriverProcess {
+        self.process.as_ref().expect("present until dropped")
+    }
+}
+
+impl Drop for PooledDriver {
+    fn drop(&mut self) {
+        // Back to the pool before the permit is released
+        if let Some(process) = self.process.take() {
+            self.pool.idle().push(process);
+        }
+    }
+}
+
+impl fmt::Debug for DriverPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DriverPool")
+            .field("size", &self.size)
+            .field("idle", &self.idle())
+            .finish()
+    }
+}
+
+impl fmt::Debug for PooledDriver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PooledDriver").field(&**self).finish()
+    }
+}
@@ -0,0 +1,100 @@
+use reqwest::Method;
+use serde::Serialize;
+
+use crate::{error::Result, screenshot, Session};
+
+/// Layout of a page printed by [`Session::print`]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrintOptions {
+    /// Portrait or landscape
+    pub orientation: Orientation,
+    /// Zoom factor between 0.1 and 2 (default 1)
+    pub scale: f64,
+    /// Print background colors and images
+    pub background: bool,
+    /// Paper size
+    pub page: PageSize,
+    /// Space around the content
+    pub margin: Margins,
+    /// Shrink content that is wider than the page
+    pub shrink_to_fit: bool,
+    /// Pages to print, e.g. `["1-3", "5"]`; empty for all
+    pub page_ranges: Vec<String>,
+}
+
+impl Default for PrintOptions {
+    /// Portrait US Letter with 1cm margins, scale 1, no backgrounds
+    fn default() -> Self {
+        PrintOptions {
+            orientation: Orientation::Portrait,
+            scale: 1.0,
+            background: false,
+            page: PageSize::LETTER,
+            margin: Margins::uniform(1.0),
+            shrink_to_fit: true,
+            page_ranges: Vec::new(),
+        }
+    }
+}
+
+/// Page orientation of [`PrintOptions`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Orientation {
+    Portrait,
+    Landscape,
+}
+
+/// Paper size in centimeters
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct PageSize {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl PageSize {
+    /// ISO A4, 21 × 29.7cm
+    pub const A4: PageSize = PageSize {
+        width: 21.0,
+        height: 29.7,
+    };
+    /// US Letter, 8.5 × 11in
+    pub const LETTER: PageSize = PageSize {
+        width: 21.59,
+        height: 27.94,
+    };
+}
+
+/// Page margins in centimeters
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Margins {
+    pub top: f64,
+    pub bottom: f64,
+    pub left: f64,
+    pub right: f64,
+}
+
+impl Margins {
+    /// The same margin on every side
+    pub fn uniform(margin: f64) -> Margins {
+        Margins {
+            top: margin,
+            bottom: margin,
+            left: margin,
+            right: margin,
+        }
+    }
+}
+
+impl Session {
+    /// Render the current page as a PDF, e.g. to generate invoices from HTML.
+    ///
+    /// * Chrome only prints in headless mode; otherwise this fails with the
+    ///   `unsupported operation` [`crate::DriverError::WebDriver`] error.
+    pub async fn print(&self, options: &PrintOptions) -> Result<Vec<u8>> {
+        let body = serde_json::to_value(options).expect("print options are serializable");
+        let encoded = self.command(Method::POST, "/print", Some(body)).await?;
+        screenshot::decode_base64(&encoded, "print page")
+    }
+}
@@ -0,0 +1,709 @@
+use reqwest::Method;
+use serde_json::Value;
+use std::{
+    ffi::{OsStr, OsString},
+    io,
+    net::{Ipv4Addr, TcpListener},
+    path::{Path, PathBuf},
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    time::{Duration, Instant},
+};
+use tracing::{debug, info, warn};
+
+use crate::{
+    error::{DriverError, Result},
+    output::{OutputSink, OutputStream},
+    rt,
+    supervise::{self, RestartEvent, RestartPolicy, Supervision},
+    DriverInfo,
+};
+
+/// Port chromedriver listens on unless told otherwise
+pub const DEFAULT_DRIVER_PORT: u16 = 9515;
+
+/// Deadline of a single request to the driver
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Free ports tried by `port(0)` before giving up on a driver that keeps quitting
+const FREE_PORT_ATTEMPTS: u32 = 3;
+
+/// Delay between readiness checks
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A running chromedriver.
+///
+/// * Created through [`DriverProcess::start`] or [`DriverProcess::builder`].
+/// * [`DriverProcess::stop`] terminates it; so does dropping the handle, e.g. when a test
+///   panics, unless it was [`detach`](DriverProcess::detach)ed.
+/// * Terminating also kills the Chrome instances the driver launched.
+#[derive(Debug)]
+pub struct DriverProcess {
+    child: Arc<Mutex<Child>>,
+    launcher: Arc<Launcher>,
+    port: u16,
+    base_url: String,
+    client: reqwest::Client,
+    shutdown_grace: Duration,
+    /// Set once the driver is meant to exit, so it isn't restarted
+    stopping: Arc<AtomicBool>,
+    detached: bool,
+}
+
+/// Everything needed to (re)spawn the driver
+#[derive(Debug, Clone)]
+pub(crate) struct Launcher {
+    driver_path: PathBuf,
+    port: u16,
+    args: DriverArgs,
+    environment: Environment,
+    output: OutputSink,
+    /// Output goes straight to the log file instead of through this process
+    detachable: bool,
+}
+
+impl Launcher {
+    /// Spawn the driver in its own process group, forwarding its output
+    pub(crate) fn spawn(&self) -> Result<Child> {
+        let mut command = Command::new(&self.driver_path);
+        command
+            .arg(format!("--port={}", self.port))
+            .args(self.args.to_args())
+            .stdin(Stdio::null());
+        if self.detachable {
+            let (stdout, stderr) = self.output.direct()?;
+            command.stdout(stdout).stderr(stderr);
+        } else {
+            command.stdout(Stdio::piped()).stderr(Stdio::piped());
+        }
+        self.environment.apply(&mut command);
+        // Own process group, so Chrome children can be killed along with the driver
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut command, 0);
+        let mut child = command
+            .spawn()
+            .map_err(|e| DriverError::io(format!("starting {}", self.driver_path.display()), e))?;
+        if let (Some(stdout), Some(stderr)) = (child.stdout.take(), child.stderr.take()) {
+            self.output.attach(stdout, stderr)?;
+        }
+        Ok(child)
+    }
+}
+
+/// Environment and working directory of the driver child
+#[derive(Debug, Clone, Default)]
+struct Environment {
+    /// Start from an empty environment instead of inheriting this process's
+    clear: bool,
+    /// Variables to set (`Some`) or remove (`None`), in call order
+    vars: Vec<(OsString, Option<OsString>)>,
+    current_dir: Option<PathBuf>,
+}
+
+impl Environment {
+    fn apply(&self, command: &mut Command) {
+        if self.clear {
+            command.env_clear();
+        }
+        for (key, value) in &self.vars {
+            match value {
+                Some(value) => command.env(key, value),
+                None => command.env_remove(key),
+            };
+        }
+        if let Some(dir) = &self.current_dir {
+            command.current_dir(dir);
+        }
+    }
+}
+
+/// Command-line flags passed to a spawned chromedriver.
+///
+/// * `--port` is set by [`DriverProcessBuilder::port`]; don't repeat it in `extra`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DriverArgs {
+    /// `--verbose`: log everything the driver does
+    pub verbose: bool,
+    /// `--log-path`: write the driver's log to this file instead of stderr
+    pub log_path: Option<PathBuf>,
+    /// `--allowed-ips`: remote clients allowed to connect, besides localhost
+    pub allowed_ips: Vec<String>,
+    /// `--allowed-origins`: origins allowed to make requests, e.g. `*`
+    pub allowed_origins: Vec<String>,
+    /// `--whitelisted-ips`: the name of `--allowed-ips` before ChromeDriver 94
+    pub whitelisted_ips: Vec<String>,
+    /// Any other flags, passed verbatim after the typed ones
+    pub extra: Vec<String>,
+}
+
+impl DriverArgs {
+    /// The flags in command-line form
+    pub fn to_args(&self) -> Vec<OsString> {
+        let mut args = Vec::new();
+        if self.verbose {
+            args.push(OsString::from("--verbose"));
+        }
+        if let Some(path) = &self.log_path {
+            let mut arg = OsString::from("--log-path=");
+            arg.push(path);
+            args.push(arg);
+        }
+        let lists = [
+            ("--allowed-ips", &self.allowed_ips),
+            ("--allowed-origins", &self.allowed_origins),
+            ("--whitelisted-ips", &self.whitelisted_ips),
+        ];
+        for (flag, values) in lists {
+            if !values.is_empty() {
+                args.push(format!("{flag}={}", values.join(",")).into());
+            }
+        }
+        args.extend(self.extra.iter().map(OsString::from));
+        args
+    }
+}
+
+/// Fluent builder for [`DriverProcess`]
+#[derive(Debug, Clone)]
+pub struct DriverProcessBuilder {
+    driver_path: PathBuf,
+    port: u16,
+    args: DriverArgs,
+    environment: Environment,
+    ready_timeout: Duration,
+    shutdown_grace: Duration,
+    output: OutputSink,
+    supervision: Option<Supervision>,
+    detachable: bool,
+}
+
+impl DriverProcessBuilder {
+    /// Listen on `port` (default [`DEFAULT_DRIVER_PORT`]).
+    ///
+    /// * `0` picks a free ephemeral port, so parallel test shards never collide; read it back
+    ///   from [`DriverProcess::port`].
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Pass these flags to the driver, replacing earlier [`args`](Self::args) and
+    /// [`arg`](Self::arg) calls
+    pub fn args(mut self, args: DriverArgs) -> Self {
+        self.args = args;
+        self
+    }
+
+    /// Pass one more flag verbatim, e.g. `--disable-dev-shm-usage`
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.extra.push(arg.into());
+        self
+    }
+
+    /// Accept commands from any host and origin, so a Selenium Grid node on another machine
+    /// can relay sessions to this driver.
+    ///
+    /// * Keeps `allowed_ips`/`allowed_origins` already set through [`args`](Self::args), to
+    ///   restrict access to known nodes.
+    /// * Exposes the driver to the network: only use it on trusted hosts.
+    pub fn grid_relay(mut self) -> Self {
+        if self.args.allowed_ips.is_empty() {
+            // An empty list allows every remote IP
+            self.args.extra.push("--allowed-ips=".into());
+        }
+        if self.args.allowed_origins.is_empty() {
+            self.args.allowed_origins.push("*".into());
+        }
+        self
+    }
+
+    /// Set an environment variable for the driver, e.g. `DISPLAY` for an Xvfb server.
+    ///
+    /// * Chrome inherits the driver's environment, so this reaches the browser too.
+    pub fn env(mut self, key: impl AsRef<OsStr>, value: impl AsRef<OsStr>) -> Self {
+        let value = Some(value.as_ref().to_owned());
+        self.environment.vars.push((key.as_ref().to_owned(), value));
+        self
+    }
+
+    /// Set several environment variables, see [`env`](Self::env)
+    pub fn envs<K, V>(mut self, vars: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+    {
+        for (key, value) in vars {
+            self = self.env(key, value);
+        }
+        self
+    }
+
+    /// Don't pass the variable `key` on to the driver
+    pub fn env_remove(mut self, key: impl AsRef<OsStr>) -> Self {
+        self.environment.vars.push((key.as_ref().to_owned(), None));
+        self
+    }
+
+    /// Start the driver with an empty environment instead of inheriting this process's.
+    ///
+    /// * Variables set through [`env`](Self::env) are still passed, whenever they were set.
+    /// * Chrome may need `HOME`, `PATH` or `DISPLAY`; set them again if so.
+    pub fn env_clear(mut self) -> Self {
+        self.environment.clear = true;
+        self.environment.vars.retain(|(_, value)| value.is_some());
+        self
+    }
+
+    /// Run the driver in `dir`.
+    ///
+    /// * Relative paths in [`DriverArgs`], such as `log_path`, resolve against it.
+    pub fn current_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.environment.current_dir = Some(dir.as_ref().to_owned());
+        self
+    }
+
+    /// How long [`start`](Self::start) waits for the driver to report ready (default 20s)
+    pub fn ready_timeout(mut self, timeout: Duration) -> Self {
+        self.ready_timeout = timeout;
+        self
+    }
+
+    /// How long [`DriverProcess::shutdown`] waits for a clean exit before killing the
+    /// driver (default 5s)
+    pub fn shutdown_grace(mut self, grace: Duration) -> Self {
+        self.shutdown_grace = grace;
+        self
+    }
+
+    /// Receive every line the driver prints, e.g. to forward it into the application's logs.
+    ///
+    /// * Lines are also emitted as `tracing` `debug` events with target `chromedriver`.
+    pub fn on_output(
+        mut self,
+        callback: impl Fn(OutputStream, &str) + Send + Sync + 'static,
+    ) -> Self {
+        self.output.set_callback(callback);
+        self
+    }
+
+    /// Also write the driver's output to the file at `path`.
+    ///
+    /// * [`start`](Self::start) replaces its contents; automatic restarts append to it.
+    pub fn log_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.output.set_file(path.as_ref());
+        self
+    }
+
+    /// Let the driver outlive the application after [`DriverProcess::detach`].
+    ///
+    /// * Its output is written straight to the [`log_file`](Self::log_file), or discarded
+    ///   without one, instead of passing through this process, which would leave the
+    ///   driver writing into a closed pipe once the application exits.
+    /// * [`on_output`](Self::on_output) callbacks and `tracing` events then receive nothing.
+    pub fn detachable(mut self) -> Self {
+        self.detachable = true;
+        self
+    }
+
+    /// Restart the driver when it exits unexpectedly, see [`RestartPolicy`].
+    ///
+    /// * Restarts reuse the port, so the [`base_url`](DriverProcess::base_url) stays valid.
+    /// * Each one is logged as a warning and reported to [`on_restart`](Self::on_restart).
+    pub fn restart(mut self, policy: RestartPolicy) -> Self {
+        self.supervision
+            .get_or_insert_with(Supervision::default)
+            .policy = policy;
+        self
+    }
+
+    /// Receive an event after each automatic restart; implies [`restart`](Self::restart)
+    /// with the default policy unless one is set
+    pub fn on_restart(mut self, callback: impl Fn(&RestartEvent) + Send + Sync + 'static) -> Self {
+        self.supervision
+            .get_or_insert_with(Supervision::default)
+            .set_callback(callback);
+        self
+    }
+
+    /// Launch the driver and wait until its `/status` endpoint reports ready.
+    ///
+    /// * Fails with [`DriverError::DriverExited`] if the driver quits during startup
+    ///   (e.g. the port is taken), and with [`DriverError::Timeout`] if it never gets ready.
+    /// * With [`port(0)`](Self::port), a driver that quits during startup is retried on
+    ///   another free port, in case another process took the first one.
+    pub async fn start(self) -> Result<DriverProcess> {
+        let client = reqwest::Client::builder()
+            .no_proxy()
+            .build()
+            .map_err(|e| DriverError::Config(format!("building HTTP client: {e}")))?;
+        // A relative driver path would otherwise resolve against `current_dir`
+        let driver_path = if self.environment.current_dir.is_some() {
+            std::path::absolute(&self.driver_path).map_err(|e| {
+                DriverError::io(format!("resolving {}", self.driver_path.display()), e)
+            })?
+        } else {
+            self.driver_path
+        };
+        self.output.truncate()?;
+        let template = Launcher {
+            driver_path,
+            port: self.port,
+            args: self.args,
+            environment: self.environment,
+            output: self.output,
+            detachable: self.detachable,
+        };
+
+        let attempts = if self.port == 0 {
+            FREE_PORT_ATTEMPTS
+        } else {
+            1
+        };
+        let mut attempt = 1;
+        let process = loop {
+            // 1️⃣ Probe the port, since another driver answering `/status` there would look
+            //    ready; nothing reserves it, so it can still be taken before the driver binds it
+            let port = match self.port {
+                0 => free_port()?,
+                port => {
+                    TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+                        .map_err(|e| DriverError::io(format!("port {port} is unavailable"), e))?;
+                    port
+                }
+            };
+
+            // 2️⃣ Launch it and wait for it to get ready
+            let launcher = Arc::new(Launcher {
+                port,
+                ..template.clone()
+            });
+            let mut process = DriverProcess {
+                child: Arc::new(Mutex::new(launcher.spawn()?)),
+                launcher,
+                port,
+                base_url: format!("http://127.0.0.1:{port}"),
+                client: client.clone(),
+                shutdown_grace: self.shutdown_grace,
+                stopping: Arc::default(),
+                detached: false,
+            };
+            // Dropping the half-started process kills it
+            match process.wait_until_ready(self.ready_timeout).await {
+                Ok(()) => break process,
+                Err(DriverError::DriverExited { status, .. }) if attempt < attempts => {
+                    debug!("chromedriver exited on port {port} ({status}), trying another port");
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        };
+        info!(
+            "🚀 chromedriver ready at {} (pid {})",
+            process.base_url,
+            process.pid()
+        );
+
+        // 3️⃣ Watch it if it should be kept alive
+        if let Some(supervision) = self.supervision {
+            supervise::watch(
+                supervision,
+                process.child.clone(),
+                process.launcher.clone(),
+                process.stopping.clone(),
+            )?;
+        }
+        Ok(process)
+    }
+}
+
+impl DriverProcess {
+    /// Configure a driver process for an installed driver
+    pub fn builder(driver: &DriverInfo) -> DriverProcessBuilder {
+        DriverProcessBuilder {
+            driver_path: PathBuf::from(&driver.driver_path),
+            port: DEFAULT_DRIVER_PORT,
+            args: DriverArgs::default(),
+            environment: Environment::default(),
+            ready_timeout: Duration::from_secs(20),
+            shutdown_grace: Duration::from_secs(5),
+            output: OutputSink::default(),
+            supervision: None,
+            detachable: false,
+        }
+    }
+
+    /// Launch `driver` on [`DEFAULT_DRIVER_PORT`]
+    pub async fn start(driver: &DriverInfo) -> Result<DriverProcess> {
+        DriverProcess::builder(driver).start().await
+    }
+
+    /// Port the driver listens on
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// WebDriver endpoint, e.g. `http://127.0.0.1:9515`
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// OS process id; changes when the driver is restarted
+    pub fn pid(&self) -> u32 {
+        self.child().id()
+    }
+
+    /// Path of the running executable
+    pub fn driver_path(&self) -> &Path {
+        &self.launcher.driver_path
+    }
+
+    /// Poll `GET /status` until it reports `ready: true`
+    async fn wait_until_ready(&mut self, timeout: Duration) -> Result<()> {
+        let started = Instant::now();
+        loop {
+            let exited = self.child().try_wait();
+            if let Some(status) =
+                exited.map_err(|e| DriverError::io(format!("watching pid {}", self.pid()), e))?
+            {
+                return Err(DriverError::DriverExited {
+                    path: self.launcher.driver_path.clone(),
+                    status,
+                });
+            }
+            let ready = self
+                .command(Method::GET, "/status")
+                .await
+                .is_some_and(|status| status["value"]["ready"] == true);
+            if ready {
+                return Ok(());
+            }
+            if started.elapsed() >= timeout {
+                return Err(DriverError::Timeout {
+                    operation: format!("waiting for chromedriver at {}", self.base_url),
+                    after: timeout,
+                });
+            }
+            rt::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Let the driver outlive this handle, returning its pid.
+    ///
+    /// * To outlive the application too, the driver must have been started
+    ///   [`detachable`](DriverProcessBuilder::detachable); otherwise its output is still
+    ///   piped into this process and it fails on its next write once the application exits.
+    /// * Automatic restarts end with the handle.
+    pub fn detach(mut self) -> u32 {
+        if !self.launcher.detachable {
+            warn!(
+                "⚠️ detaching chromedriver (pid {}) whose output is piped into this process; \
+                 start it with `detachable()` so it survives the application",
+                self.pid()
+            );
+        }
+        self.stopping.store(true, Ordering::SeqCst);
+        self.detached = true;
+        self.pid()
+    }
+
+    /// Terminate the driver and wait for it to exit
+    pub async fn stop(mut self) -> Result<()> {
+        self.stopping.store(true, Ordering::SeqCst);
+        let pid = self.pid();
+        let context = |e| DriverError::io(format!("stopping chromedriver (pid {pid})"), e);
+        self.kill().map_err(context)?;
+        self.wait_for_exit(Duration::MAX).await.map_err(context)?;
+        info!("🛑 chromedriver at {} stopped", self.base_url);
+        Ok(())
+    }
+
+    /// Stop the driver cleanly, returning its exit status.
+    ///
+    /// 1. Deletes the active sessions, so Chrome quits and removes its temporary profile.
+    /// 2. Asks the driver to exit through `GET /shutdown`, plus `SIGTERM` on Unix.
+    /// 3. Kills it along with its browsers if it is still running after the
+    ///    [`shutdown_grace`](DriverProcessBuilder::shutdown_grace) period.
+    pub async fn shutdown(mut self) -> Result<ExitStatus> {
+        self.stopping.store(true, Ordering::SeqCst);
+        let pid = self.pid();
+        let context = |e| DriverError::io(format!("shutting down chromedriver (pid {pid})"), e);
+
+        // 1️⃣ End the sessions
+        let sessions = self.command(Method::GET, "/sessions").await;
+        let ids = sessions
+            .as_ref()
+            .and_then(|sessions| sessions["value"].as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|session| session["id"].as_str());
+        for id in ids {
+            debug!("closing session {id}");
+            self.command(Method::DELETE, &format!("/session/{id}"))
+                .await;
+        }
+
+        // 2️⃣ Ask the driver to exit, giving `/shutdown` half the grace period before SIGTERM
+        let grace = self.shutdown_grace;
+        let asked = self.command(Method::GET, "/shutdown").await.is_some();
+        let mut status = if asked {
+            self.wait_for_exit(grace / 2).await.map_err(context)?
+        } else {
+            None
+        };
+        #[cfg(unix)]
+        if status.is_none() && self.child().try_wait().map_err(context)?.is_none() {
+            // SAFETY: plain syscall on our own, not yet reaped child
+            unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM) };
+        }
+
+        // 3️⃣ Escalate once the grace period is over
+        if status.is_none() {
+            let remaining = if asked { grace - grace / 2 } else { grace };
+            status = self.wait_for_exit(remaining).await.map_err(context)?;
+        }
+        let status = match status {
+            Some(status) => status,
+            None => {
+                warn!("⚠️ chromedriver (pid {pid}) still running after {grace:?}, killing it");
+                self.kill().map_err(context)?;
+                self.wait_for_exit(Duration::MAX)
+                    .await
+                    .map_err(context)?
+                    .expect("waited without a deadline")
+            }
+        };
+        info!("🛑 chromedriver at {} exited ({status})", self.base_url);
+        Ok(status)
+    }
+}
+
+impl DriverProcess {
+    /// The current child process
+    fn child(&self) -> MutexGuard<'_, Child> {
+        supervise::lock(&self.child)
+    }
+
+    /// Send a bodiless WebDriver command, returning the JSON response if there is one
+    async fn command(&self, method: Method, path: &str) -> Option<Value> {
+        let (client, url) = (self.client.clone(), format!("{}{path}", self.base_url));
+        rt::io(async move {
+            let response = client
+                .request(method, url)
+                .timeout(COMMAND_TIMEOUT)
+                .send()
+                .await
+                .ok()?;
+            serde_json::from_str(&response.text().await.ok()?).ok()
+        })
+        .await
+    }
+
+    /// Wait up to `timeout` for the driver to exit
+    async fn wait_for_exit(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        let started = Instant::now();
+        loop {
+            if let Some(status) = self.child().try_wait()? {
+                return Ok(Some(status));
+            }
+            if started.elapsed() >= timeout {
+                return Ok(None);
+            }
+            rt::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    /// Kill the driver together with the browsers it started
+    fn kill(&self) -> io::Result<()> {
+        let mut child = self.child();
+        if child.try_wait()?.is_some() {
+            return Ok(());
+        }
+        #[cfg(unix)]
+        {
+            // SAFETY: plain syscall; the group id is the driver's pid, see `process_group(0)`
+            if unsafe { libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL) } == 0 {
+                return Ok(());
+            }
+        }
+        #[cfg(windows)]
+        {
+            let pid = child.id().to_string();
+            let killed = Command::new("taskkill")
+                .args(["/T", "/F", "/PID", &pid])
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status();
+            if killed.is_ok_and(|status| status.success()) {
+                return Ok(());
+            }
+        }
+        child.kill()
+    }
+}
+
+impl Drop for DriverProcess {
+    fn drop(&mut self) {
+        self.stopping.store(true, Ordering::SeqCst);
+        if !self.detached && self.kill().is_ok() {
+            let _ = self.child().wait();
+        }
+    }
+}
+
+/// An ephemeral port nothing listens on right now, as assigned by the OS
+fn free_port() -> Result<u16> {
+    TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .map_err(|e| DriverError::io("finding a free port", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_args_are_empty() {
+        assert!(DriverArgs::default().to_args().is_empty());
+    }
+
+    #[test]
+    fn renders_typed_flags_before_extra_ones() {
+        let args = DriverArgs {
+            verbose: true,
+            log_path: Some(PathBuf::from("/tmp/chromedriver.log")),
+            allowed_ips: vec!["10.0.0.1".into(), "10.0.0.2".into()],
+            allowed_origins: vec!["*".into()],
+            whitelisted_ips: vec!["".into()],
+            extra: vec!["--readable-timestamp".into(), "--append-log".into()],
+        };
+        assert_eq!(
+            args.to_args(),
+            [
+                "--verbose",
+                "--log-path=/tmp/chromedriver.log",
+                "--allowed-ips=10.0.0.1,10.0.0.2",
+                "--allowed-origins=*",
+                "--whitelisted-ips=",
+                "--readable-timestamp",
+                "--append-log",
+            ]
+            .map(OsString::from)
+        );
+    }
+
+    #[test]
+    fn omits_empty_lists() {
+        let args = DriverArgs {
+            allowed_origins: vec!["https://example.com".into()],
+            ..DriverArgs::default()
+        };
+        assert_eq!(
+            args.to_args(),
+            [OsString::from("--allowed-origins=https://example.com")]
+        );
+    }
+}
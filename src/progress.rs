@@ -0,0 +1,188 @@
+use std::{fmt, sync::Arc};
+
+/// Receiver of download and extraction progress
+#[derive(Clone)]
+pub(crate) enum Progress {
+    /// User callback receiving `(bytes_downloaded, total_bytes)`
+    Callback(Arc<dyn Fn(u64, Option<u64>) + Send + Sync>),
+    /// Built-in terminal progress bar
+    #[cfg(feature = "progress-bar")]
+    Bar(Arc<bar::TerminalBar>),
+}
+
+impl Progress {
+    pub(crate) fn new(callback: impl Fn(u64, Option<u64>) + Send + Sync + 'static) -> Self {
+        Progress::Callback(Arc::new(callback))
+    }
+
+    #[cfg(feature = "progress-bar")]
+    pub(crate) fn bar() -> Self {
+        Progress::Bar(Arc::default())
+    }
+
+    /// Report the current state of a download
+    pub(crate) fn report(&self, downloaded: u64, total: Option<u64>) {
+        match self {
+            Progress::Callback(callback) => callback(downloaded, total),
+            #[cfg(feature = "progress-bar")]
+            Progress::Bar(bar) => bar.download(downloaded, total),
+        }
+    }
+
+    /// Report that extraction started (`false`) or finished (`true`)
+    #[cfg_attr(not(feature = "progress-bar"), allow(unused_variables))]
+    pub(crate) fn extracting(&self, finished: bool) {
+        match self {
+            Progress::Callback(_) => {}
+            #[cfg(feature = "progress-bar")]
+            Progress::Bar(bar) => bar.extract(finished),
+        }
+    }
+}
+
+impl fmt::Debug for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Progress::Callback(_) => f.write_str("Progress::Callback(..)"),
+            #[cfg(feature = "progress-bar")]
+            Progress::Bar(_) => f.write_str("Progress::Bar"),
+        }
+    }
+}
+
+#[cfg(feature = "progress-bar")]
+mod bar {
+    use std::{
+        io::{self, Write},
+        sync::Mutex,
+        time::{Duration, Instant},
+    };
+
+    const WIDTH: usize = 30;
+    const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+    /// Single-line progress bar on stderr
+    #[derive(Default)]
+    pub(crate) struct TerminalBar {
+        state: Mutex<State>,
+    }
+
+    #[derive(Default)]
+    struct State {
+        started: Option<(Instant, u64)>,
+        last_downloaded: u64,
+        last_draw: Option<Instant>,
+    }
+
+    impl State {
+        /// Bytes per second of the current attempt, seeing `downloaded` bytes at `now`.
+        ///
+        /// * Speed only counts bytes of this attempt, not a resumed prefix.
+        /// * A count going backwards means a new attempt, e.g. a failover mirror
+        ///   restarting at 0, so the measurement starts over.
+        fn speed(&mut self, now: Instant, downloaded: u64) -> f64 {
+            if downloaded < self.last_downloaded {
+                self.started = None;
+            }
+            self.last_downloaded = downloaded;
+            let (started, offset) = *self.started.get_or_insert((now, downloaded));
+            let elapsed = now.saturating_duration_since(started).as_secs_f64();
+            if elapsed > 0.0 {
+                downloaded.saturating_sub(offset) as f64 / elapsed
+            } else {
+                0.0
+            }
+        }
+    }
+
+    impl TerminalBar {
+        pub(crate) fn download(&self, downloaded: u64, total: Option<u64>) {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let speed = state.speed(now, downloaded);
+            let finished = total == Some(downloaded);
+            if !finished
+                && state
+                    .last_draw
+                    .is_some_and(|last| now - last < REDRAW_INTERVAL)
+            {
+                return;
+            }
+            state.last_draw = Some(now);
+
+            let line = match total {
+                Some(total) if total > 0 => {
+                    // Servers may under-report Content-Length
+                    let ratio = (downloaded as f64 / total as f64).min(1.0);
+                    let filled = ((ratio * WIDTH as f64) as usize).min(WIDTH);
+                    let eta = if speed > 0.0 {
+                        format!(
+                            "{}s",
+                            (total.saturating_sub(downloaded) as f64 / speed).ceil()
+                        )
+                    } else {
+                        "?".to_string()
+                    };
+                    format!(
+                        "⬇️ [{}{}] {:>3}% {}/{} {}/s ETA {eta}",
+                        "#".repeat(filled),
+                        "-".repeat(WIDTH - filled),
+                        (ratio * 100.0) as u32,
+                        megabytes(downloaded),
+                        megabytes(total),
+                        megabytes(speed as u64),
+                    )
+                }
+                _ => format!("⬇️ {} {}/s", megabytes(downloaded), megabytes(speed as u64)),
+            };
+            let mut stderr = io::stderr().lock();
+            let _ = write!(stderr, "\r\x1b[2K{line}");
+            if finished {
+                let _ = writeln!(stderr);
+                *state = State::default();
+            }
+            let _ = stderr.flush();
+        }
+
+        pub(crate) fn extract(&self, finished: bool) {
+            let mut stderr = io::stderr().lock();
+            let _ = if finished {
+                writeln!(stderr, "\r\x1b[2K📦 Extracted")
+            } else {
+                write!(stderr, "\r\x1b[2K📦 Extracting...")
+            };
+            let _ = stderr.flush();
+        }
+    }
+
+    fn megabytes(bytes: u64) -> String {
+        format!("{:.1} MB", bytes as f64 / 1_000_000.0)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn speed_excludes_the_resumed_prefix() {
+            let mut state = State::default();
+            let start = Instant::now();
+            assert_eq!(state.speed(start, 1_000), 0.0);
+            assert_eq!(state.speed(start + Duration::from_secs(2), 3_000), 1_000.0);
+        }
+
+        #[test]
+        fn speed_restarts_when_the_count_goes_backwards() {
+            let mut state = State::default();
+            let start = Instant::now();
+            // A resumed attempt at 5000 fails, then a failover mirror restarts at 0
+            state.speed(start, 5_000);
+            state.speed(start + Duration::from_secs(1), 6_000);
+            assert_eq!(state.speed(start + Duration::from_secs(2), 0), 0.0);
+            assert_eq!(state.speed(start + Duration::from_secs(4), 500), 250.0);
+            // A restart at a lower, non-zero offset starts over as well
+            assert_eq!(state.speed(start + Duration::from_secs(5), 100), 0.0);
+            assert_eq!(state.speed(start + Duration::from_secs(6), 300), 200.0);
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::ChromeOptions;
+
+/// How the browser reaches the network, attached with [`ChromeOptions::proxy`].
+///
+/// * Proxy addresses are `host:port`, without a scheme.
+/// * Chrome can't log in to proxies that need credentials; use an IP allow-list or a
+///   local forwarding proxy for those.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "proxyType", rename_all = "lowercase")]
+pub enum Proxy {
+    /// No proxy at all, ignoring the system settings
+    Direct,
+    /// The operating system's proxy settings
+    System,
+    /// Discover a proxy through WPAD
+    #[serde(rename = "autodetect")]
+    AutoDetect,
+    /// Read the proxy from a PAC script
+    Pac {
+        #[serde(rename = "proxyAutoconfigUrl")]
+        url: String,
+    },
+    /// Explicit proxies per protocol
+    #[serde(rename_all = "camelCase")]
+    Manual {
+        /// Proxy for `http://` URLs
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        http_proxy: Option<String>,
+        /// Proxy for `https://` URLs
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ssl_proxy: Option<String>,
+        /// SOCKS proxy for all traffic
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        socks_proxy: Option<String>,
+        /// 4 or 5; required with `socks_proxy`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        socks_version: Option<u8>,
+        /// Hosts that bypass the proxy, e.g. `localhost` or `.internal.corp`
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        no_proxy: Vec<String>,
+    },
+}
+
+impl Proxy {
+    /// Send both HTTP and HTTPS traffic through the HTTP proxy at `address`
+    pub fn http(address: impl Into<String>) -> Proxy {
+        let address = address.into();
+        Proxy::Manual {
+            http_proxy: Some(address.clone()),
+            ssl_proxy: Some(address),
+            socks_proxy: None,
+            socks_version: None,
+            no_proxy: Vec::new(),
+        }
+    }
+
+    /// Send all traffic through the SOCKS5 proxy at `address`
+    pub fn socks5(address: impl Into<String>) -> Proxy {
+        Proxy::Manual {
+            http_proxy: None,
+            ssl_proxy: None,
+            socks_proxy: Some(address.into()),
+            socks_version: Some(5),
+            no_proxy: Vec::new(),
+        }
+    }
+
+    /// Read the proxy from the PAC script at `url`
+    pub fn pac(url: impl Into<String>) -> Proxy {
+        Proxy::Pac { url: url.into() }
+    }
+
+    /// Let `hosts` bypass a [`Proxy::Manual`] proxy; other kinds are left unchanged
+    pub fn bypass<I, S>(mut self, hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        if let Proxy::Manual { no_proxy, .. } = &mut self {
+            no_proxy.extend(hosts.into_iter().map(Into::into));
+        }
+        self
+    }
+
+    /// The W3C `proxy` capability, for hand-built [`crate::Session::new`] capabilities
+    pub fn to_capability(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+}
+
+impl ChromeOptions {
+    /// Route the browser's traffic through `proxy`, e.g. `proxy(Proxy::http("10.0.0.5:3128"))`.
+    ///
+    /// * Applies to the whole session; start a new session to switch proxies.
+    pub fn proxy(self, proxy: Proxy) -> Self {
+        self.capability("proxy", proxy.to_capability())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn serializes_parameterless_kinds() {
+        assert_eq!(
+            Proxy::Direct.to_capability(),
+            json!({ "proxyType": "direct" })
+        );
+        assert_eq!(
+            Proxy::System.to_capability(),
+            json!({ "proxyType": "system" })
+        );
+        assert_eq!(
+            Proxy::AutoDetect.to_capability(),
+            json!({ "proxyType": "autodetect" })
+        );
+    }
+
+    #[test]
+    fn serializes_pac_scripts() {
+        assert_eq!(
+            Proxy::pac("http://wpad/proxy.pac").to_capability(),
+            json!({ "proxyType": "pac", "proxyAutoconfigUrl": "http://wpad/proxy.pac" })
+        );
+    }
+
+    #[test]
+    fn serializes_manual_proxies_without_unset_fields() {
+        assert_eq!(
+            Proxy::http("10.0.0.5:3128").to_capability(),
+            json!({
+                "proxyType": "manual",
+                "httpProxy": "10.0.0.5:3128",
+                "sslProxy": "10.0.0.5:3128",
+            })
+        );
+        assert_eq!(
+            Proxy::socks5("127.0.0.1:1080")
+                .bypass(["localhost", ".internal.corp"])
+                .to_capability(),
+            json!({
+                "proxyType": "manual",
+                "socksProxy": "127.0.0.1:1080",
+                "socksVersion": 5,
+                "noProxy": ["localhost", ".internal.corp"],
+            })
+        );
+    }
+
+    #[test]
+    fn bypass_leaves_other_kinds_alone() {
+        assert_eq!(Proxy::System.bypass(["localhost"]), Proxy::System);
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        for proxy in [
+            Proxy::Direct,
+            Proxy::AutoDetect,
+            Proxy::pac("http://wpad/proxy.pac"),
+            Proxy::socks5("127.0.0.1:1080").bypass(["localhost"]),
+        ] {
+            let parsed: Proxy = serde_json::from_value(proxy.to_capability()).unwrap();
+            assert_eq!(parsed, proxy);
+        }
+        let parsed: Proxy =
+            serde_json::from_value(json!({ "proxyType": "manual", "httpProxy": "p:1" })).unwrap();
+        assert_eq!(
+            parsed,
+            Proxy::Manual {
+                http_proxy: Some("p:1".into()),
+                ssl_proxy: None,
+                socks_proxy: None,
+                socks_version: None,
+                no_proxy: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn becomes_the_proxy_capability() {
+        let capabilities = ChromeOptions::new()
+            .proxy(Proxy::Direct)
+            .to_capabilities()
+            .unwrap();
+        assert_eq!(capabilities["proxy"], json!({ "proxyType": "direct" }));
+    }
+}
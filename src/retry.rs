@@ -0,0 +1,218 @@
+use std::{future::Future, time::Duration};
+use tracing::warn;
+
+use crate::{
+    error::{DriverError, Result},
+    rt,
+};
+
+/// How failed requests are retried.
+///
+/// * Applies to the version lookup and to artifact downloads.
+/// * Connection resets and timeouts are always retryable; HTTP errors only
+///   when their status is listed in `retryable_statuses`.
+/// * The delay doubles after every attempt, capped at `max_backoff`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts per request, including the first one
+    pub attempts: u32,
+    /// Delay before the first retry
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between attempts
+    pub max_backoff: Duration,
+    /// HTTP status codes worth retrying
+    pub retryable_statuses: Vec<u16>,
+}
+
+impl RetryPolicy {
+    /// Fail on the first error
+    pub fn none() -> Self {
+        RetryPolicy {
+            attempts: 1,
+            ..RetryPolicy::default()
+        }
+    }
+
+    /// Whether `error` is transient under this policy
+    fn is_retryable(&self, error: &DriverError) -> bool {
+        match error {
+            DriverError::Network { source, .. } => match source.status() {
+                Some(status) => self.retryable_statuses.contains(&status.as_u16()),
+                None => source.is_connect() || source.is_timeout() || source.is_body(),
+            },
+            _ => false,
+        }
+    }
+
+    /// Delay before retry number `retry` (starting at 1)
+    fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry - 1))
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    /// 3 attempts, 500ms initial backoff, at most 8s, retrying 408, 429 and 5xx gateway errors
+    fn default() -> Self {
+        RetryPolicy {
+            attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+            retryable_statuses: vec![408, 429, 500, 502, 503, 504],
+        }
+    }
+}
+
+/// Run `op` until it succeeds, fails permanently or runs out of attempts
+pub(crate) async fn retry<T, F, Fut>(policy: &RetryPolicy, mut op: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Err(e) if attempt < policy.attempts && policy.is_retryable(&e) => {
+                let delay = policy.backoff(attempt);
+                warn!(
+                    "🔁 Retrying in {delay:?} ({attempt}/{}): {e}",
+                    policy.attempts - 1
+                );
+                rt::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    fn policy(initial_ms: u64, max_ms: u64) -> RetryPolicy {
+        RetryPolicy {
+            initial_backoff: Duration::from_millis(initial_ms),
+            max_backoff: Duration::from_millis(max_ms),
+            ..RetryPolicy::default()
+        }
+    }
+
+    /// A network error as reqwest reports a refused connection
+    async fn connect_error() -> DriverError {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        drop(listener);
+        let source = reqwest::get(&url).await.unwrap_err();
+        DriverError::Network { url, source }
+    }
+
+    /// A network error for an HTTP response with `status`
+    async fn status_error(status: u16) -> DriverError {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = [0; 1024];
+            let _ = stream.read(&mut buffer).await.unwrap();
+            let response = format!(
+                "HTTP/1.1 {status} Error\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        });
+        let response = reqwest::get(&url).await.unwrap();
+        let source = response.error_for_status().unwrap_err();
+        DriverError::Network { url, source }
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = policy(500, 8_000);
+        let delays: Vec<_> = (1..=7).map(|retry| policy.backoff(retry)).collect();
+        assert_eq!(
+            delays,
+            [500, 1_000, 2_000, 4_000, 8_000, 8_000, 8_000].map(Duration::from_millis)
+        );
+    }
+
+    #[test]
+    fn backoff_saturates_instead_of_overflowing() {
+        let policy = policy(500, u64::MAX);
+        assert_eq!(policy.backoff(1), Duration::from_millis(500));
+        assert!(policy.backoff(40) >= policy.backoff(33));
+        assert_eq!(policy.backoff(u32::MAX), policy.backoff(200));
+    }
+
+    #[tokio::test]
+    async fn connection_failures_are_retryable() {
+        let policy = RetryPolicy::default();
+        assert!(policy.is_retryable(&connect_error().await));
+    }
+
+    #[tokio::test]
+    async fn only_listed_statuses_are_retryable() {
+        let policy = RetryPolicy::default();
+        assert!(policy.is_retryable(&status_error(503).await));
+        assert!(policy.is_retryable(&status_error(429).await));
+        assert!(!policy.is_retryable(&status_error(404).await));
+
+        let strict = RetryPolicy {
+            retryable_statuses: vec![],
+            ..RetryPolicy::default()
+        };
+        assert!(!strict.is_retryable(&status_error(503).await));
+    }
+
+    #[test]
+    fn other_errors_are_permanent() {
+        let policy = RetryPolicy::default();
+        assert!(!policy.is_retryable(&DriverError::Config("bad".into())));
+        assert!(!policy.is_retryable(&DriverError::Cancelled));
+    }
+
+    #[tokio::test]
+    async fn retries_transient_errors_until_attempts_run_out() {
+        let error = connect_error().await;
+        let DriverError::Network { url, .. } = &error else {
+            unreachable!()
+        };
+        let url = url.clone();
+        let policy = policy(0, 0);
+        let calls = AtomicU32::new(0);
+        let result: Result<()> = retry(&policy, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            let source = reqwest::get(&url).await.unwrap_err();
+            Err(DriverError::Network {
+                url: url.clone(),
+                source,
+            })
+        })
+        .await;
+        assert!(matches!(result, Err(DriverError::Network { .. })));
+        assert_eq!(calls.load(Ordering::SeqCst), policy.attempts);
+    }
+
+    #[tokio::test]
+    async fn stops_at_the_first_success_or_permanent_error() {
+        let policy = policy(0, 0);
+        let calls = AtomicU32::new(0);
+        let result: Result<()> = retry(&policy, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(DriverError::Config("bad".into()))
+        })
+        .await;
+        assert!(matches!(result, Err(DriverError::Config(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let result = retry(&RetryPolicy::none(), || async { Ok(7) }).await;
+        assert_eq!(result.unwrap(), 7);
+    }
+}
@@ -0,0 +1,268 @@
+//! Commands and events over a websocket, shared by the DevTools and BiDi protocols.
+//!
+//! * Replies are routed to their command by `id`; messages without one are events.
+//! * The connection lives on the background runtime until its last handle is dropped.
+
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    io,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+use tokio::{
+    sync::{mpsc, oneshot, Mutex as AsyncMutex},
+    task::JoinHandle,
+};
+use tracing::{debug, warn};
+
+use crate::{
+    error::{DriverError, Result},
+    rt,
+    websocket::{self, Message, WsReader, WsWriter},
+    CdpEvent,
+};
+
+/// Wire format of a connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Protocol {
+    /// Chrome DevTools: errors are `{code, message}` objects
+    Cdp,
+    /// WebDriver BiDi: errors carry W3C codes, like classic WebDriver
+    Bidi,
+}
+
+impl Protocol {
+    fn name(self) -> &'static str {
+        match self {
+            Protocol::Cdp => "DevTools",
+            Protocol::Bidi => "BiDi",
+        }
+    }
+}
+
+/// Methods an event subscription wants; empty for all
+type Filter = Vec<String>;
+
+/// `result` of a command, or its error code and message
+type Reply = std::result::Result<Value, (String, String)>;
+
+/// An open websocket; stops its reader task when dropped
+pub(crate) struct Connection {
+    url: String,
+    protocol: Protocol,
+    shared: Arc<Shared>,
+    reader: JoinHandle<()>,
+}
+
+/// Socket and bookkeeping, shared with the reader task
+struct Shared {
+    writer: AsyncMutex<WsWriter>,
+    next_id: AtomicU64,
+    /// Commands waiting for their reply; `None` once the connection closed
+    pending: Mutex<Option<HashMap<u64, oneshot::Sender<Reply>>>>,
+    subscribers: Mutex<Vec<Subscriber>>,
+}
+
+struct Subscriber {
+    methods: Filter,
+    session_id: Option<String>,
+    sender: mpsc::UnboundedSender<CdpEvent>,
+}
+
+impl Connection {
+    /// Open a websocket to `url` and start routing its messages
+    pub(crate) async fn open(url: &str, protocol: Protocol) -> Result<Arc<Connection>> {
+        let target = url.to_string();
+        let (reader, writer) = rt::background_io(async move { websocket::connect(&target).await })
+            .await
+            .map_err(|e| {
+                DriverError::io(format!("connecting to {} at {url}", protocol.name()), e)
+            })?;
+        let shared = Arc::new(Shared {
+            writer: AsyncMutex::new(writer),
+            next_id: AtomicU64::new(1),
+            pending: Mutex::new(Some(HashMap::new())),
+            subscribers: Mutex::new(Vec::new()),
+        });
+        let reader = rt::spawn(read_messages(Arc::clone(&shared), reader, protocol));
+        Ok(Arc::new(Connection {
+            url: url.to_string(),
+            protocol,
+            shared,
+            reader,
+        }))
+    }
+
+    pub(crate) fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Run `method` and wait for its `result`
+    pub(crate) async fn call(
+        &self,
+        method: &str,
+        params: Value,
+        session_id: Option<&str>,
+    ) -> Result<Value> {
+        let shared = &self.shared;
+        let id = shared.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut message = json!({ "id": id, "method": method, "params": params });
+        if let Some(session_id) = session_id {
+            message["sessionId"] = json!(session_id);
+        }
+
+        let (sender, reply) = oneshot::channel();
+        match shared.pending.lock().unwrap().as_mut() {
+            Some(pending) => pending.insert(id, sender),
+            None => return Err(self.closed(method)),
+        };
+        debug!("{method}");
+        let writer = Arc::clone(shared);
+        let text = message.to_string();
+        let sent =
+            rt::background_io(async move { writer.writer.lock().await.send_text(&text).await })
+                .await;
+        if let Err(e) = sent {
+            if let Some(pending) = shared.pending.lock().unwrap().as_mut() {
+                pending.remove(&id);
+            }
+            return Err(DriverError::io(format!("sending {method}"), e));
+        }
+
+        match reply.await {
+            Ok(Ok(result)) => Ok(result),
+            Ok(Err((error, message))) => Err(match self.protocol {
+                Protocol::Cdp => DriverError::DevTools {
+                    method: method.to_string(),
+                    message: format!("{message} ({error})"),
+                },
+                Protocol::Bidi => DriverError::WebDriver {
+                    command: method.to_string(),
+                    error,
+                    message,
+                },
+            }),
+            Err(_) => Err(self.closed(method)),
+        }
+    }
+
+    /// Receive events whose method is, or belongs to a module in, `methods` (all when
+    /// empty) and whose `sessionId` is `session_id`
+    pub(crate) fn subscribe(
+        &self,
+        methods: Filter,
+        session_id: Option<String>,
+    ) -> mpsc::UnboundedReceiver<CdpEvent> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.shared.subscribers.lock().unwrap().push(Subscriber {
+            methods,
+            session_id,
+            sender,
+        });
+        receiver
+    }
+
+    fn closed(&self, method: &str) -> DriverError {
+        let message = format!("the {} connection is closed", self.protocol.name());
+        DriverError::io(
+            format!("sending {method}"),
+            io::Error::new(io::ErrorKind::NotConnected, message),
+        )
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+/// Route replies to their commands and events to subscribers until the socket closes
+async fn read_messages(shared: Arc<Shared>, mut reader: WsReader, protocol: Protocol) {
+    loop {
+        let text = match reader.next().await {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Ping(payload)) => {
+                let _ = shared.writer.lock().await.pong(&payload).await;
+                continue;
+            }
+            Ok(Message::Close) => break,
+            Err(e) => {
+                warn!("⚠️ {} connection lost: {e}", protocol.name());
+                break;
+            }
+        };
+        let Ok(mut message) = serde_json::from_str::<Value>(&text) else {
+            continue;
+        };
+        if let Some(id) = message["id"].as_u64() {
+            let reply = reply(&mut message, protocol);
+            let sender = shared
+                .pending
+                .lock()
+                .unwrap()
+                .as_mut()
+                .and_then(|pending| pending.remove(&id));
+            if let Some(sender) = sender {
+                let _ = sender.send(reply);
+            }
+            continue;
+        }
+        let event = CdpEvent {
+            method: message["method"].as_str().unwrap_or_default().to_string(),
+            params: message
+                .get_mut("params")
+                .map(Value::take)
+                .unwrap_or_default(),
+            session_id: message["sessionId"].as_str().map(str::to_string),
+        };
+        // Drop subscriptions whose receiver is gone
+        shared.subscribers.lock().unwrap().retain(|subscriber| {
+            let wanted = subscriber.session_id == event.session_id
+                && (subscriber.methods.is_empty()
+                    || subscriber.methods.iter().any(|method| {
+                        event.method == *method
+                            || event
+                                .method
+                                .strip_prefix(method.as_str())
+                                .is_some_and(|rest| rest.starts_with('.'))
+                    }));
+            !wanted || subscriber.sender.send(event.clone()).is_ok()
+        });
+    }
+    debug!("{} connection closed", protocol.name());
+    // Fail waiting commands and end subscriptions
+    shared.pending.lock().unwrap().take();
+    shared.subscribers.lock().unwrap().clear();
+}
+
+fn reply(message: &mut Value, protocol: Protocol) -> Reply {
+    match protocol {
+        Protocol::Cdp => match message.get("error") {
+            Some(error) => Err((
+                error["code"].to_string(),
+                error["message"].as_str().unwrap_or_default().to_string(),
+            )),
+            None => Ok(message
+                .get_mut("result")
+                .map(Value::take)
+                .unwrap_or_default()),
+        },
+        Protocol::Bidi => match message["type"].as_str() {
+            Some("error") => Err((
+                message["error"]
+                    .as_str()
+                    .unwrap_or("unknown error")
+                    .to_string(),
+                message["message"].as_str().unwrap_or_default().to_string(),
+            )),
+            _ => Ok(message
+                .get_mut("result")
+                .map(Value::take)
+                .unwrap_or_default()),
+        },
+    }
+}
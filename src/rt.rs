@@ -0,0 +1,140 @@
+//! Executor glue keeping the public futures runtime-neutral.
+//!
+//! * With the `tokio-runtime` feature (default) work runs on the caller's tokio runtime.
+//! * Without it, network I/O and blocking work run on a private background runtime;
+//!   its `JoinHandle`s can be awaited from any executor (async-std, smol, ...).
+//! * Websocket connections always live on that background runtime.
+
+use std::{
+    ffi::OsStr,
+    future::{poll_fn, Future},
+    io,
+    pin::pin,
+    process::{Command, Output},
+    task::Poll,
+    time::Duration,
+};
+use tracing::{instrument::WithSubscriber, Dispatch};
+
+/// Run blocking work (filesystem, zip, child processes) off the async executor
+pub(crate) async fn spawn_blocking<F, T>(f: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    // Keep reporting to the caller's subscriber on the blocking thread
+    let dispatch = tracing::dispatcher::get_default(Dispatch::clone);
+    let f = move || tracing::dispatcher::with_default(&dispatch, f);
+    #[cfg(feature = "tokio-runtime")]
+    let handle = tokio::task::spawn_blocking(f);
+    #[cfg(not(feature = "tokio-runtime"))]
+    let handle = background().spawn_blocking(f);
+
+    join(handle.await)
+}
+
+/// Drive a future that needs tokio's reactor (reqwest, timers)
+pub(crate) async fn io<F>(future: F) -> F::Output
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    #[cfg(feature = "tokio-runtime")]
+    return future.await;
+    #[cfg(not(feature = "tokio-runtime"))]
+    return background_io(future).await;
+}
+
+/// Drive `future` on the background runtime, for sockets that must outlive the caller's
+/// runtime
+pub(crate) async fn background_io<F>(future: F) -> F::Output
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    // Dropping the returned future (timeout, cancellation) stops the task too
+    struct AbortOnDrop<T>(tokio::task::JoinHandle<T>);
+    impl<T> Drop for AbortOnDrop<T> {
+        fn drop(&mut self) {
+            self.0.abort();
+        }
+    }
+    let mut task = AbortOnDrop(spawn(future));
+    join((&mut task.0).await)
+}
+
+/// Start a long-lived task, e.g. a websocket reader, on the background runtime
+pub(crate) fn spawn<F>(future: F) -> tokio::task::JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    background().spawn(future.with_current_subscriber())
+}
+
+/// Wait for `duration` without blocking the executor
+pub(crate) async fn sleep(duration: Duration) {
+    io(tokio::time::sleep(duration)).await
+}
+
+/// Run `future` to completion, or give up with `None` after `duration`
+pub(crate) async fn timeout<F: Future>(duration: Duration, future: F) -> Option<F::Output> {
+    until(future, sleep(duration)).await
+}
+
+/// Run `future` to completion, or give up with `None` once `stop` completes
+pub(crate) async fn until<F: Future>(
+    future: F,
+    stop: impl Future<Output = ()>,
+) -> Option<F::Output> {
+    let mut future = pin!(future);
+    let mut stop = pin!(stop);
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = future.as_mut().poll(cx) {
+            return Poll::Ready(Some(output));
+        }
+        stop.as_mut().poll(cx).map(|()| None)
+    })
+    .await
+}
+
+/// Run `program` with `args` to completion, capturing its output
+pub(crate) async fn command_output(
+    program: impl AsRef<OsStr>,
+    args: &[&str],
+) -> io::Result<Output> {
+    let mut command = Command::new(program);
+    command.args(args);
+    spawn_blocking(move || command.output()).await
+}
+
+/// Propagate a panic from a spawned task to the awaiting caller
+fn join<T>(result: Result<T, tokio::task::JoinError>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(e) => std::panic::resume_unwind(e.into_panic()),
+    }
+}
+
+/// Run `future` to completion on the background runtime from synchronous code, so the
+/// connections it opens outlive the call; panics inside an async runtime
+#[cfg(feature = "blocking")]
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    background().block_on(future)
+}
+
+/// Lazily started runtime shared by all calls when the caller brings no tokio, and by
+/// connections outliving a single call
+fn background() -> &'static tokio::runtime::Runtime {
+    use std::sync::OnceLock;
+
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("chrome-driver-rs")
+            .enable_all()
+            .build()
+            .expect("failed to start chrome-driver-rs background runtime")
+    })
+}
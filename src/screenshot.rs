@@ -0,0 +1,41 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::Method;
+use serde_json::Value;
+use std::{fs, path::Path};
+
+use crate::{
+    error::{DriverError, Result},
+    rt, Session,
+};
+
+impl Session {
+    /// Capture the visible part of the current page as PNG bytes
+    pub async fn screenshot(&self) -> Result<Vec<u8>> {
+        let encoded = self.command(Method::GET, "/screenshot", None).await?;
+        decode_base64(&encoded, "take screenshot")
+    }
+
+    /// Capture the page like [`screenshot`](Self::screenshot) and save the PNG at `path`,
+    /// replacing an existing file
+    pub async fn screenshot_to_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        let png = self.screenshot().await?;
+        let path = path.as_ref().to_owned();
+        rt::spawn_blocking(move || {
+            fs::write(&path, png)
+                .map_err(|e| DriverError::io(format!("writing {}", path.display()), e))
+        })
+        .await
+    }
+}
+
+/// Decode the base64 payload of a screenshot or print response
+pub(crate) fn decode_base64(encoded: &Value, command: &str) -> Result<Vec<u8>> {
+    encoded
+        .as_str()
+        .and_then(|encoded| STANDARD.decode(encoded).ok())
+        .ok_or_else(|| DriverError::WebDriver {
+            command: command.into(),
+            error: "invalid response".into(),
+            message: "the payload is not valid base64".into(),
+        })
+}
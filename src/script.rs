@@ -0,0 +1,109 @@
+use reqwest::Method;
+use serde::{de::DeserializeOwned, ser::SerializeMap, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    element::ELEMENT_KEY,
+    error::{DriverError, Result},
+    Element, Session,
+};
+
+/// Value returned by [`Session::execute_script`]
+#[derive(Debug, Clone)]
+pub struct ScriptValue {
+    session: Session,
+    value: Value,
+}
+
+impl ScriptValue {
+    /// The value as JSON; elements are `{"element-6066-...": id}` references
+    pub fn json(&self) -> &Value {
+        &self.value
+    }
+
+    /// The value as JSON, see [`json`](Self::json)
+    pub fn into_json(self) -> Value {
+        self.value
+    }
+
+    /// Deserialize the value, e.g. into a `Vec<String>` or a `#[derive(Deserialize)]` struct
+    pub fn convert<T: DeserializeOwned>(self) -> Result<T> {
+        serde_json::from_value(self.value).map_err(|e| invalid_result(e.to_string()))
+    }
+
+    /// The element the script returned
+    pub fn element(&self) -> Result<Element> {
+        Element::from_json(&self.session, &self.value)
+    }
+
+    /// The elements the script returned as an array
+    pub fn elements(&self) -> Result<Vec<Element>> {
+        if !self.value.is_array() {
+            return Err(invalid_result(format!("not an array: {}", self.value)));
+        }
+        Element::from_json_list(&self.session, &self.value)
+    }
+}
+
+fn invalid_result(message: String) -> DriverError {
+    DriverError::WebDriver {
+        command: "execute script".into(),
+        error: "invalid response".into(),
+        message,
+    }
+}
+
+impl Serialize for Element {
+    /// As a WebDriver element reference, so elements can be passed to scripts
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(ELEMENT_KEY, self.id())?;
+        map.end()
+    }
+}
+
+impl Session {
+    /// Run `script` as the body of a function in the page and return its result.
+    ///
+    /// * `args` is a tuple, slice or `Vec` of serializable values, `()` for none; the script
+    ///   reads them from `arguments`. A single array argument goes in a 1-tuple, `(list,)`.
+    /// * [`Element`]s are passed as DOM nodes.
+    /// * Returned DOM nodes come back as element references, see [`ScriptValue::element`].
+    /// * A script that throws fails with the `javascript error` [`DriverError::WebDriver`].
+    pub async fn execute_script(&self, script: &str, args: impl Serialize) -> Result<ScriptValue> {
+        self.execute("/execute/sync", script, args).await
+    }
+
+    /// Run `script` like [`execute_script`](Self::execute_script), but wait until it calls
+    /// the callback passed as its last argument, returning the value it was called with.
+    ///
+    /// * Fails with the `script timeout` error past the session's script timeout (30s by
+    ///   default).
+    pub async fn execute_async_script(
+        &self,
+        script: &str,
+        args: impl Serialize,
+    ) -> Result<ScriptValue> {
+        self.execute("/execute/async", script, args).await
+    }
+
+    async fn execute(&self, path: &str, script: &str, args: impl Serialize) -> Result<ScriptValue> {
+        let args = match serde_json::to_value(args) {
+            Ok(Value::Null) => Vec::new(),
+            Ok(Value::Array(args)) => args,
+            Ok(arg) => vec![arg],
+            Err(e) => return Err(DriverError::Config(format!("script arguments: {e}"))),
+        };
+        let value = self
+            .command(
+                Method::POST,
+                path,
+                Some(json!({ "script": script, "args": args })),
+            )
+            .await?;
+        Ok(ScriptValue {
+            session: self.clone(),
+            value,
+        })
+    }
+}
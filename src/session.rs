@@ -0,0 +1,221 @@
+use reqwest::{header::CONTENT_TYPE, Method};
+use serde_json::{json, Value};
+use tracing::{debug, info};
+
+use crate::{
+    error::{DriverError, Result},
+    http, rt, DriverProcess,
+};
+
+/// A W3C WebDriver session, i.e. one browser controlled through a driver.
+///
+/// * Created through [`Session::new`] or [`DriverProcess::new_session`].
+/// * [`Session::quit`] ends it and closes the browser; dropping the handle doesn't, so
+///   the session lives on until the driver stops.
+#[derive(Debug, Clone)]
+pub struct Session {
+    client: reqwest::Client,
+    base_url: String,
+    id: String,
+    capabilities: Value,
+}
+
+/// Readiness reported by a driver's `GET /status`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DriverStatus {
+    /// Whether the driver accepts new sessions
+    pub ready: bool,
+    /// Human-readable state, e.g. `ChromeDriver ready for new sessions.`
+    pub message: String,
+}
+
+impl Session {
+    /// Start a session on the driver at `base_url`, e.g. `http://127.0.0.1:9515`.
+    ///
+    /// * `capabilities` are required of the browser (`alwaysMatch`), e.g.
+    ///   `json!({"browserName": "chrome"})`.
+    /// * Fails with [`DriverError::WebDriver`] if the driver can't start a matching browser.
+    pub async fn new(base_url: &str, capabilities: Value) -> Result<Session> {
+        let client = client()?;
+        let base_url = base_url.trim_end_matches('/').to_string();
+        let body = json!({ "capabilities": { "alwaysMatch": capabilities } });
+        let url = format!("{base_url}/session");
+        let value = send(&client, Method::POST, &url, "POST /session", Some(body)).await?;
+        let id = value["sessionId"]
+            .as_str()
+            .ok_or_else(|| DriverError::WebDriver {
+                command: "POST /session".into(),
+                error: "invalid response".into(),
+                message: "the driver returned no session id".into(),
+            })?
+            .to_string();
+        info!("🌐 session {id} started on {base_url}");
+        Ok(Session {
+            client,
+            base_url,
+            id,
+            capabilities: value["capabilities"].clone(),
+        })
+    }
+
+    /// Session id assigned by the driver
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Capabilities of the started browser, e.g. `browserVersion`
+    pub fn capabilities(&self) -> &Value {
+        &self.capabilities
+    }
+
+    /// WebDriver endpoint of the driver hosting the session
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Readiness of the driver hosting the session
+    pub async fn status(&self) -> Result<DriverStatus> {
+        status(&self.client, &self.base_url).await
+    }
+
+    /// End the session, closing its browser windows
+    pub async fn quit(self) -> Result<()> {
+        self.command(Method::DELETE, "", None).await?;
+        info!("🛑 session {} ended", self.id);
+        Ok(())
+    }
+
+    /// Navigate to `url`, waiting for the page to load
+    pub async fn goto(&self, url: &str) -> Result<()> {
+        self.command(Method::POST, "/url", Some(json!({ "url": url })))
+            .await
+            .map(drop)
+    }
+
+    /// Go one page back in the history
+    pub async fn back(&self) -> Result<()> {
+        self.command(Method::POST, "/back", None).await.map(drop)
+    }
+
+    /// Go one page forward in the history
+    pub async fn forward(&self) -> Result<()> {
+        self.command(Method::POST, "/forward", None).await.map(drop)
+    }
+
+    /// Reload the current page
+    pub async fn refresh(&self) -> Result<()> {
+        self.command(Method::POST, "/refresh", None).await.map(drop)
+    }
+
+    /// URL of the current page
+    pub async fn current_url(&self) -> Result<String> {
+        let url = self.command(Method::GET, "/url", None).await?;
+        Ok(url.as_str().unwrap_or_default().to_string())
+    }
+
+    /// Title of the current page
+    pub async fn title(&self) -> Result<String> {
+        let title = self.command(Method::GET, "/title", None).await?;
+        Ok(title.as_str().unwrap_or_default().to_string())
+    }
+
+    /// Send a command for this session; `path` is relative to `/session/{id}`.
+    ///
+    /// * `POST` commands without a `body` send an empty JSON object, as WebDriver requires.
+    pub(crate) async fn command(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Value>,
+    ) -> Result<Value> {
+        let url = format!("{}/session/{}{path}", self.base_url, self.id);
+        // Named like the spec's endpoint table, e.g. `POST /session/{id}/url`
+        let command = format!("{method} /session/{{id}}{path}");
+        send(&self.client, method, &url, &command, body).await
+    }
+}
+
+impl DriverProcess {
+    /// Start a [`Session`] on this driver, see [`Session::new`]
+    pub async fn new_session(&self, capabilities: Value) -> Result<Session> {
+        Session::new(self.base_url(), capabilities).await
+    }
+
+    /// Readiness reported by the driver's `GET /status`
+    pub async fn status(&self) -> Result<DriverStatus> {
+        driver_status(self.base_url()).await
+    }
+}
+
+/// Readiness reported by `GET /status` of the driver at `base_url`
+pub async fn driver_status(base_url: &str) -> Result<DriverStatus> {
+    status(&client()?, base_url.trim_end_matches('/')).await
+}
+
+async fn status(client: &reqwest::Client, base_url: &str) -> Result<DriverStatus> {
+    let url = format!("{base_url}/status");
+    let value = send(client, Method::GET, &url, "GET /status", None).await?;
+    Ok(DriverStatus {
+        ready: value["ready"].as_bool().unwrap_or(false),
+        message: value["message"].as_str().unwrap_or_default().to_string(),
+    })
+}
+
+/// HTTP client for talking to drivers, which are usually local and never behind a proxy
+pub(crate) fn client() -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .no_proxy()
+        .build()
+        .map_err(|e| DriverError::Config(format!("building HTTP client: {e}")))
+}
+
+/// Send a WebDriver command, returning the `value` of its response
+async fn send(
+    client: &reqwest::Client,
+    method: Method,
+    url: &str,
+    command: &str,
+    body: Option<Value>,
+) -> Result<Value> {
+    debug!("{command}");
+    let body = match (body, &method) {
+        (None, &Method::POST) => Some(json!({})),
+        (body, _) => body,
+    };
+    let (client, url, command) = (client.clone(), url.to_string(), command.to_string());
+    rt::io(async move {
+        let mut request = client.request(method, &url);
+        if let Some(body) = &body {
+            request = request
+                .header(CONTENT_TYPE, "application/json")
+                .body(body.to_string());
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|source| http::network(&url, source))?;
+        let success = response.status().is_success();
+        let text = response
+            .text()
+            .await
+            .map_err(|source| http::network(&url, source))?;
+        let mut reply: Value = serde_json::from_str(&text).unwrap_or_default();
+        let value = reply.get_mut("value").map(Value::take).unwrap_or_default();
+        // Errors carry a W3C code such as `no such element`
+        match value["error"].as_str() {
+            Some(error) => Err(DriverError::WebDriver {
+                command,
+                error: error.to_string(),
+                message: value["message"].as_str().unwrap_or_default().to_string(),
+            }),
+            None if !success => Err(DriverError::WebDriver {
+                command,
+                error: "unknown error".into(),
+                message: text,
+            }),
+            None => Ok(value),
+        }
+    })
+    .await
+}
@@ -0,0 +1,89 @@
+use reqwest::Method;
+use serde_json::Value;
+
+use crate::{
+    error::{DriverError, Result},
+    By, Element, Session,
+};
+
+/// Key under which WebDriver serializes shadow root references
+const SHADOW_ROOT_KEY: &str = "shadow-6066-11e4-a52e-4f735466cecf";
+
+/// The shadow root of a web component, found through [`Element::shadow_root`].
+///
+/// * Only [`By::Css`] (and the ids, names and classes built on it), [`By::TagName`] and
+///   link texts work inside shadow roots; [`By::XPath`] doesn't.
+#[derive(Debug, Clone)]
+pub struct ShadowRoot {
+    session: Session,
+    id: String,
+}
+
+impl ShadowRoot {
+    /// Reference assigned by the driver
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// First element of the shadow tree matching `by`
+    pub async fn find_element(&self, by: By) -> Result<Element> {
+        let found = self.command("/element", by).await?;
+        Element::from_json(&self.session, &found)
+    }
+
+    /// All elements of the shadow tree matching `by`, in document order
+    pub async fn find_elements(&self, by: By) -> Result<Vec<Element>> {
+        let found = self.command("/elements", by).await?;
+        Element::from_json_list(&self.session, &found)
+    }
+
+    async fn command(&self, path: &str, by: By) -> Result<Value> {
+        let path = format!("/shadow/{}{path}", self.id);
+        self.session
+            .command(Method::POST, &path, Some(by.to_json()))
+            .await
+    }
+}
+
+impl Element {
+    /// The shadow root this element hosts.
+    ///
+    /// * Fails with the `no such shadow root` [`DriverError::WebDriver`] error for elements
+    ///   without one, and for closed shadow roots.
+    pub async fn shadow_root(&self) -> Result<ShadowRoot> {
+        let root = self.command(Method::GET, "/shadow", None).await?;
+        let id = root[SHADOW_ROOT_KEY]
+            .as_str()
+            .ok_or_else(|| DriverError::WebDriver {
+                command: "get element shadow root".into(),
+                error: "invalid response".into(),
+                message: format!("not a shadow root reference: {root}"),
+            })?;
+        Ok(ShadowRoot {
+            session: self.session().clone(),
+            id: id.to_string(),
+        })
+    }
+}
+
+impl Session {
+    /// Find an element nested in web components, where each locator of `path` but the last
+    /// picks a shadow host inside the previous one's shadow root, e.g.
+    /// `&[By::css("my-app"), By::css("login-form"), By::css("input[name=user]")]`
+    pub async fn find_shadow_element(&self, path: &[By]) -> Result<Element> {
+        let Some((first, rest)) = path.split_first() else {
+            return Err(DriverError::Config(
+                "a shadow element path needs at least one locator".into(),
+            ));
+        };
+        let mut element = self.find_element(first.clone()).await?;
+        for by in rest {
+            element = element
+                .shadow_root()
+                .await?
+                .find_element(by.clone())
+                .await?;
+        }
+        Ok(element)
+    }
+}
@@ -0,0 +1,188 @@
+use std::{
+    fmt,
+    process::{Child, ExitStatus},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    thread,
+    time::Duration,
+};
+use tracing::{info, warn, Dispatch};
+
+use crate::{
+    error::{DriverError, Result},
+    process::Launcher,
+};
+
+/// Delay between checks whether a supervised driver is still running
+const WATCH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How a driver that exits unexpectedly is restarted.
+///
+/// * Restarts stop once `max_restarts` is used up; the driver then stays down.
+/// * The delay before each restart doubles, capped at `max_backoff`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestartPolicy {
+    /// Restarts over the lifetime of the handle
+    pub max_restarts: u32,
+    /// Delay before the first restart
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between restarts
+    pub max_backoff: Duration,
+}
+
+impl RestartPolicy {
+    /// Delay before restart number `restart` (starting at 1)
+    fn backoff(&self, restart: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(restart - 1))
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RestartPolicy {
+    /// 5 restarts, 500ms initial backoff, at most 30s
+    fn default() -> Self {
+        RestartPolicy {
+            max_restarts: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Reported after a supervised driver was restarted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestartEvent {
+    /// Restarts so far, including this one
+    pub restart: u32,
+    /// How the previous process exited
+    pub status: ExitStatus,
+    /// Process id of the new driver
+    pub pid: u32,
+}
+
+/// User callback receiving restart events
+type RestartCallback = Arc<dyn Fn(&RestartEvent) + Send + Sync>;
+
+/// Restart settings of a [`crate::DriverProcessBuilder`]
+#[derive(Clone, Default)]
+pub(crate) struct Supervision {
+    pub(crate) policy: RestartPolicy,
+    callback: Option<RestartCallback>,
+}
+
+impl Supervision {
+    pub(crate) fn set_callback(
+        &mut self,
+        callback: impl Fn(&RestartEvent) + Send + Sync + 'static,
+    ) {
+        self.callback = Some(Arc::new(callback));
+    }
+}
+
+impl fmt::Debug for Supervision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Supervision")
+            .field("policy", &self.policy)
+            .field("callback", &self.callback.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+/// Lock `child`, even if a thread panicked while holding it
+pub(crate) fn lock(child: &Mutex<Child>) -> MutexGuard<'_, Child> {
+    child
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Restart `child` on a background thread whenever it exits before `stopping` is set
+pub(crate) fn watch(
+    supervision: Supervision,
+    child: Arc<Mutex<Child>>,
+    launcher: Arc<Launcher>,
+    stopping: Arc<AtomicBool>,
+) -> Result<()> {
+    // Keep reporting to the caller's subscriber
+    let dispatch = tracing::dispatcher::get_default(Dispatch::clone);
+    let watch = move || {
+        let Supervision { policy, callback } = supervision;
+        let mut restarts = 0;
+        loop {
+            thread::sleep(WATCH_INTERVAL);
+            if stopping.load(Ordering::SeqCst) {
+                return;
+            }
+            let status = match lock(&child).try_wait() {
+                Ok(Some(status)) => status,
+                Ok(None) => continue,
+                Err(_) => return,
+            };
+            if restarts == policy.max_restarts {
+                warn!("⚠️ chromedriver exited ({status}), giving up after {restarts} restarts");
+                return;
+            }
+            restarts += 1;
+            let delay = policy.backoff(restarts);
+            warn!(
+                "⚠️ chromedriver exited ({status}), restarting in {delay:?} ({restarts}/{})",
+                policy.max_restarts
+            );
+            thread::sleep(delay);
+
+            // Checked under the lock: stopping handles kill whichever child they find
+            let mut current = lock(&child);
+            if stopping.load(Ordering::SeqCst) {
+                return;
+            }
+            match launcher.spawn() {
+                Ok(respawned) => {
+                    *current = respawned;
+                    let pid = current.id();
+                    drop(current);
+                    info!("🔁 chromedriver restarted (pid {pid})");
+                    if let Some(callback) = &callback {
+                        callback(&RestartEvent {
+                            restart: restarts,
+                            status,
+                            pid,
+                        });
+                    }
+                }
+                Err(e) => warn!("⚠️ {e}"),
+            }
+        }
+    };
+    thread::Builder::new()
+        .name("chromedriver-supervisor".to_string())
+        .spawn(move || tracing::dispatcher::with_default(&dispatch, watch))
+        .map(drop)
+        .map_err(|e| DriverError::io("starting the driver supervisor", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = RestartPolicy::default();
+        let delays: Vec<_> = (1..=8).map(|restart| policy.backoff(restart)).collect();
+        assert_eq!(
+            delays,
+            [500, 1_000, 2_000, 4_000, 8_000, 16_000, 30_000, 30_000].map(Duration::from_millis)
+        );
+    }
+
+    #[test]
+    fn backoff_saturates_instead_of_overflowing() {
+        let policy = RestartPolicy {
+            max_backoff: Duration::MAX,
+            ..RestartPolicy::default()
+        };
+        assert_eq!(policy.backoff(u32::MAX), policy.backoff(200));
+        assert!(policy.backoff(u32::MAX) > Duration::from_secs(1_000_000));
+    }
+}
@@ -0,0 +1,84 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::{Method, Url};
+use serde_json::json;
+use std::{
+    fs,
+    io::{Cursor, Write},
+    net::IpAddr,
+    path::{Path, PathBuf},
+};
+use tracing::debug;
+use zip::{write::SimpleFileOptions, ZipWriter};
+
+use crate::{
+    error::{DriverError, Result},
+    rt, Element, Session,
+};
+
+impl Element {
+    /// Choose the local file at `path` in this `<input type="file">`.
+    ///
+    /// * A driver on another host (e.g. a Selenium Grid node) can't read local files, so
+    ///   the file is first pushed to it through the `se/file` upload endpoint.
+    pub async fn upload_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref().to_owned();
+        let local = rt::spawn_blocking(move || {
+            fs::canonicalize(&path)
+                .map_err(|e| DriverError::io(format!("reading {}", path.display()), e))
+        })
+        .await?;
+        let target = if self.session().is_local() {
+            local.to_string_lossy().into_owned()
+        } else {
+            self.session().push_file(local).await?
+        };
+        self.send_keys(&target).await
+    }
+}
+
+impl Session {
+    /// Whether the driver runs on this machine and shares its filesystem
+    fn is_local(&self) -> bool {
+        let url = Url::parse(self.base_url()).ok();
+        match url.as_ref().and_then(Url::host_str) {
+            Some("localhost") => true,
+            Some(host) => host
+                .trim_matches(['[', ']'])
+                .parse::<IpAddr>()
+                .is_ok_and(|ip| ip.is_loopback()),
+            None => false,
+        }
+    }
+
+    /// Upload `path` to the driver's host as a zipped file, returning its path there
+    async fn push_file(&self, path: PathBuf) -> Result<String> {
+        let zipped = rt::spawn_blocking(move || zip_file(&path)).await?;
+        debug!("uploading {} bytes to the driver", zipped.len());
+        let body = json!({ "file": STANDARD.encode(zipped) });
+        let remote = self.command(Method::POST, "/se/file", Some(body)).await?;
+        match remote.as_str() {
+            Some(path) if !path.is_empty() => Ok(path.to_string()),
+            _ => Err(DriverError::WebDriver {
+                command: "upload file".into(),
+                error: "invalid response".into(),
+                message: format!("not a file path: {remote}"),
+            }),
+        }
+    }
+}
+
+/// `path` as the single entry of an in-memory zip archive
+fn zip_file(path: &Path) -> Result<Vec<u8>> {
+    let context = |e| DriverError::io(format!("zipping {}", path.display()), e);
+    let contents = fs::read(path).map_err(context)?;
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    zip.start_file(name, SimpleFileOptions::default())
+        .map_err(|e| context(e.into()))?;
+    zip.write_all(&contents).map_err(context)?;
+    let archive = zip.finish().map_err(|e| context(e.into()))?;
+    Ok(archive.into_inner())
+}
@@ -0,0 +1,119 @@
+use std::fmt::{self, Write};
+use tracing::{
+    field::{Field, Visit},
+    span, Dispatch, Event, Level, Metadata, Subscriber,
+};
+
+/// How much an installer prints on its own, for mirroring a CLI's `-q`/`-v` flags.
+///
+/// Every level still reports to the application's `tracing` subscriber.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    /// Print nothing (the default)
+    #[default]
+    Silent,
+    /// Print versions, downloads, installs and warnings to stderr
+    Normal,
+    /// Also print checksum and version checks
+    Verbose,
+}
+
+impl Verbosity {
+    /// Most detailed level printed, `None` for [`Verbosity::Silent`]
+    fn max_level(self) -> Option<Level> {
+        match self {
+            Verbosity::Silent => None,
+            Verbosity::Normal => Some(Level::INFO),
+            Verbosity::Verbose => Some(Level::DEBUG),
+        }
+    }
+
+    /// Subscriber printing this crate's events to stderr and forwarding everything else
+    pub(crate) fn dispatch(self) -> Dispatch {
+        let inner = tracing::dispatcher::get_default(Dispatch::clone);
+        if inner.is::<Printer>() {
+            // Nested call of an installer that is already printing
+            return inner;
+        }
+        match self.max_level() {
+            Some(max_level) => Dispatch::new(Printer { inner, max_level }),
+            None => inner,
+        }
+    }
+}
+
+/// Prints events of this crate up to `max_level`, then hands them on to `inner`
+struct Printer {
+    inner: Dispatch,
+    max_level: Level,
+}
+
+impl Printer {
+    fn prints(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.is_event()
+            && *metadata.level() <= self.max_level
+            && metadata.target().starts_with(env!("CARGO_CRATE_NAME"))
+    }
+}
+
+impl Subscriber for Printer {
+    fn register_callsite(&self, _: &'static Metadata<'static>) -> tracing::subscriber::Interest {
+        tracing::subscriber::Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.prints(metadata) || self.inner.enabled(metadata)
+    }
+
+    fn new_span(&self, attributes: &span::Attributes<'_>) -> span::Id {
+        self.inner.new_span(attributes)
+    }
+
+    fn record(&self, span: &span::Id, values: &span::Record<'_>) {
+        self.inner.record(span, values)
+    }
+
+    fn record_follows_from(&self, span: &span::Id, follows: &span::Id) {
+        self.inner.record_follows_from(span, follows)
+    }
+
+    fn event(&self, event: &Event<'_>) {
+        if self.prints(event.metadata()) {
+            let mut line = Line::default();
+            event.record(&mut line);
+            eprintln!("{}", line.0);
+        }
+        if self.inner.enabled(event.metadata()) {
+            self.inner.event(event);
+        }
+    }
+
+    fn enter(&self, span: &span::Id) {
+        self.inner.enter(span)
+    }
+
+    fn exit(&self, span: &span::Id) {
+        self.inner.exit(span)
+    }
+
+    fn clone_span(&self, id: &span::Id) -> span::Id {
+        self.inner.clone_span(id)
+    }
+
+    fn try_close(&self, id: span::Id) -> bool {
+        self.inner.try_close(id)
+    }
+}
+
+/// An event's message followed by its other fields
+#[derive(Default)]
+struct Line(String);
+
+impl Visit for Line {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        let _ = match field.name() {
+            "message" => write!(self.0, "{value:?}"),
+            name => write!(self.0, " {name}={value:?}"),
+        };
+    }
+}
@@ -0,0 +1,198 @@
+use std::{fmt, path::Path, str::FromStr};
+use tracing::debug;
+
+use crate::{
+    browser::read_chrome_version,
+    error::{DriverError, Result},
+    rt,
+};
+
+/// A ChromeDriver version as reported by `chromedriver --version`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DriverVersion {
+    /// Milestone, e.g. `130`
+    pub major: u32,
+    /// Minor version, `0` for every release so far
+    pub minor: u32,
+    /// Build number, e.g. `6723`
+    pub build: u32,
+    /// Patch number, e.g. `58`
+    pub patch: u32,
+    /// Source the driver was built from, e.g. `a1b2c3d4-refs/branch-heads/6723@{#1234}`
+    pub commit: Option<String>,
+}
+
+impl DriverVersion {
+    /// Parse the `ChromeDriver X.Y.Z.W (commit)` line printed by `--version`
+    pub fn from_output(output: &str) -> Result<Self> {
+        let invalid = || {
+            let first_line = output.trim().lines().next().unwrap_or_default();
+            DriverError::InvalidVersion(first_line.to_string())
+        };
+        let line = output
+            .lines()
+            .find(|line| line.trim_start().starts_with("ChromeDriver"))
+            .ok_or_else(invalid)?;
+        let mut tokens = line.split_whitespace().skip(1);
+        let mut version: DriverVersion = tokens.next().ok_or_else(invalid)?.parse()?;
+        let rest = tokens.collect::<Vec<_>>().join(" ");
+        version.commit = rest
+            .strip_prefix('(')
+            .and_then(|rest| rest.strip_suffix(')'))
+            .map(str::to_string);
+        Ok(version)
+    }
+}
+
+impl FromStr for DriverVersion {
+    type Err = DriverError;
+
+    /// Parse an `A.B.C.D` version string
+    fn from_str(version: &str) -> Result<Self> {
+        let parts: Vec<u32> = version
+            .split('.')
+            .map(str::parse)
+            .collect::<std::result::Result<_, _>>()
+            .map_err(|_| DriverError::InvalidVersion(version.to_string()))?;
+        let [major, minor, build, patch] = parts[..] else {
+            return Err(DriverError::InvalidVersion(version.to_string()));
+        };
+        Ok(DriverVersion {
+            major,
+            minor,
+            build,
+            patch,
+            commit: None,
+        })
+    }
+}
+
+impl fmt::Display for DriverVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}.{}",
+            self.major, self.minor, self.build, self.patch
+        )
+    }
+}
+
+/// Run `driver_path --version` and parse what it reports
+pub async fn driver_version(driver_path: impl AsRef<Path>) -> Result<DriverVersion> {
+    let driver_path = driver_path.as_ref();
+    let output = rt::command_output(driver_path, &["--version"])
+        .await
+        .map_err(|e| DriverError::io(format!("running {}", driver_path.display()), e))?;
+    DriverVersion::from_output(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Whether a driver of `driver_version` can drive Chrome `chrome_version`.
+///
+/// ChromeDriver supports exactly the browser milestone it was released with.
+pub fn is_compatible(driver_version: &str, chrome_version: &str) -> bool {
+    let major = |version: &str| version.split('.').next().map(str::trim).map(str::to_string);
+    major(driver_version).is_some_and(|driver| Some(driver) == major(chrome_version))
+}
+
+/// Fail fast with [`DriverError::IncompatibleVersions`] unless the driver at `driver_path`
+/// supports the browser at `chrome_path`.
+///
+/// * Gives an actionable message instead of chromedriver's "session not created".
+pub async fn verify_compatibility(
+    driver_path: impl AsRef<Path>,
+    chrome_path: impl AsRef<Path>,
+) -> Result<()> {
+    let driver = driver_version(driver_path).await?.to_string();
+    let chrome = read_chrome_version(chrome_path.as_ref())
+        .await
+        .ok_or(DriverError::ChromeNotFound)?;
+    if !is_compatible(&driver, &chrome) {
+        return Err(DriverError::IncompatibleVersions { driver, chrome });
+    }
+    debug!("✅ ChromeDriver {driver} supports Chrome {chrome}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_version_strings() {
+        let version: DriverVersion = "130.0.6723.58".parse().unwrap();
+        assert_eq!(
+            (version.major, version.minor, version.build, version.patch),
+            (130, 0, 6723, 58)
+        );
+        assert_eq!(version.commit, None);
+        assert_eq!(version.to_string(), "130.0.6723.58");
+    }
+
+    #[test]
+    fn rejects_malformed_version_strings() {
+        for version in [
+            "",
+            "130",
+            "130.0.6723",
+            "130.0.6723.58.1",
+            "130.0.x.58",
+            " 130.0.6723.58",
+        ] {
+            assert!(
+                matches!(
+                    version.parse::<DriverVersion>(),
+                    Err(DriverError::InvalidVersion(ref v)) if v == version
+                ),
+                "{version:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn orders_numerically() {
+        let older: DriverVersion = "99.0.4844.51".parse().unwrap();
+        let newer: DriverVersion = "130.0.6723.58".parse().unwrap();
+        assert!(older < newer);
+    }
+
+    #[test]
+    fn parses_version_output() {
+        let version = DriverVersion::from_output(
+            "ChromeDriver 130.0.6723.58 (3a1b2c3d4e5f-refs/branch-heads/6723@{#1234})\n",
+        )
+        .unwrap();
+        assert_eq!(version.to_string(), "130.0.6723.58");
+        assert_eq!(
+            version.commit.as_deref(),
+            Some("3a1b2c3d4e5f-refs/branch-heads/6723@{#1234}")
+        );
+    }
+
+    #[test]
+    fn parses_version_output_without_commit() {
+        let version = DriverVersion::from_output("ChromeDriver 114.0.5735.90").unwrap();
+        assert_eq!(version.to_string(), "114.0.5735.90");
+        assert_eq!(version.commit, None);
+    }
+
+    #[test]
+    fn skips_lines_before_the_version() {
+        let version =
+            DriverVersion::from_output("Starting up...\n  ChromeDriver 130.0.6723.58 (abc)\n")
+                .unwrap();
+        assert_eq!(version.commit.as_deref(), Some("abc"));
+    }
+
+    #[test]
+    fn rejects_other_output() {
+        match DriverVersion::from_output("bash: chromedriver: command not found\n") {
+            Err(DriverError::InvalidVersion(line)) => {
+                assert_eq!(line, "bash: chromedriver: command not found")
+            }
+            other => panic!("expected InvalidVersion, got {other:?}"),
+        }
+        assert!(DriverVersion::from_output("ChromeDriver").is_err());
+        assert!(DriverVersion::from_output("ChromeDriver latest").is_err());
+    }
+}
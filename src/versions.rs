@@ -0,0 +1,106 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::{
+    error::{DriverError, Result},
+    Artifact, DriverManager,
+};
+
+/// A Chrome for Testing release with its download URLs
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AvailableVersion {
+    /// Version string, e.g. `126.0.6478.126`
+    pub version: String,
+    /// Chromium revision the release was built from
+    pub revision: String,
+    /// Per-artifact, per-platform download URLs
+    pub downloads: Vec<Download>,
+}
+
+/// A single downloadable zip of a release
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Download {
+    /// Which artifact the zip contains
+    pub artifact: Artifact,
+    /// Chrome for Testing platform name, e.g. `mac-arm64`
+    pub platform: String,
+    /// Download URL of the zip
+    pub url: String,
+}
+
+impl AvailableVersion {
+    /// Download URL of `artifact` for `platform`, if published
+    pub fn url_for(&self, artifact: Artifact, platform: &str) -> Option<&str> {
+        self.downloads
+            .iter()
+            .find(|d| d.artifact == artifact && d.platform == platform)
+            .map(|d| d.url.as_str())
+    }
+}
+
+#[derive(Deserialize)]
+struct KnownGoodVersions {
+    versions: Vec<RawVersion>,
+}
+
+#[derive(Deserialize)]
+struct RawVersion {
+    version: String,
+    revision: String,
+    #[serde(default)]
+    downloads: HashMap<String, Vec<RawDownload>>,
+}
+
+#[derive(Deserialize)]
+struct RawDownload {
+    platform: String,
+    url: String,
+}
+
+/// List every known-good Chrome for Testing version, oldest first.
+///
+/// * Backed by `known-good-versions-with-downloads.json`.
+/// * Artifacts this crate doesn't know (e.g. `mojojs`) are skipped.
+pub async fn list_available_versions() -> Result<Vec<AvailableVersion>> {
+    DriverManager::builder().build()?.available_versions().await
+}
+
+/// Fetch and flatten `known-good-versions-with-downloads.json` with `manager`'s settings
+pub(crate) async fn fetch_available_versions(
+    manager: &DriverManager,
+) -> Result<Vec<AvailableVersion>> {
+    let json = manager
+        .fetch_metadata("known-good-versions-with-downloads.json")
+        .await?;
+    let known: KnownGoodVersions = serde_json::from_value(json)
+        .map_err(|e| DriverError::VersionLookup(format!("unexpected known-good versions: {e}")))?;
+
+    Ok(known
+        .versions
+        .into_iter()
+        .map(|raw| {
+            let mut downloads: Vec<Download> = raw
+                .downloads
+                .into_iter()
+                .filter_map(|(name, list)| Some((Artifact::from_name(&name)?, list)))
+                .flat_map(|(artifact, list)| {
+                    list.into_iter().map(move |d| Download {
+                        artifact,
+                        platform: d.platform,
+                        url: d.url,
+                    })
+                })
+                .collect();
+            downloads.sort_by(|a, b| {
+                (a.artifact.as_str(), &a.platform).cmp(&(b.artifact.as_str(), &b.platform))
+            });
+            AvailableVersion {
+                version: raw.version,
+                revision: raw.revision,
+                downloads,
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,168 @@
+use std::{
+    future::{Future, IntoFuture},
+    pin::Pin,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    error::{DriverError, Result},
+    rt, By, Element, Session,
+};
+
+/// Polls the page until a condition holds, see [`Session::wait`].
+///
+/// * Gives up with [`DriverError::Timeout`] after `timeout` (default 10s).
+/// * Checks every `interval` (default 250ms).
+#[derive(Debug, Clone)]
+pub struct Wait {
+    session: Session,
+    timeout: Duration,
+    interval: Duration,
+}
+
+/// What [`ElementWait`] waits for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Condition {
+    Present,
+    Displayed,
+    Clickable,
+}
+
+impl Condition {
+    fn as_str(self) -> &'static str {
+        match self {
+            Condition::Present => "present",
+            Condition::Displayed => "displayed",
+            Condition::Clickable => "clickable",
+        }
+    }
+}
+
+/// Waits for an element of the page, returned once found; `.await` it.
+#[derive(Debug, Clone)]
+pub struct ElementWait {
+    wait: Wait,
+    by: By,
+    condition: Condition,
+}
+
+impl Session {
+    /// Wait for something on the page, e.g.
+    /// `session.wait().for_element(By::id("save")).clickable().timeout(d).await`
+    pub fn wait(&self) -> Wait {
+        Wait {
+            session: self.clone(),
+            timeout: Duration::from_secs(10),
+            interval: Duration::from_millis(250),
+        }
+    }
+}
+
+impl Wait {
+    /// Give up after `timeout`
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Check again after `interval`
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Wait until an element matching `by` is in the page
+    pub fn for_element(self, by: By) -> ElementWait {
+        ElementWait {
+            wait: self,
+            by,
+            condition: Condition::Present,
+        }
+    }
+}
+
+impl ElementWait {
+    /// Also wait until the element is visible
+    pub fn displayed(mut self) -> Self {
+        self.condition = Condition::Displayed;
+        self
+    }
+
+    /// Also wait until the element is visible and enabled
+    pub fn clickable(mut self) -> Self {
+        self.condition = Condition::Clickable;
+        self
+    }
+
+    /// See [`Wait::timeout`]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.wait.timeout = timeout;
+        self
+    }
+
+    /// See [`Wait::interval`]
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.wait.interval = interval;
+        self
+    }
+
+    /// Poll until the condition holds, returning the element.
+    ///
+    /// * Missing and stale elements are retried; other command errors end the wait.
+    pub async fn wait(self) -> Result<Element> {
+        let started = Instant::now();
+        let Wait {
+            session,
+            timeout,
+            interval,
+        } = &self.wait;
+        loop {
+            if let Some(element) = retry_missing(self.check(session).await)? {
+                return Ok(element);
+            }
+            if started.elapsed() >= *timeout {
+                return Err(DriverError::Timeout {
+                    operation: format!(
+                        "waiting for {:?} to be {}",
+                        self.by,
+                        self.condition.as_str()
+                    ),
+                    after: *timeout,
+                });
+            }
+            rt::sleep(*interval).await;
+        }
+    }
+
+    /// The element if it meets the condition right now
+    async fn check(&self, session: &Session) -> Result<Option<Element>> {
+        let element = session.find_element(self.by.clone()).await?;
+        let ready = match self.condition {
+            Condition::Present => true,
+            Condition::Displayed => element.is_displayed().await?,
+            Condition::Clickable => element.is_displayed().await? && element.is_enabled().await?,
+        };
+        Ok(ready.then_some(element))
+    }
+}
+
+/// Treat elements that are missing or were just replaced as not ready yet
+fn retry_missing<T>(result: Result<Option<T>>) -> Result<Option<T>> {
+    match result {
+        Err(DriverError::WebDriver { error, .. })
+            if error == "no such element" || error == "stale element reference" =>
+        {
+            Ok(None)
+        }
+        result => result,
+    }
+}
+
+impl IntoFuture for ElementWait {
+    type Output = Result<Element>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Result<Element>> + Send>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.wait())
+    }
+}
@@ -0,0 +1,432 @@
+//! Minimal RFC 6455 websocket client for the browser's DevTools endpoint.
+//!
+//! * Only `ws://`: the endpoints are served by the browser or driver without TLS.
+//! * The reader and writer halves are separate so one task can read while others send.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::Url;
+use sha1::{Digest, Sha1};
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    io,
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpStream,
+    },
+};
+
+/// Appended to the handshake key before hashing, fixed by the RFC
+const ACCEPT_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// Largest message accepted from the server, fragments included; DevTools screenshots and
+/// traces stay well below
+const MAX_MESSAGE: u64 = 1 << 30;
+
+const TEXT: u8 = 0x1;
+const CLOSE: u8 = 0x8;
+const PING: u8 = 0x9;
+const PONG: u8 = 0xA;
+
+/// A message received from the server
+#[derive(Debug)]
+pub(crate) enum Message {
+    Text(String),
+    /// Must be answered with [`WsWriter::pong`]
+    Ping(Vec<u8>),
+    /// The server ended the connection
+    Close,
+}
+
+/// Receiving half of a connection
+pub(crate) struct WsReader {
+    stream: BufReader<OwnedReadHalf>,
+    /// Fragments of a message interrupted by a control frame
+    partial: Vec<u8>,
+    /// Largest message accepted, [`MAX_MESSAGE`] outside tests
+    limit: u64,
+}
+
+/// Sending half of a connection
+pub(crate) struct WsWriter {
+    stream: OwnedWriteHalf,
+}
+
+/// Open a websocket to `url`, e.g. `ws://127.0.0.1:9222/devtools/browser/<id>`
+pub(crate) async fn connect(url: &str) -> io::Result<(WsReader, WsWriter)> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidInput, message);
+    let url = Url::parse(url).map_err(|e| invalid(format!("invalid websocket URL {url}: {e}")))?;
+    if url.scheme() != "ws" {
+        return Err(invalid(format!("only ws:// URLs are supported, got {url}")));
+    }
+    let host = url.host_str().unwrap_or_default();
+    let port = url.port_or_known_default().unwrap_or(80);
+    let mut path = url.path().to_string();
+    if let Some(query) = url.query() {
+        path = format!("{path}?{query}");
+    }
+
+    // 1️⃣ HTTP upgrade
+    // IPv6 hosts come bracketed, as the Host header wants them but connect doesn't
+    let (read, mut write) = TcpStream::connect((host.trim_matches(['[', ']']), port))
+        .await?
+        .into_split();
+    let key = STANDARD.encode([nonce().to_le_bytes(), nonce().to_le_bytes()].concat());
+    let request = format!(
+        "GET {path} HTTP/1.1\r\nHost: {host}:{port}\r\nUpgrade: websocket\r\n\
+         Connection: Upgrade\r\nSec-WebSocket-Key: {key}\r\nSec-WebSocket-Version: 13\r\n\r\n"
+    );
+    write.write_all(request.as_bytes()).await?;
+
+    // 2️⃣ The server must switch protocols and prove it read our key
+    let mut stream = BufReader::new(read);
+    let mut status = String::new();
+    stream.read_line(&mut status).await?;
+    let mut accept = None;
+    loop {
+        let mut line = String::new();
+        if stream.read_line(&mut line).await? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("sec-websocket-accept") {
+                accept = Some(value.trim().to_string());
+            }
+        }
+    }
+    let refused = |message: String| io::Error::new(io::ErrorKind::ConnectionRefused, message);
+    if status.split_whitespace().nth(1) != Some("101") {
+        return Err(refused(format!(
+            "websocket upgrade refused: {}",
+            status.trim()
+        )));
+    }
+    let expected = STANDARD.encode(Sha1::digest(format!("{key}{ACCEPT_GUID}")));
+    if accept.as_deref() != Some(expected.as_str()) {
+        return Err(refused(
+            "invalid Sec-WebSocket-Accept in upgrade response".into(),
+        ));
+    }
+
+    let reader = WsReader {
+        stream,
+        partial: Vec::new(),
+        limit: MAX_MESSAGE,
+    };
+    Ok((reader, WsWriter { stream: write }))
+}
+
+impl WsReader {
+    /// Wait for the next message, joining fragmented ones
+    pub(crate) async fn next(&mut self) -> io::Result<Message> {
+        loop {
+            let (fin, opcode, payload) = self.frame().await?;
+            match opcode {
+                // Text, binary or continuation
+                0x0..=0x2 => {
+                    if (self.partial.len() + payload.len()) as u64 > self.limit {
+                        self.partial = Vec::new();
+                        return Err(too_large(self.limit));
+                    }
+                    self.partial.extend(payload);
+                    if fin {
+                        let message = std::mem::take(&mut self.partial);
+                        return String::from_utf8(message)
+                            .map(Message::Text)
+                            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+                    }
+                }
+                CLOSE => return Ok(Message::Close),
+                PING => return Ok(Message::Ping(payload)),
+                PONG => {}
+                opcode => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("unknown websocket opcode {opcode:#x}"),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// One frame as (final fragment, opcode, unmasked payload)
+    async fn frame(&mut self) -> io::Result<(bool, u8, Vec<u8>)> {
+        let mut head = [0; 2];
+        self.stream.read_exact(&mut head).await?;
+        let len = match head[1] & 0x7f {
+            126 => u64::from(self.stream.read_u16().await?),
+            127 => self.stream.read_u64().await?,
+            len => u64::from(len),
+        };
+        // Checked before allocating; fragments are checked again once joined
+        if len > self.limit {
+            return Err(too_large(self.limit));
+        }
+        let mask = if head[1] & 0x80 != 0 {
+            Some(self.stream.read_u32().await?.to_be_bytes())
+        } else {
+            None
+        };
+        let mut payload = vec![0; len as usize];
+        self.stream.read_exact(&mut payload).await?;
+        if let Some(mask) = mask {
+            apply_mask(&mut payload, mask);
+        }
+        Ok((head[0] & 0x80 != 0, head[0] & 0x0f, payload))
+    }
+}
+
+impl WsWriter {
+    /// Send a text message
+    pub(crate) async fn send_text(&mut self, text: &str) -> io::Result<()> {
+        self.send(TEXT, text.as_bytes()).await
+    }
+
+    /// Answer a [`Message::Ping`]
+    pub(crate) async fn pong(&mut self, payload: &[u8]) -> io::Result<()> {
+        self.send(PONG, payload).await
+    }
+
+    /// Client frames must be masked
+    async fn send(&mut self, opcode: u8, payload: &[u8]) -> io::Result<()> {
+        let mut frame = vec![0x80 | opcode];
+        match payload.len() {
+            len if len < 126 => frame.push(0x80 | len as u8),
+            len if len <= usize::from(u16::MAX) => {
+                frame.push(0x80 | 126);
+                frame.extend((len as u16).to_be_bytes());
+            }
+            len => {
+                frame.push(0x80 | 127);
+                frame.extend((len as u64).to_be_bytes());
+            }
+        }
+        let mask = (nonce() as u32).to_be_bytes();
+        frame.extend(mask);
+        let start = frame.len();
+        frame.extend(payload);
+        apply_mask(&mut frame[start..], mask);
+        self.stream.write_all(&frame).await
+    }
+}
+
+fn too_large(limit: u64) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("websocket message exceeds {limit} bytes"),
+    )
+}
+
+fn apply_mask(payload: &mut [u8], mask: [u8; 4]) {
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+}
+
+/// Unpredictable enough for handshake keys and frame masks, which only guard against
+/// caching proxies
+fn nonce() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    /// A client connected to a server on `host` that completed the handshake,
+    /// and the server's end of the connection
+    async fn pair(host: &str) -> (WsReader, WsWriter, TcpStream) {
+        let listener = TcpListener::bind((host, 0)).await.unwrap();
+        let url = match listener.local_addr().unwrap() {
+            std::net::SocketAddr::V4(addr) => format!("ws://{addr}/devtools/browser/1"),
+            std::net::SocketAddr::V6(addr) => {
+                format!("ws://[{}]:{}/devtools/browser/1", addr.ip(), addr.port())
+            }
+        };
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut stream = BufReader::new(stream);
+            let mut key = String::new();
+            loop {
+                let mut line = String::new();
+                stream.read_line(&mut line).await.unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some(value) = line.strip_prefix("Sec-WebSocket-Key:") {
+                    key = value.trim().to_string();
+                }
+            }
+            let accept = STANDARD.encode(Sha1::digest(format!("{key}{ACCEPT_GUID}")));
+            let mut stream = stream.into_inner();
+            stream
+                .write_all(
+                    format!(
+                        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\n\
+                         Connection: Upgrade\r\nSec-WebSocket-Accept: {accept}\r\n\r\n"
+                    )
+                    .as_bytes(),
+                )
+                .await
+                .unwrap();
+            stream
+        });
+        let (reader, writer) = connect(&url).await.unwrap();
+        (reader, writer, server.await.unwrap())
+    }
+
+    /// One client frame read by the server, as (header byte, unmasked payload)
+    async fn read_client_frame(server: &mut TcpStream) -> (u8, Vec<u8>) {
+        let mut head = [0; 2];
+        server.read_exact(&mut head).await.unwrap();
+        assert_eq!(head[1] & 0x80, 0x80, "client frames must be masked");
+        let len = match head[1] & 0x7f {
+            126 => u64::from(server.read_u16().await.unwrap()),
+            127 => server.read_u64().await.unwrap(),
+            len => u64::from(len),
+        };
+        let mask = server.read_u32().await.unwrap().to_be_bytes();
+        let mut payload = vec![0; len as usize];
+        server.read_exact(&mut payload).await.unwrap();
+        apply_mask(&mut payload, mask);
+        (head[0], payload)
+    }
+
+    #[tokio::test]
+    async fn encodes_masked_frames_of_every_length_form() {
+        let (_reader, mut writer, mut server) = pair("127.0.0.1").await;
+        for len in [0, 5, 125, 126, 65535, 65536] {
+            let text = "x".repeat(len);
+            writer.send_text(&text).await.unwrap();
+            let (head, payload) = read_client_frame(&mut server).await;
+            assert_eq!(head, 0x80 | TEXT);
+            assert_eq!(payload.len(), len);
+            assert!(payload == text.as_bytes());
+        }
+        writer.pong(b"beat").await.unwrap();
+        assert_eq!(
+            read_client_frame(&mut server).await,
+            (0x80 | PONG, b"beat".to_vec())
+        );
+    }
+
+    #[tokio::test]
+    async fn decodes_server_frames() {
+        let (mut reader, _writer, mut server) = pair("127.0.0.1").await;
+        let long = "y".repeat(300);
+        let mut frames = vec![0x81, 5];
+        frames.extend(b"hello");
+        frames.extend([0x81, 126]);
+        frames.extend(300u16.to_be_bytes());
+        frames.extend(long.as_bytes());
+        frames.extend([0x8A, 0]);
+        frames.extend([0x88, 0]);
+        server.write_all(&frames).await.unwrap();
+
+        assert!(matches!(reader.next().await.unwrap(), Message::Text(text) if text == "hello"));
+        assert!(matches!(reader.next().await.unwrap(), Message::Text(text) if text == long));
+        // The pong is skipped
+        assert!(matches!(reader.next().await.unwrap(), Message::Close));
+    }
+
+    #[tokio::test]
+    async fn joins_fragments_around_control_frames() {
+        let (mut reader, _writer, mut server) = pair("127.0.0.1").await;
+        let mut frames = vec![0x01, 3];
+        frames.extend(b"hel");
+        frames.extend([0x89, 1, b'p']);
+        frames.extend([0x80, 2]);
+        frames.extend(b"lo");
+        server.write_all(&frames).await.unwrap();
+
+        assert!(matches!(reader.next().await.unwrap(), Message::Ping(payload) if payload == b"p"));
+        assert!(matches!(reader.next().await.unwrap(), Message::Text(text) if text == "hello"));
+    }
+
+    #[tokio::test]
+    async fn unmasks_masked_server_frames() {
+        let (mut reader, _writer, mut server) = pair("127.0.0.1").await;
+        let mask = [1, 2, 3, 4];
+        let mut payload = b"hi".to_vec();
+        apply_mask(&mut payload, mask);
+        let mut frame = vec![0x81, 0x80 | 2];
+        frame.extend(mask);
+        frame.extend(payload);
+        server.write_all(&frame).await.unwrap();
+
+        assert!(matches!(reader.next().await.unwrap(), Message::Text(text) if text == "hi"));
+    }
+
+    #[tokio::test]
+    async fn limits_the_joined_message() {
+        let (mut reader, _writer, mut server) = pair("127.0.0.1").await;
+        reader.limit = 4;
+        let mut frames = vec![0x01, 3];
+        frames.extend(b"hel");
+        frames.extend([0x80, 2]);
+        frames.extend(b"lo");
+        server.write_all(&frames).await.unwrap();
+
+        let e = reader.next().await.unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        assert!(reader.partial.is_empty());
+    }
+
+    #[tokio::test]
+    async fn limits_a_frame_before_reading_it() {
+        let (mut reader, _writer, mut server) = pair("127.0.0.1").await;
+        let mut frame = vec![0x81, 127];
+        frame.extend(u64::MAX.to_be_bytes());
+        server.write_all(&frame).await.unwrap();
+
+        let e = reader.next().await.unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_opcodes() {
+        let (mut reader, _writer, mut server) = pair("127.0.0.1").await;
+        server.write_all(&[0x83, 0]).await.unwrap();
+
+        let e = reader.next().await.unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn connects_to_ipv6_hosts() {
+        let (mut reader, _writer, mut server) = pair("::1").await;
+        server.write_all(&[0x81, 2, b'o', b'k']).await.unwrap();
+
+        assert!(matches!(reader.next().await.unwrap(), Message::Text(text) if text == "ok"));
+    }
+
+    #[tokio::test]
+    async fn refuses_a_wrong_accept_key() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = [0; 1024];
+            let _ = stream.read(&mut request).await;
+            let _ = stream
+                .write_all(
+                    b"HTTP/1.1 101 Switching Protocols\r\nSec-WebSocket-Accept: forged\r\n\r\n",
+                )
+                .await;
+        });
+
+        let e = connect(&url).await.err().unwrap();
+        assert_eq!(e.kind(), io::ErrorKind::ConnectionRefused);
+    }
+
+    #[tokio::test]
+    async fn rejects_other_schemes() {
+        let e = connect("wss://127.0.0.1/").await.err().unwrap();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidInput);
+    }
+}
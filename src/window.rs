@@ -0,0 +1,145 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    error::{DriverError, Result},
+    Element, Session,
+};
+
+/// Kind of browsing context opened by [`Session::new_window`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WindowType {
+    Tab,
+    Window,
+}
+
+impl WindowType {
+    /// `"tab"` or `"window"`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WindowType::Tab => "tab",
+            WindowType::Window => "window",
+        }
+    }
+}
+
+/// Position and outer size of a browser window, in CSS pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WindowRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Frame picked by [`Session::switch_to_frame`]
+#[derive(Debug, Clone)]
+pub enum Frame {
+    /// The n-th frame of the current document, counting from 0
+    Index(u16),
+    /// This `<iframe>` or `<frame>` element
+    Element(Element),
+    /// The frame containing the current one
+    Parent,
+}
+
+impl Session {
+    /// Open a new tab or window and return its handle; the session stays on the current one
+    pub async fn new_window(&self, kind: WindowType) -> Result<String> {
+        let opened = self
+            .command(
+                Method::POST,
+                "/window/new",
+                Some(json!({ "type": kind.as_str() })),
+            )
+            .await?;
+        Ok(opened["handle"].as_str().unwrap_or_default().to_string())
+    }
+
+    /// Handle of the window commands currently go to
+    pub async fn window_handle(&self) -> Result<String> {
+        let handle = self.command(Method::GET, "/window", None).await?;
+        Ok(handle.as_str().unwrap_or_default().to_string())
+    }
+
+    /// Handles of all open tabs and windows
+    pub async fn window_handles(&self) -> Result<Vec<String>> {
+        let handles = self.command(Method::GET, "/window/handles", None).await?;
+        Ok(handles_of(&handles))
+    }
+
+    /// Send further commands to the window with this handle
+    pub async fn switch_to_window(&self, handle: &str) -> Result<()> {
+        self.command(Method::POST, "/window", Some(json!({ "handle": handle })))
+            .await
+            .map(drop)
+    }
+
+    /// Close the current window, returning the handles still open.
+    ///
+    /// * Switch to one of them before sending further commands; closing the last window
+    ///   ends the session.
+    pub async fn close_window(&self) -> Result<Vec<String>> {
+        let handles = self.command(Method::DELETE, "/window", None).await?;
+        Ok(handles_of(&handles))
+    }
+
+    /// Send further commands to `frame`, e.g. an embedded payment widget.
+    ///
+    /// * Elements found before switching can't be used inside the frame, and vice versa.
+    pub async fn switch_to_frame(&self, frame: Frame) -> Result<()> {
+        let (path, body) = match frame {
+            Frame::Index(index) => ("/frame", json!({ "id": index })),
+            Frame::Element(element) => ("/frame", json!({ "id": element })),
+            Frame::Parent => ("/frame/parent", json!({})),
+        };
+        self.command(Method::POST, path, Some(body)).await.map(drop)
+    }
+
+    /// Send further commands to the top-level document of the current window again
+    pub async fn switch_to_default_content(&self) -> Result<()> {
+        self.command(Method::POST, "/frame", Some(json!({ "id": null })))
+            .await
+            .map(drop)
+    }
+
+    /// Position and size of the current window
+    pub async fn window_rect(&self) -> Result<WindowRect> {
+        let rect = self.command(Method::GET, "/window/rect", None).await?;
+        rect_of(rect)
+    }
+
+    /// Move and resize the current window, returning the rect it ended up with, e.g. for
+    /// screenshots of a fixed viewport
+    pub async fn set_window_rect(&self, rect: WindowRect) -> Result<WindowRect> {
+        let body = serde_json::to_value(rect).expect("a rect is serializable");
+        let rect = self
+            .command(Method::POST, "/window/rect", Some(body))
+            .await?;
+        rect_of(rect)
+    }
+
+    /// Maximize the current window, returning its new rect
+    pub async fn maximize(&self) -> Result<WindowRect> {
+        let rect = self.command(Method::POST, "/window/maximize", None).await?;
+        rect_of(rect)
+    }
+}
+
+fn handles_of(handles: &Value) -> Vec<String> {
+    handles
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|handle| handle.as_str().map(str::to_string))
+        .collect()
+}
+
+fn rect_of(rect: Value) -> Result<WindowRect> {
+    serde_json::from_value(rect).map_err(|e| DriverError::WebDriver {
+        command: "window rect".into(),
+        error: "invalid response".into(),
+        message: e.to_string(),
+    })
+}